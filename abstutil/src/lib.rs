@@ -100,6 +100,36 @@ pub fn path_camera_state(map_name: &str) -> String {
     format!("../data/player/camera_state/{}.json", map_name)
 }
 
+pub fn path_recent_maps() -> String {
+    "../data/player/recent_maps.json".to_string()
+}
+
+pub fn path_player_screenshot(map_name: &str, timestamp: &str) -> String {
+    format!("../data/player/screenshots/{}/{}.png", map_name, timestamp)
+}
+
+pub fn path_player_timeline(map_name: &str, name: &str) -> String {
+    format!("../data/player/timelines/{}/{}.png", map_name, name)
+}
+
+pub fn path_signal_timing(map_name: &str, name: &str) -> String {
+    format!("../data/player/signal_timings/{}/{}.json", map_name, name)
+}
+pub fn path_all_signal_timings(map_name: &str) -> String {
+    format!("../data/player/signal_timings/{}", map_name)
+}
+
+pub fn path_intersection_delays(map_name: &str, intersection: usize) -> String {
+    format!(
+        "../data/player/intersection_delays/{}/{}.csv",
+        map_name, intersection
+    )
+}
+
+pub fn path_tutorial_log() -> String {
+    "../data/player/tutorial_log.txt".to_string()
+}
+
 pub fn path_edits(map_name: &str, edits_name: &str) -> String {
     format!("../data/player/edits/{}/{}.json", map_name, edits_name)
 }
@@ -113,6 +143,19 @@ pub fn path_save(map_name: &str, edits_name: &str, run_name: &str, time: String)
         map_name, edits_name, run_name, time
     )
 }
+pub fn path_sim_export(map_name: &str, edits_name: &str, run_name: &str, time: String) -> String {
+    format!(
+        "../data/player/sim_exports/{}/{}_{}/{}.bin",
+        map_name, edits_name, run_name, time
+    )
+}
+pub fn path_all_sim_exports(map_name: &str, edits_name: &str, run_name: &str) -> String {
+    format!(
+        "../data/player/sim_exports/{}/{}_{}",
+        map_name, edits_name, run_name
+    )
+}
+
 pub fn path_all_saves(map_name: &str, edits_name: &str, run_name: &str) -> String {
     format!(
         "../data/player/saves/{}/{}_{}",
@@ -94,6 +94,7 @@ impl App {
                             pts: (0..(self.elapsed.inner_seconds() as usize))
                                 .map(|s| (Time::START_OF_DAY + Duration::seconds(s as f64), s))
                                 .collect(),
+                            dashed: false,
                         },
                         Series {
                             label: "Quadratic".to_string(),
@@ -103,6 +104,7 @@ impl App {
                                     (Time::START_OF_DAY + Duration::seconds(s as f64), s.pow(2))
                                 })
                                 .collect(),
+                            dashed: false,
                         },
                     ],
                     PlotOptions {
@@ -112,6 +114,7 @@ impl App {
                         max_x: Some(Time::START_OF_DAY + self.elapsed),
                         max_y: None,
                         disabled: HashSet::new(),
+                        band: None,
                     },
                 ),
             ])
@@ -95,6 +95,19 @@ impl Assets {
             .insert(key_scale_factor(scale_factor), (geom, bounds));
     }
 
+    // Text sizes are expressed relative to text::DEFAULT_FONT_SIZE, so changing the default size
+    // scales all text.
+    pub fn scale_font_size(&self, size: usize) -> usize {
+        size * *self.default_font_size.borrow() / text::DEFAULT_FONT_SIZE
+    }
+
+    pub fn set_default_font_size(&self, size: usize) {
+        *self.default_font_size.borrow_mut() = size;
+        self.text_cache.borrow_mut().clear();
+        *self.default_line_height.borrow_mut() =
+            self.line_height(text::DEFAULT_FONT, *self.default_font_size.borrow());
+    }
+
     pub fn set_scale_factor(&self, scale_factor: f64) {
         *self.scale_factor.borrow_mut() = scale_factor;
         self.text_cache.borrow_mut().clear();
@@ -269,6 +269,18 @@ impl PrerenderInnards {
             .set_window_icon(Some(icon));
     }
 
+    // Whatever was last drawn
+    pub fn read_frame(&self) -> Result<image::RgbaImage, String> {
+        let raw: glium::texture::RawImage2d<u8> = self
+            .display
+            .read_front_buffer()
+            .map_err(|err| format!("Couldn't read the screen: {:?}", err))?;
+        let img = image::RgbaImage::from_raw(raw.width, raw.height, raw.data.into_owned())
+            .ok_or_else(|| "Screen has the wrong size".to_string())?;
+        // OpenGL starts at the bottom
+        Ok(image::imageops::flip_vertical(&img))
+    }
+
     pub fn monitor_scale_factor(&self) -> f64 {
         self.display.gl_window().window().scale_factor()
     }
@@ -319,6 +319,28 @@ impl PrerenderInnards {
         self.windowed_context.window().set_window_icon(Some(icon));
     }
 
+    // Whatever was last drawn
+    pub fn read_frame(&self) -> Result<image::RgbaImage, String> {
+        let size = self.windowed_context.window().inner_size();
+        let mut pixels = vec![0; (size.width * size.height * 4) as usize];
+        unsafe {
+            self.gl.read_buffer(glow::FRONT);
+            self.gl.read_pixels(
+                0,
+                0,
+                size.width as i32,
+                size.height as i32,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                &mut pixels,
+            );
+        }
+        let img = image::RgbaImage::from_raw(size.width, size.height, pixels)
+            .ok_or_else(|| "Screen has the wrong size".to_string())?;
+        // OpenGL starts at the bottom
+        Ok(image::imageops::flip_vertical(&img))
+    }
+
     pub fn monitor_scale_factor(&self) -> f64 {
         self.windowed_context.window().scale_factor()
     }
@@ -334,6 +334,10 @@ impl PrerenderInnards {
         self.window.set_window_icon(Some(icon));
     }
 
+    pub fn read_frame(&self) -> Result<image::RgbaImage, String> {
+        Err("Can't take screenshots on the web".to_string())
+    }
+
     pub fn monitor_scale_factor(&self) -> f64 {
         self.window.scale_factor()
     }
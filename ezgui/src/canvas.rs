@@ -1,7 +1,7 @@
 use crate::assets::Assets;
-use crate::{hotkey, Key, ScreenDims, ScreenPt, ScreenRectangle, UserInput};
+use crate::{hotkey, lctrl, Key, ScreenDims, ScreenPt, ScreenRectangle, UserInput};
 use abstutil::Timer;
-use geom::{Bounds, Pt2D};
+use geom::{Bounds, Duration, Pt2D};
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 
@@ -45,6 +45,14 @@ pub struct Canvas {
     // Kind of just ezgui state awkwardly stuck here...
     pub(crate) lctrl_held: bool,
     pub(crate) lshift_held: bool,
+    // Only one panel at a time has keyboard focus
+    pub(crate) focused_panel: Option<usize>,
+    // The name of the focused widget, so a panel rebuilt from scratch can take focus back
+    pub(crate) focused_widget: Option<String>,
+
+    // Stats about the previous frame drawn, for profiling
+    pub last_frame_time: Duration,
+    pub last_frame_draw_calls: usize,
 }
 
 impl Canvas {
@@ -74,6 +82,11 @@ impl Canvas {
 
             lctrl_held: false,
             lshift_held: false,
+            focused_panel: None,
+            focused_widget: None,
+
+            last_frame_time: Duration::ZERO,
+            last_frame_draw_calls: 0,
         }
     }
 
@@ -128,6 +141,11 @@ impl Canvas {
             }
         }
 
+        // For anybody who got lost. Not Home, since panels use that to scroll to the top.
+        if input.new_was_pressed(&lctrl(Key::Num0).unwrap()) {
+            self.zoom_to_fit();
+        }
+
         // If we start the drag on the map and move the mouse off the map, keep dragging.
         if let Some((click, orig)) = self.drag_canvas_from {
             let pt = self.get_cursor();
@@ -181,6 +199,12 @@ impl Canvas {
         self.cam_y = ((self.cam_zoom / old_zoom) * (focus.1 + self.cam_y)) - focus.1;
     }
 
+    // Frame the entire map.
+    pub fn zoom_to_fit(&mut self) {
+        self.cam_zoom = self.min_zoom();
+        self.center_on_map_pt(Pt2D::new(self.map_dims.0 / 2.0, self.map_dims.1 / 2.0));
+    }
+
     pub(crate) fn start_drawing(&self) {
         self.covered_areas.borrow_mut().clear();
     }
@@ -162,6 +162,10 @@ pub enum Key {
     RightArrow,
     UpArrow,
     DownArrow,
+    PageUp,
+    PageDown,
+    Home,
+    End,
     F1,
     F2,
     F3,
@@ -250,6 +254,10 @@ impl Key {
             | Key::RightArrow
             | Key::UpArrow
             | Key::DownArrow
+            | Key::PageUp
+            | Key::PageDown
+            | Key::Home
+            | Key::End
             | Key::F1
             | Key::F2
             | Key::F3
@@ -280,6 +288,10 @@ impl Key {
             Key::RightArrow => "→ arrow".to_string(),
             Key::UpArrow => "↑".to_string(),
             Key::DownArrow => "↓".to_string(),
+            Key::PageUp => "Page Up".to_string(),
+            Key::PageDown => "Page Down".to_string(),
+            Key::Home => "Home".to_string(),
+            Key::End => "End".to_string(),
             Key::F1 => "F1".to_string(),
             Key::F2 => "F2".to_string(),
             Key::F3 => "F3".to_string(),
@@ -360,6 +372,10 @@ impl Key {
             VirtualKeyCode::Right => Key::RightArrow,
             VirtualKeyCode::Up => Key::UpArrow,
             VirtualKeyCode::Down => Key::DownArrow,
+            VirtualKeyCode::PageUp => Key::PageUp,
+            VirtualKeyCode::PageDown => Key::PageDown,
+            VirtualKeyCode::Home => Key::Home,
+            VirtualKeyCode::End => Key::End,
             VirtualKeyCode::F1 => Key::F1,
             VirtualKeyCode::F2 => Key::F2,
             VirtualKeyCode::F3 => Key::F3,
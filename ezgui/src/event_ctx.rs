@@ -99,6 +99,14 @@ impl<'a> EventCtx<'a> {
         *self.prerender.assets.scale_factor.borrow()
     }
 
+    pub fn set_default_font_size(&self, size: usize) {
+        self.prerender.assets.set_default_font_size(size)
+    }
+
+    pub fn get_default_font_size(&self) -> usize {
+        *self.prerender.assets.default_font_size.borrow()
+    }
+
     pub fn monitor_scale_factor(&self) -> f64 {
         self.prerender.inner.monitor_scale_factor()
     }
@@ -118,6 +118,69 @@ impl GeomBatch {
         }
     }
 
+    /// Rasterizes the batch and saves it as a PNG file, using one pixel per unit. Hatched polygons
+    /// are skipped, and gradients use their first color.
+    pub fn save_png(&self, path: &str) -> Result<(), String> {
+        let mut bounds = Bounds::new();
+        for (_, poly) in &self.list {
+            bounds.union(poly.get_bounds());
+        }
+        let width = bounds.width().ceil().max(1.0) as u32;
+        let height = bounds.height().ceil().max(1.0) as u32;
+        let mut img = image::RgbaImage::new(width, height);
+        for (color, poly) in &self.list {
+            let color = match color {
+                FancyColor::RGBA(c) => *c,
+                FancyColor::Hatching => {
+                    continue;
+                }
+                FancyColor::LinearGradient(lg) => lg.stops[0].1,
+            };
+            for tri in poly.triangles() {
+                let pts: Vec<(f64, f64)> = vec![tri.pt1, tri.pt2, tri.pt3]
+                    .into_iter()
+                    .map(|pt| (pt.x() - bounds.min_x, pt.y() - bounds.min_y))
+                    .collect();
+                let area = edge(pts[0], pts[1], pts[2]);
+                if area == 0.0 {
+                    continue;
+                }
+                let x1 = pts
+                    .iter()
+                    .map(|pt| pt.0)
+                    .fold(std::f64::MAX, f64::min)
+                    .max(0.0);
+                let x2 = pts.iter().map(|pt| pt.0).fold(std::f64::MIN, f64::max);
+                let y1 = pts
+                    .iter()
+                    .map(|pt| pt.1)
+                    .fold(std::f64::MAX, f64::min)
+                    .max(0.0);
+                let y2 = pts.iter().map(|pt| pt.1).fold(std::f64::MIN, f64::max);
+                for y in (y1.floor() as u32)..(y2.ceil() as u32).min(height) {
+                    for x in (x1.floor() as u32)..(x2.ceil() as u32).min(width) {
+                        // Sample the center of each pixel
+                        let pt = (f64::from(x) + 0.5, f64::from(y) + 0.5);
+                        let w1 = edge(pts[1], pts[2], pt) / area;
+                        let w2 = edge(pts[2], pts[0], pt) / area;
+                        let w3 = edge(pts[0], pts[1], pt) / area;
+                        if w1 < 0.0 || w2 < 0.0 || w3 < 0.0 {
+                            continue;
+                        }
+                        blend(img.get_pixel_mut(x, y), color);
+                    }
+                }
+            }
+        }
+
+        if let Some(dir) = std::path::Path::new(path).parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|err| format!("Couldn't create {}: {}", dir.display(), err))?;
+        }
+        img.save(path)
+            .map_err(|err| format!("Couldn't write {}: {}", path, err))
+    }
+
     /// Returns a batch containing a parsed SVG string.
     pub fn from_svg_contents(raw: Vec<u8>) -> GeomBatch {
         let mut batch = GeomBatch::new();
@@ -202,3 +265,23 @@ impl RewriteColor {
         }
     }
 }
+
+// Positive when pt is to the left of the line from a to b, scaled by twice the triangle's area.
+fn edge(a: (f64, f64), b: (f64, f64), pt: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (pt.1 - a.1) - (b.1 - a.1) * (pt.0 - a.0)
+}
+
+// Draws color over an existing pixel.
+fn blend(pixel: &mut image::Rgba<u8>, color: Color) {
+    let dst_a = f32::from(pixel[3]) / 255.0;
+    let out_a = color.a + dst_a * (1.0 - color.a);
+    if out_a == 0.0 {
+        return;
+    }
+    for (idx, src) in [color.r, color.g, color.b].iter().enumerate() {
+        let dst = f32::from(pixel[idx]) / 255.0;
+        let out = (src * color.a + dst * dst_a * (1.0 - color.a)) / out_a;
+        pixel[idx] = (out * 255.0).round() as u8;
+    }
+    pixel[3] = (out_a * 255.0).round() as u8;
+}
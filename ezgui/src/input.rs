@@ -121,7 +121,10 @@ impl UserInput {
         None
     }
 
-    pub(crate) fn get_mouse_scroll(&self) -> Option<(f64, f64)> {
+    pub fn get_mouse_scroll(&self) -> Option<(f64, f64)> {
+        if self.event_consumed {
+            return None;
+        }
         if let Event::MouseWheelScroll(dx, dy) = self.event {
             return Some((dx, dy));
         }
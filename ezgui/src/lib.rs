@@ -68,7 +68,7 @@ pub(crate) use crate::widgets::dropdown::Dropdown;
 pub use crate::widgets::filler::Filler;
 pub use crate::widgets::just_draw::DrawWithTooltips;
 pub(crate) use crate::widgets::just_draw::JustDraw;
-pub use crate::widgets::line_plot::{LinePlot, PlotOptions, Series};
+pub use crate::widgets::line_plot::{Band, LinePlot, PlotOptions, Series};
 pub(crate) use crate::widgets::menu::Menu;
 pub use crate::widgets::persistent_split::PersistentSplit;
 pub use crate::widgets::scatter_plot::ScatterPlot;
@@ -1,12 +1,13 @@
 use crate::widgets::containers::{Container, Nothing};
 use crate::{
-    AreaSlider, Autocomplete, Button, Checkbox, Choice, Color, Drawable, Dropdown, EventCtx,
-    GeomBatch, GfxCtx, HorizontalAlignment, JustDraw, Menu, Outcome, PersistentSplit, RewriteColor,
-    ScreenDims, ScreenPt, ScreenRectangle, Slider, Spinner, TextBox, VerticalAlignment, WidgetImpl,
-    WidgetOutput,
+    hotkey, AreaSlider, Autocomplete, Button, Checkbox, Choice, Color, Drawable, Dropdown,
+    EventCtx, GeomBatch, GfxCtx, HorizontalAlignment, JustDraw, Key, Menu, Outcome,
+    PersistentSplit, RewriteColor, ScreenDims, ScreenPt, ScreenRectangle, Slider, Spinner, TextBox,
+    VerticalAlignment, WidgetImpl, WidgetOutput,
 };
-use geom::{Distance, Polygon};
+use geom::{Distance, Duration, Polygon};
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use stretch::geometry::{Rect, Size};
 use stretch::node::{Node, Stretch};
 use stretch::number::Number;
@@ -459,6 +460,55 @@ impl Widget {
         None
     }
 
+    // Buttons, checkboxes, and spinners can be reached with Tab, in depth-first order.
+    fn focusable_rects(&self, rects: &mut Vec<ScreenRectangle>) {
+        if self.widget.downcast_ref::<Button>().is_some()
+            || self.widget.downcast_ref::<Checkbox>().is_some()
+            || self.widget.downcast_ref::<Spinner>().is_some()
+        {
+            rects.push(self.rect.clone());
+        } else if let Some(container) = self.widget.downcast_ref::<Container>() {
+            for w in &container.members {
+                w.focusable_rects(rects);
+            }
+        }
+    }
+
+    // Same order as focusable_rects. Buttons are named by their action.
+    fn focusable_names(&self, names: &mut Vec<Option<String>>) {
+        if let Some(btn) = self.widget.downcast_ref::<Button>() {
+            names.push(Some(btn.action.clone()));
+        } else if self.widget.downcast_ref::<Checkbox>().is_some()
+            || self.widget.downcast_ref::<Spinner>().is_some()
+        {
+            names.push(self.id.clone());
+        } else if let Some(container) = self.widget.downcast_ref::<Container>() {
+            for w in &container.members {
+                w.focusable_names(names);
+            }
+        }
+    }
+
+    // Counts down idx until reaching the focused widget, then returns it.
+    fn nth_focusable(&mut self, idx: &mut usize) -> Option<&mut Widget> {
+        if self.widget.downcast_ref::<Button>().is_some()
+            || self.widget.downcast_ref::<Checkbox>().is_some()
+            || self.widget.downcast_ref::<Spinner>().is_some()
+        {
+            if *idx == 0 {
+                return Some(self);
+            }
+            *idx -= 1;
+        } else if let Some(container) = self.widget.downcast_mut::<Container>() {
+            for w in &mut container.members {
+                if let Some(found) = w.nth_focusable(idx) {
+                    return Some(found);
+                }
+            }
+        }
+        None
+    }
+
     fn restore(&mut self, ctx: &mut EventCtx, prev: &Composite) {
         if let Some(container) = self.widget.downcast_mut::<Container>() {
             for w in &mut container.members {
@@ -525,6 +575,8 @@ enum Dims {
     ExactPercent(f64, f64),
 }
 
+static NEXT_COMPOSITE_ID: AtomicUsize = AtomicUsize::new(0);
+
 pub struct CompositeBuilder {
     top_level: Widget,
     horiz: HorizontalAlignment,
@@ -534,6 +586,12 @@ pub struct CompositeBuilder {
 
 pub struct Composite {
     top_level: Widget,
+    // Identifies this panel in Canvas::focused_panel. Kept across restore(); a panel rebuilt
+    // without restore() takes focus back by matching Canvas::focused_widget instead.
+    id: usize,
+    // Index into the focusable widgets, for keyboard navigation. Only meaningful while this panel
+    // is the focused one.
+    focus: Option<usize>,
     horiz: HorizontalAlignment,
     vert: VerticalAlignment,
     dims: Dims,
@@ -651,6 +709,23 @@ impl Composite {
     }
 
     pub fn event(&mut self, ctx: &mut EventCtx) -> Option<Outcome> {
+        if ctx.input.is_window_resized() {
+            self.recompute_layout(ctx, false);
+        }
+
+        if let Some(outcome) = self.focus_event(ctx) {
+            return Some(outcome);
+        }
+
+        let before = self.scroll_offset();
+        let mut output = WidgetOutput {
+            redo_layout: false,
+            outcome: None,
+        };
+        self.top_level.widget.event(ctx, &mut output);
+
+        // Widgets go first, so that scrolling over something like a slider doesn't also scroll the
+        // panel
         if (self.scrollable_x || self.scrollable_y)
             && ctx
                 .canvas
@@ -671,18 +746,28 @@ impl Composite {
                 };
                 self.set_scroll_offset(ctx, (x_offset, y_offset));
             }
-        }
 
-        if ctx.input.is_window_resized() {
-            self.recompute_layout(ctx, false);
+            // Page through long panels with the keyboard
+            if self.scrollable_y {
+                let (x, y) = self.scroll_offset();
+                let page = self.container_dims.height;
+                let max = (self.contents_dims.height - self.container_dims.height).max(0.0);
+                let new_y = if ctx.input.new_was_pressed(&hotkey(Key::PageUp).unwrap()) {
+                    Some(y - page)
+                } else if ctx.input.new_was_pressed(&hotkey(Key::PageDown).unwrap()) {
+                    Some(y + page)
+                } else if ctx.input.new_was_pressed(&hotkey(Key::Home).unwrap()) {
+                    Some(0.0)
+                } else if ctx.input.new_was_pressed(&hotkey(Key::End).unwrap()) {
+                    Some(max)
+                } else {
+                    None
+                };
+                if let Some(y) = new_y {
+                    self.set_scroll_offset(ctx, (x, y));
+                }
+            }
         }
-
-        let before = self.scroll_offset();
-        let mut output = WidgetOutput {
-            redo_layout: false,
-            outcome: None,
-        };
-        self.top_level.widget.event(ctx, &mut output);
         if self.scroll_offset() != before || output.redo_layout {
             self.recompute_layout(ctx, true);
         }
@@ -690,6 +775,85 @@ impl Composite {
         output.outcome
     }
 
+    // Tab moves focus forwards through this panel; once it runs off the end, the Tab falls through
+    // to the next panel handling events. Shift-Tab moves backwards. Enter activates the focused
+    // button or checkbox, and the arrow keys adjust a focused spinner.
+    fn focus_event(&mut self, ctx: &mut EventCtx) -> Option<Outcome> {
+        // Another panel took focus
+        if ctx.canvas.focused_panel != Some(self.id) {
+            self.focus = None;
+        }
+
+        // Using the mouse drops keyboard focus
+        if ctx.input.left_mouse_button_pressed() {
+            self.set_focus(ctx, None);
+            return None;
+        }
+
+        let mut rects = Vec::new();
+        self.top_level.focusable_rects(&mut rects);
+        if rects.is_empty() {
+            self.set_focus(ctx, None);
+            return None;
+        }
+
+        if ctx.input.new_was_pressed(&hotkey(Key::Tab).unwrap()) {
+            let backwards = ctx.canvas.lshift_held;
+            let focus = match (self.focus, backwards) {
+                (None, false) => Some(0),
+                (None, true) => Some(rects.len() - 1),
+                (Some(idx), false) if idx + 1 < rects.len() => Some(idx + 1),
+                (Some(_), false) => {
+                    // Let the next panel take focus
+                    ctx.input.unconsume_event();
+                    None
+                }
+                (Some(idx), true) => Some(idx.max(1) - 1),
+            };
+            self.set_focus(ctx, focus);
+            return None;
+        }
+
+        let mut idx = self.focus?;
+        if idx >= rects.len() {
+            self.set_focus(ctx, None);
+            return None;
+        }
+        let widget = self.top_level.nth_focusable(&mut idx).unwrap();
+        // Space is left alone; it usually pauses the simulation.
+        let activate = hotkey(Key::Enter).unwrap();
+        if let Some(btn) = widget.widget.downcast_ref::<Button>() {
+            if ctx.input.new_was_pressed(&activate) {
+                return Some(Outcome::Clicked(btn.action.clone()));
+            }
+        } else if let Some(cb) = widget.widget.downcast_mut::<Checkbox>() {
+            if ctx.input.new_was_pressed(&activate) {
+                cb.toggle();
+                self.recompute_layout(ctx, true);
+            }
+        } else if let Some(spinner) = widget.widget.downcast_mut::<Spinner>() {
+            if ctx.input.new_was_pressed(&hotkey(Key::UpArrow).unwrap()) {
+                spinner.increase();
+            } else if ctx.input.new_was_pressed(&hotkey(Key::DownArrow).unwrap()) {
+                spinner.decrease();
+            }
+        }
+        None
+    }
+
+    fn set_focus(&mut self, ctx: &mut EventCtx, focus: Option<usize>) {
+        self.focus = focus;
+        if let Some(idx) = focus {
+            let mut names = Vec::new();
+            self.top_level.focusable_names(&mut names);
+            ctx.canvas.focused_panel = Some(self.id);
+            ctx.canvas.focused_widget = names.into_iter().nth(idx).and_then(|name| name);
+        } else if ctx.canvas.focused_panel == Some(self.id) {
+            ctx.canvas.focused_panel = None;
+            ctx.canvas.focused_widget = None;
+        }
+    }
+
     pub fn draw(&self, g: &mut GfxCtx) {
         if let Some(ref rect) = self.clip_rect {
             g.enable_clipping(rect.clone());
@@ -719,6 +883,21 @@ impl Composite {
         g.unfork();
 
         self.top_level.draw(g);
+        if let Some(idx) = self
+            .focus
+            .filter(|_| g.canvas.focused_panel == Some(self.id))
+        {
+            let mut rects = Vec::new();
+            self.top_level.focusable_rects(&mut rects);
+            if let Some(rect) = rects.get(idx) {
+                g.fork_screenspace();
+                g.draw_polygon(
+                    Color::YELLOW,
+                    &rect.to_polygon().to_outline(Distance::meters(2.0)),
+                );
+                g.unfork();
+            }
+        }
         if self.scrollable_x || self.scrollable_y {
             g.disable_clipping();
 
@@ -741,6 +920,12 @@ impl Composite {
 
     pub fn restore(&mut self, ctx: &mut EventCtx, prev: &Composite) {
         self.set_scroll_offset(ctx, prev.scroll_offset());
+        // build() might've already taken focus back under the new ID
+        if ctx.canvas.focused_panel == Some(self.id) {
+            ctx.canvas.focused_panel = Some(prev.id);
+        }
+        self.id = prev.id;
+        self.focus = prev.focus.or(self.focus);
 
         self.top_level.restore(ctx, &prev);
 
@@ -786,6 +971,9 @@ impl Composite {
     pub fn spinner(&self, name: &str) -> usize {
         self.find::<Spinner>(name).current
     }
+    pub fn duration_spinner(&self, name: &str) -> Duration {
+        Duration::seconds(self.find::<Spinner>(name).current as f64)
+    }
 
     pub fn dropdown_value<T: 'static + PartialEq + Clone>(&self, name: &str) -> T {
         self.find::<Dropdown<T>>(name).current_value()
@@ -869,6 +1057,8 @@ impl CompositeBuilder {
     pub fn build(self, ctx: &mut EventCtx) -> Composite {
         let mut c = Composite {
             top_level: self.top_level,
+            id: NEXT_COMPOSITE_ID.fetch_add(1, Ordering::Relaxed),
+            focus: None,
 
             horiz: self.horiz,
             vert: self.vert,
@@ -938,6 +1128,16 @@ impl CompositeBuilder {
             c.clip_rect = Some(ScreenRectangle::top_left(top_left, c.container_dims));
         }
 
+        // If the focused widget belonged to a panel that's being rebuilt, like after an undo, pick
+        // focus back up.
+        if let Some(name) = ctx.canvas.focused_widget.clone() {
+            let mut names = Vec::new();
+            c.top_level.focusable_names(&mut names);
+            if let Some(idx) = names.iter().position(|n| n.as_ref() == Some(&name)) {
+                c.set_focus(ctx, Some(idx));
+            }
+        }
+
         // Just trigger error if a button is double-defined
         c.get_all_click_actions();
         // Let all widgets initially respond to the mouse being somewhere
@@ -1,5 +1,5 @@
 use crate::assets::Assets;
-use crate::tools::screenshot::screenshot_everything;
+use crate::tools::screenshot::{screenshot_current, screenshot_everything};
 use crate::{text, Canvas, Event, EventCtx, GfxCtx, Key, Prerender, Style, UserInput};
 use geom::Duration;
 use image::{GenericImageView, Pixel};
@@ -29,6 +29,10 @@ pub enum EventLoopMode {
         max_x: f64,
         max_y: f64,
     },
+    // Draw the current frame once and save it to this path
+    ScreenCaptureCurrentShot {
+        path: String,
+    },
 }
 
 pub(crate) struct State<G: GUI> {
@@ -143,28 +147,34 @@ impl<G: GUI> State<G> {
 
     // Returns naming hint. Logically consumes the number of uploads.
     pub(crate) fn draw(&mut self, prerender: &Prerender, screenshot: bool) -> Option<String> {
-        let mut g = GfxCtx::new(prerender, &self.canvas, &self.style, screenshot);
+        let started = Instant::now();
+        let (naming_hint, num_draw_calls) = {
+            let mut g = GfxCtx::new(prerender, &self.canvas, &self.style, screenshot);
 
-        self.canvas.start_drawing();
+            self.canvas.start_drawing();
 
-        if let Err(err) = panic::catch_unwind(panic::AssertUnwindSafe(|| {
-            self.gui.draw(&mut g);
-        })) {
-            self.gui.dump_before_abort(&self.canvas);
-            panic::resume_unwind(err);
-        }
-        let naming_hint = g.naming_hint.take();
-
-        if false {
-            println!(
-                "----- {} uploads, {} draw calls, {} forks -----",
-                g.get_num_uploads(),
-                g.num_draw_calls,
-                g.num_forks
-            );
-        }
+            if let Err(err) = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                self.gui.draw(&mut g);
+            })) {
+                self.gui.dump_before_abort(&self.canvas);
+                panic::resume_unwind(err);
+            }
+            let naming_hint = g.naming_hint.take();
 
-        g.inner.finish();
+            if false {
+                println!(
+                    "----- {} uploads, {} draw calls, {} forks -----",
+                    g.get_num_uploads(),
+                    g.num_draw_calls,
+                    g.num_forks
+                );
+            }
+
+            g.inner.finish();
+            (naming_hint, g.num_draw_calls)
+        };
+        self.canvas.last_frame_time = Duration::realtime_elapsed(started);
+        self.canvas.last_frame_draw_calls = num_draw_calls;
         naming_hint
     }
 }
@@ -344,6 +354,12 @@ pub fn run<G: 'static + GUI, F: FnOnce(&mut EventCtx) -> G>(settings: Settings,
             } => {
                 screenshot_everything(&mut state, &dir, &prerender, zoom, max_x, max_y);
             }
+            EventLoopMode::ScreenCaptureCurrentShot { path } => {
+                match screenshot_current(&mut state, &path, &prerender) {
+                    Ok(()) => println!("Saved screenshot to {}", path),
+                    Err(err) => println!("Screenshot failed: {}", err),
+                }
+            }
         }
     });
 }
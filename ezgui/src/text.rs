@@ -252,7 +252,8 @@ impl Text {
         for (line_color, line) in self.lines {
             // Assume size doesn't change mid-line. Always use this fixed line height per font
             // size.
-            let line_height = assets.line_height(line[0].font, line[0].size);
+            let line_height =
+                assets.line_height(line[0].font, assets.scale_font_size(line[0].size));
 
             let line_batch = render_line(line, tolerance, assets);
             let line_dims = if line_batch.is_empty() {
@@ -382,7 +383,7 @@ fn render_line(spans: Vec<TextSpan>, tolerance: f32, assets: &Assets) -> GeomBat
     write!(
         &mut svg,
         r##"<text x="0" y="0" font-size="{}" {}>"##,
-        spans[0].size,
+        assets.scale_font_size(spans[0].size),
         match spans[0].font {
             Font::BungeeInlineRegular => "font-family=\"Bungee Inline\"",
             Font::BungeeRegular => "font-family=\"Bungee\"",
@@ -52,6 +52,23 @@ pub(crate) fn screenshot_everything<G: GUI>(
     finish(dir_path, filenames, num_tiles_x, num_tiles_y);
 }
 
+pub(crate) fn screenshot_current<G: GUI>(
+    state: &mut State<G>,
+    path: &str,
+    prerender: &Prerender,
+) -> Result<(), String> {
+    if let Some(dir) = std::path::Path::new(path).parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Couldn't create {}: {}", dir.display(), err))?;
+    }
+    state.draw(prerender, false);
+    prerender
+        .inner
+        .read_frame()?
+        .save(path)
+        .map_err(|err| format!("Couldn't write {}: {}", path, err))
+}
+
 fn screencap(filename: &str) -> bool {
     if !process::Command::new("scrot")
         .args(&[
@@ -88,6 +88,14 @@ impl Checkbox {
     }
 }
 
+impl Checkbox {
+    pub(crate) fn toggle(&mut self) {
+        std::mem::swap(&mut self.btn, &mut self.other_btn);
+        self.btn.set_pos(self.other_btn.top_left);
+        self.enabled = !self.enabled;
+    }
+}
+
 impl WidgetImpl for Checkbox {
     fn get_dims(&self) -> ScreenDims {
         self.btn.get_dims()
@@ -100,9 +108,7 @@ impl WidgetImpl for Checkbox {
     fn event(&mut self, ctx: &mut EventCtx, output: &mut WidgetOutput) {
         self.btn.event(ctx, output);
         if output.outcome.take().is_some() {
-            std::mem::swap(&mut self.btn, &mut self.other_btn);
-            self.btn.set_pos(self.other_btn.top_left);
-            self.enabled = !self.enabled;
+            self.toggle();
             output.redo_layout = true;
         }
     }
@@ -24,6 +24,16 @@ pub struct PlotOptions<T: Yvalue<T>> {
     pub max_x: Option<Time>,
     pub max_y: Option<T>,
     pub disabled: HashSet<String>,
+    // Only supported by LinePlot
+    pub band: Option<Band<T>>,
+}
+
+// Shades the area between two curves, like the min and max of some distribution. Both curves must
+// have the same X values.
+pub struct Band<T> {
+    pub color: Color,
+    pub lower: Vec<(Time, T)>,
+    pub upper: Vec<(Time, T)>,
 }
 
 impl<T: Yvalue<T>> PlotOptions<T> {
@@ -33,6 +43,7 @@ impl<T: Yvalue<T>> PlotOptions<T> {
             max_x: None,
             max_y: None,
             disabled: HashSet::new(),
+            band: None,
         }
     }
 
@@ -42,6 +53,7 @@ impl<T: Yvalue<T>> PlotOptions<T> {
             max_x: None,
             max_y: None,
             disabled: HashSet::new(),
+            band: None,
         }
     }
 }
@@ -66,6 +78,11 @@ impl<T: Yvalue<T>> LinePlot<T> {
                 .unwrap_or(Time::START_OF_DAY)
         });
         let max_y = opts.max_y.unwrap_or_else(|| {
+            let max_band = opts
+                .band
+                .as_ref()
+                .and_then(|b| b.upper.iter().map(|(_, value)| *value).max())
+                .unwrap_or(T::zero());
             series
                 .iter()
                 .map(|s| {
@@ -77,6 +94,7 @@ impl<T: Yvalue<T>> LinePlot<T> {
                 })
                 .max()
                 .unwrap_or(T::zero())
+                .max(max_band)
         });
 
         // TODO Tuned to fit the info panel. Instead these should somehow stretch to fill their
@@ -127,6 +145,33 @@ impl<T: Yvalue<T>> LinePlot<T> {
             }
         }
 
+        if let Some(band) = opts.band {
+            if max_x != Time::START_OF_DAY && band.lower.len() >= 2 {
+                assert_eq!(band.lower.len(), band.upper.len());
+                let to_pt = |(t, y): (Time, T)| {
+                    Pt2D::new(
+                        t.to_percent(max_x) * width,
+                        (1.0 - y.to_percent(max_y)) * height,
+                    )
+                };
+                // The first n points are the upper curve, then the lower. Make a triangle strip
+                // between them.
+                let n = band.upper.len();
+                let mut indices = Vec::new();
+                for i in 0..n - 1 {
+                    indices.extend(vec![i, i + 1, n + i]);
+                    indices.extend(vec![n + i, i + 1, n + i + 1]);
+                }
+                let pts = band
+                    .upper
+                    .into_iter()
+                    .chain(band.lower.into_iter())
+                    .map(to_pt)
+                    .collect();
+                batch.push(band.color, Polygon::precomputed(pts, indices));
+            }
+        }
+
         let mut closest = FindClosest::new(&Bounds::from(&vec![
             Pt2D::new(0.0, 0.0),
             Pt2D::new(width, height),
@@ -149,7 +194,19 @@ impl<T: Yvalue<T>> LinePlot<T> {
             pts.dedup();
             if pts.len() >= 2 {
                 closest.add(s.label.clone(), &pts);
-                batch.push(s.color, thick_lineseries(pts, Distance::meters(5.0)));
+                if s.dashed {
+                    // The series may cross back over itself, so don't use PolyLine::new
+                    batch.extend(
+                        s.color,
+                        PolyLine::unchecked_new(pts).exact_dashed_polygons(
+                            Distance::meters(5.0),
+                            Distance::meters(10.0),
+                            Distance::meters(5.0),
+                        ),
+                    );
+                } else {
+                    batch.push(s.color, thick_lineseries(pts, Distance::meters(5.0)));
+                }
             }
         }
 
@@ -306,6 +363,8 @@ pub struct Series<T> {
     pub color: Color,
     // X-axis is time. Assume this is sorted by X.
     pub pts: Vec<(Time, T)>,
+    // Useful to distinguish a baseline from the current data when both share a label and color.
+    pub dashed: bool,
 }
 
 pub fn make_legend<T: Yvalue<T>>(
@@ -1,6 +1,6 @@
 use crate::{
-    Color, Drawable, EventCtx, GeomBatch, GfxCtx, ScreenDims, ScreenPt, ScreenRectangle, Widget,
-    WidgetImpl, WidgetOutput,
+    Color, Drawable, EventCtx, GeomBatch, GfxCtx, Line, ScreenDims, ScreenPt, ScreenRectangle,
+    Text, Widget, WidgetImpl, WidgetOutput,
 };
 use geom::{Circle, Distance, Polygon, Pt2D};
 
@@ -20,6 +20,8 @@ pub struct Slider {
 }
 
 const BG_CROSS_AXIS_LEN: f64 = 20.0;
+// How much one tick of the mouse wheel changes a slider
+const SCROLL_STEP: f64 = 0.05;
 
 impl Slider {
     pub fn horizontal(
@@ -165,6 +167,17 @@ impl Slider {
             }
             return self.mouse_on_slider != old;
         }
+        if let Some((_, dy)) = ctx.input.get_mouse_scroll() {
+            if cursor_on_bar(ctx, self.top_left, self.dims) {
+                // Don't also scroll the panel or zoom the map
+                ctx.input.consume_event();
+                // Scrolling up means up for a vertical scrollbar, but increasing for a horizontal
+                // slider
+                let dy = if self.horiz { dy } else { -dy };
+                self.current_percent = (self.current_percent + dy * SCROLL_STEP).min(1.0).max(0.0);
+                return true;
+            }
+        }
         if ctx.input.left_mouse_button_pressed() {
             if self.mouse_on_slider {
                 self.dragging = true;
@@ -216,6 +229,10 @@ impl WidgetImpl for Slider {
         // this stays for now.
         g.canvas
             .mark_covered_area(ScreenRectangle::top_left(self.top_left, self.dims));
+
+        if self.dragging {
+            g.draw_mouse_tooltip(percent_tooltip(self.current_percent));
+        }
     }
 }
 
@@ -224,6 +241,8 @@ pub struct AreaSlider {
     current_percent: f64,
     mouse_on_slider: bool,
     dragging: bool,
+    // Describes the value at some percent, shown while dragging
+    describe: Box<dyn Fn(f64) -> String>,
 
     width: f64,
     draw: Drawable,
@@ -233,11 +252,17 @@ pub struct AreaSlider {
 }
 
 impl AreaSlider {
-    pub fn new(ctx: &EventCtx, width: f64, current_percent: f64) -> Widget {
+    pub fn new(
+        ctx: &EventCtx,
+        width: f64,
+        current_percent: f64,
+        describe: Box<dyn Fn(f64) -> String>,
+    ) -> Widget {
         let mut s = AreaSlider {
             current_percent,
             mouse_on_slider: false,
             dragging: false,
+            describe,
 
             width,
             draw: ctx.upload(GeomBatch::new()),
@@ -338,6 +363,13 @@ impl AreaSlider {
             }
             return self.mouse_on_slider != old;
         }
+        if let Some((_, dy)) = ctx.input.get_mouse_scroll() {
+            if cursor_on_bar(ctx, self.top_left, self.dims) {
+                ctx.input.consume_event();
+                self.current_percent = (self.current_percent + dy * SCROLL_STEP).min(1.0).max(0.0);
+                return true;
+            }
+        }
         if ctx.input.left_mouse_button_pressed() {
             if self.mouse_on_slider {
                 self.dragging = true;
@@ -379,5 +411,24 @@ impl WidgetImpl for AreaSlider {
 
     fn draw(&self, g: &mut GfxCtx) {
         g.redraw_at(self.top_left, &self.draw);
+
+        if self.dragging {
+            g.draw_mouse_tooltip(Text::from(Line((self.describe)(self.current_percent))));
+        }
     }
 }
+
+fn cursor_on_bar(ctx: &EventCtx, top_left: ScreenPt, dims: ScreenDims) -> bool {
+    ctx.canvas
+        .get_cursor_in_screen_space()
+        .map(|pt| {
+            Polygon::rectangle(dims.width, dims.height)
+                .translate(top_left.x, top_left.y)
+                .contains_pt(pt.to_pt())
+        })
+        .unwrap_or(false)
+}
+
+fn percent_tooltip(percent: f64) -> Text {
+    Text::from(Line(format!("{}%", (percent * 100.0).round() as usize)))
+}
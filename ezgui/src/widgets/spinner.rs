@@ -2,10 +2,12 @@ use crate::{
     text, Btn, Button, EventCtx, GeomBatch, GfxCtx, Line, ScreenDims, ScreenPt, ScreenRectangle,
     Text, Widget, WidgetImpl, WidgetOutput,
 };
-use geom::{Polygon, Pt2D};
+use geom::{Duration, Polygon, Pt2D};
 
 // TODO MAX_CHAR_WIDTH is a hardcoded nonsense value
 const TEXT_WIDTH: f64 = 2.0 * text::MAX_CHAR_WIDTH;
+// Enough for "mm:ss"
+const DURATION_TEXT_WIDTH: f64 = 4.0 * text::MAX_CHAR_WIDTH;
 
 // TODO Allow text entry
 // TODO Allow click and hold
@@ -13,7 +15,10 @@ const TEXT_WIDTH: f64 = 2.0 * text::MAX_CHAR_WIDTH;
 pub struct Spinner {
     low: usize,
     high: usize,
+    step: usize,
     pub current: usize,
+    describe: fn(usize) -> String,
+    text_width: f64,
 
     up: Button,
     down: Button,
@@ -24,6 +29,37 @@ pub struct Spinner {
 
 impl Spinner {
     pub fn new(ctx: &EventCtx, (low, high): (usize, usize), current: usize) -> Widget {
+        Spinner::build(ctx, low, high, 1, current, |x| x.to_string(), TEXT_WIDTH)
+    }
+
+    // Steps through whole seconds, shown as mm:ss. Use Composite::duration_spinner to get the
+    // value.
+    pub fn duration(
+        ctx: &EventCtx,
+        (low, high): (Duration, Duration),
+        step: Duration,
+        current: Duration,
+    ) -> Widget {
+        Spinner::build(
+            ctx,
+            low.inner_seconds() as usize,
+            high.inner_seconds() as usize,
+            step.inner_seconds() as usize,
+            current.inner_seconds() as usize,
+            |secs| format!("{}:{:02}", secs / 60, secs % 60),
+            DURATION_TEXT_WIDTH,
+        )
+    }
+
+    fn build(
+        ctx: &EventCtx,
+        low: usize,
+        high: usize,
+        step: usize,
+        current: usize,
+        describe: fn(usize) -> String,
+        text_width: f64,
+    ) -> Widget {
         let up = Btn::text_fg("▲")
             .build(ctx, "increase value", None)
             .take_btn();
@@ -32,14 +68,17 @@ impl Spinner {
             .take_btn();
 
         let dims = ScreenDims::new(
-            TEXT_WIDTH + up.get_dims().width,
+            text_width + up.get_dims().width,
             up.get_dims().height + down.get_dims().height,
         );
 
         Widget::new(Box::new(Spinner {
             low,
             high,
+            step,
             current,
+            describe,
+            text_width,
 
             up,
             down,
@@ -50,6 +89,16 @@ impl Spinner {
     }
 }
 
+impl Spinner {
+    pub(crate) fn increase(&mut self) {
+        self.current = (self.current + self.step).min(self.high);
+    }
+
+    pub(crate) fn decrease(&mut self) {
+        self.current = self.current.saturating_sub(self.step).max(self.low);
+    }
+}
+
 impl WidgetImpl for Spinner {
     fn get_dims(&self) -> ScreenDims {
         self.dims
@@ -60,9 +109,9 @@ impl WidgetImpl for Spinner {
         // here and use that. Wait, why can't we? ...
         self.top_left = top_left;
         self.up
-            .set_pos(ScreenPt::new(top_left.x + TEXT_WIDTH, top_left.y));
+            .set_pos(ScreenPt::new(top_left.x + self.text_width, top_left.y));
         self.down.set_pos(ScreenPt::new(
-            top_left.x + TEXT_WIDTH,
+            top_left.x + self.text_width,
             top_left.y + self.up.get_dims().height,
         ));
     }
@@ -70,29 +119,25 @@ impl WidgetImpl for Spinner {
     fn event(&mut self, ctx: &mut EventCtx, output: &mut WidgetOutput) {
         self.up.event(ctx, output);
         if output.outcome.take().is_some() {
-            if self.current != self.high {
-                self.current += 1;
-            }
+            self.increase();
             ctx.no_op_event(true, |ctx| self.up.event(ctx, output));
             return;
         }
 
         self.down.event(ctx, output);
         if output.outcome.take().is_some() {
-            if self.current != self.low {
-                self.current -= 1;
-            }
+            self.decrease();
             ctx.no_op_event(true, |ctx| self.down.event(ctx, output));
         }
 
         if let Some(pt) = ctx.canvas.get_cursor_in_screen_space() {
             if ScreenRectangle::top_left(self.top_left, self.dims).contains(pt) {
                 if let Some((_, dy)) = ctx.input.get_mouse_scroll() {
-                    if dy > 0.0 && self.current != self.high {
-                        self.current += 1;
+                    if dy > 0.0 {
+                        self.increase();
                     }
-                    if dy < 0.0 && self.current != self.low {
-                        self.current -= 1;
+                    if dy < 0.0 {
+                        self.decrease();
                     }
                 }
             }
@@ -106,9 +151,9 @@ impl WidgetImpl for Spinner {
             Polygon::rounded_rectangle(self.dims.width, self.dims.height, Some(5.0)),
         )]);
         batch.append(
-            Text::from(Line(self.current.to_string()))
+            Text::from(Line((self.describe)(self.current)))
                 .render_to_batch(g.prerender)
-                .centered_on(Pt2D::new(TEXT_WIDTH / 2.0, self.dims.height / 2.0)),
+                .centered_on(Pt2D::new(self.text_width / 2.0, self.dims.height / 2.0)),
         );
         let draw = g.upload(batch);
         g.redraw_at(self.top_left, &draw);
@@ -3,14 +3,15 @@ use crate::colors::ColorScheme;
 use crate::helpers::ID;
 use crate::layer::Layer;
 use crate::options::Options;
+use crate::pregame::{recent_maps, remember_recent_map};
 use crate::render::{AgentCache, AgentColorScheme, DrawMap, DrawOptions, Renderable};
 use crate::sandbox::{GameplayMode, TutorialState};
 use abstutil::{MeasureMemory, Timer};
 use ezgui::{EventCtx, GfxCtx, Prerender};
 use geom::{Bounds, Circle, Distance, Duration, Pt2D, Time};
-use map_model::{IntersectionID, Map, Traversable};
+use map_model::{IntersectionID, Map, MapEdits, Traversable};
 use rand::seq::SliceRandom;
-use sim::{Analytics, GetDrawAgents, Sim, SimCallback, SimFlags};
+use sim::{Analytics, GetDrawAgents, PersonID, Sim, SimCallback, SimFlags};
 use std::collections::BTreeMap;
 
 pub struct App {
@@ -28,6 +29,8 @@ pub struct App {
 
     pub per_obj: PerObjectActions,
     pub layer: Option<Box<dyn Layer>>,
+    // The layer's legend can be collapsed to see more of the map.
+    pub hide_layer_panel: bool,
 
     // Static data that lasts the entire session. Use sparingly.
     pub session: SessionState,
@@ -79,7 +82,11 @@ impl App {
             opts,
             per_obj: PerObjectActions::new(),
             layer: None,
-            session: SessionState::empty(),
+            hide_layer_panel: false,
+            session: SessionState {
+                recent_maps: recent_maps(),
+                ..SessionState::empty()
+            },
             suspended_sim: None,
         }
     }
@@ -141,12 +148,17 @@ impl App {
     }
 
     pub fn switch_map(&mut self, ctx: &mut EventCtx, load: String) {
+        if let Some(name) = self.session.camera_mode.take() {
+            ctx.canvas.save_camera_state(&name);
+        }
         ctx.canvas.save_camera_state(self.primary.map.get_name());
         let mut flags = self.primary.current_flags.clone();
         flags.sim_flags.load = load;
+        flags.sim_flags.load_savestate = None;
         let session = std::mem::replace(&mut self.session, SessionState::empty());
         *self = App::new(flags, self.opts.clone(), ctx, false);
         self.session = session;
+        remember_recent_map(self);
     }
 
     pub fn draw(
@@ -430,7 +442,9 @@ impl App {
 
         for on in agents_on {
             for obj in agents.get(on) {
-                borrows.push(obj);
+                if self.agent_cs.shows(&obj.get_id()) {
+                    borrows.push(obj);
+                }
             }
         }
 
@@ -511,6 +525,14 @@ pub struct PerMap {
     pub sim_cb: Option<Box<dyn SimCallback>>,
     // If we ever left edit mode and resumed without restarting from midnight, this is true.
     pub dirty_from_edits: bool,
+    // Only used in freeform mode. The people created by the last manual spawn.
+    pub undo_spawn: Option<Vec<PersonID>>,
+    // The community proposal loaded from the main menu, if any, so the player's own changes can
+    // be told apart from it.
+    pub proposal: Option<MapEdits>,
+    // A debug lock from the speed controls, stronger than pausing. Nothing may advance the sim
+    // until it's unfrozen.
+    pub frozen: bool,
 }
 
 impl PerMap {
@@ -533,12 +555,16 @@ impl PerMap {
             last_warped_from: None,
             sim_cb: None,
             dirty_from_edits: false,
+            undo_spawn: None,
+            proposal: None,
+            frozen: false,
         }
     }
 
     // Returns whatever was there
     pub fn clear_sim(&mut self) -> Sim {
         self.dirty_from_edits = false;
+        self.undo_spawn = None;
         std::mem::replace(
             &mut self.sim,
             Sim::new(
@@ -554,6 +580,10 @@ impl PerMap {
 pub struct SessionState {
     pub tutorial: Option<TutorialState>,
     pub high_scores: BTreeMap<GameplayMode, Vec<HighScore>>,
+    // The camera state name of the gameplay mode currently controlling the camera
+    pub camera_mode: Option<String>,
+    // Most recently played maps first, matching what's saved in the player's data
+    pub recent_maps: Vec<String>,
 }
 
 impl SessionState {
@@ -561,6 +591,8 @@ impl SessionState {
         SessionState {
             tutorial: None,
             high_scores: BTreeMap::new(),
+            camera_mode: None,
+            recent_maps: Vec::new(),
         }
     }
 }
@@ -294,6 +294,17 @@ impl<'a> ColorNetwork<'a> {
         }
     }
 
+    pub fn ranked_buildings(&mut self, counter: Counter<BuildingID>, scale: &ColorScale) {
+        let bldgs = counter.sorted_asc();
+        let len = bldgs.len() as f64;
+        for (idx, list) in bldgs.into_iter().enumerate() {
+            let color = scale.eval((idx as f64) / len);
+            for b in list {
+                self.add_b(b, color);
+            }
+        }
+    }
+
     pub fn build(self, ctx: &mut EventCtx) -> (Drawable, Drawable) {
         (ctx.upload(self.unzoomed), ctx.upload(self.zoomed))
     }
@@ -5,7 +5,7 @@ use crate::layer::PickLayer;
 use abstutil::clamp;
 use ezgui::{
     hotkey, Btn, Checkbox, Color, Composite, EventCtx, Filler, GeomBatch, GfxCtx,
-    HorizontalAlignment, Key, Line, Outcome, ScreenDims, ScreenPt, VerticalAlignment, Widget,
+    HorizontalAlignment, Key, Outcome, ScreenDims, ScreenPt, VerticalAlignment, Widget,
 };
 use geom::{Distance, Polygon, Pt2D, Ring};
 
@@ -16,6 +16,8 @@ pub struct Minimap {
     // Update panel when other things change
     zoomed: bool,
     layer: bool,
+    // Keep the minimap centered on the camera
+    follow: bool,
 
     // [0, 3], with 0 meaning the most unzoomed
     zoom_lvl: usize,
@@ -33,9 +35,10 @@ impl Minimap {
         let base_zoom = 0.15 * ctx.canvas.window_width / bounds.width().min(bounds.height());
         let mut m = Minimap {
             dragging: false,
-            composite: make_minimap_panel(ctx, app, 0),
+            composite: make_minimap_panel(ctx, app, 0, false),
             zoomed: ctx.canvas.cam_zoom >= app.opts.min_zoom_for_detail,
             layer: app.layer.is_none(),
+            follow: false,
 
             zoom_lvl: 0,
             base_zoom,
@@ -63,7 +66,7 @@ impl Minimap {
         let zoom_speed: f64 = 2.0;
         self.zoom_lvl = zoom_lvl;
         self.zoom = self.base_zoom * zoom_speed.powi(self.zoom_lvl as i32);
-        self.composite = make_minimap_panel(ctx, app, self.zoom_lvl);
+        self.composite = make_minimap_panel(ctx, app, self.zoom_lvl, self.follow);
 
         // Find the new offset
         let map_center = ctx.canvas.center_to_map_pt();
@@ -95,11 +98,13 @@ impl Minimap {
 
             self.zoomed = zoomed;
             self.layer = layer;
-            self.composite = make_minimap_panel(ctx, app, self.zoom_lvl);
+            self.composite = make_minimap_panel(ctx, app, self.zoom_lvl, self.follow);
 
             if just_zoomed_in {
                 self.recenter(ctx, app);
             }
+        } else if self.zoomed && !self.dragging && self.follow {
+            self.recenter(ctx, app);
         } else if self.zoomed && !self.dragging {
             // If either corner of the cursor is out of bounds on the minimap, recenter.
             // TODO This means clicking the pan buttons while along the boundary won't work.
@@ -186,6 +191,11 @@ impl Minimap {
                 x if x == "change layers" => {
                     return Some(Transition::Push(PickLayer::pick(ctx, app)));
                 }
+                x if x.starts_with("show only ") => {
+                    app.agent_cs.show_only(&x["show only ".len()..]);
+                    self.composite = make_minimap_panel(ctx, app, self.zoom_lvl, self.follow);
+                    return None;
+                }
                 _ => unreachable!(),
             },
             None => {}
@@ -200,7 +210,29 @@ impl Minimap {
         }
         if let Some(label) = toggle {
             app.agent_cs.toggle(label);
-            self.composite = make_minimap_panel(ctx, app, self.zoom_lvl);
+            self.composite = make_minimap_panel(ctx, app, self.zoom_lvl, self.follow);
+        }
+        if self.zoomed && self.composite.is_checked("follow camera") != self.follow {
+            self.follow = !self.follow;
+            if self.follow {
+                self.recenter(ctx, app);
+            }
+        }
+
+        // Scrolling while hovering on the minimap zooms it
+        if self.zoomed
+            && self
+                .composite
+                .rect_of("minimap")
+                .contains(ctx.canvas.get_cursor())
+        {
+            if let Some((_, dy)) = ctx.input.get_mouse_scroll() {
+                if dy > 0.0 && self.zoom_lvl != 3 {
+                    self.set_zoom(ctx, app, self.zoom_lvl + 1);
+                } else if dy < 0.0 && self.zoom_lvl != 0 {
+                    self.set_zoom(ctx, app, self.zoom_lvl - 1);
+                }
+            }
         }
 
         if self.zoomed {
@@ -308,7 +340,7 @@ impl Minimap {
     }
 }
 
-fn make_minimap_panel(ctx: &mut EventCtx, app: &App, zoom_lvl: usize) -> Composite {
+fn make_minimap_panel(ctx: &mut EventCtx, app: &App, zoom_lvl: usize, follow: bool) -> Composite {
     if ctx.canvas.cam_zoom < app.opts.min_zoom_for_detail {
         return Composite::new(Widget::row(vec![
             make_tool_panel(ctx, app).align_right().margin_right(16),
@@ -381,6 +413,7 @@ fn make_minimap_panel(ctx: &mut EventCtx, app: &App, zoom_lvl: usize) -> Composi
         Widget::col(vec![
             Widget::row(vec![minimap_controls, zoom_col]),
             make_horiz_viz_panel(ctx, app),
+            Checkbox::text(ctx, "follow camera", None, follow).margin_above(7),
         ])
         .padding(7)
         .bg(app.cs.panel_bg),
@@ -425,7 +458,12 @@ fn make_horiz_viz_panel(ctx: &mut EventCtx, app: &App) -> Widget {
     let mut row = Vec::new();
     for (label, color, enabled) in &app.agent_cs.rows {
         row.push(Checkbox::colored(ctx, label, *color, *enabled).margin_right(8));
-        row.push(Line(label).draw(ctx).margin_right(24));
+        // Clicking the label hides every other type of agent
+        row.push(
+            Btn::plaintext(label)
+                .build(ctx, format!("show only {}", label), None)
+                .margin_right(24),
+        );
     }
     let last = row.pop().unwrap();
     row.push(last.margin_right(0));
@@ -438,7 +476,7 @@ fn make_vert_viz_panel(ctx: &mut EventCtx, app: &App) -> Widget {
     for (label, color, enabled) in &app.agent_cs.rows {
         let mut row = Vec::new();
         row.push(Checkbox::colored(ctx, label, *color, *enabled).margin_right(8));
-        row.push(Line(label).draw(ctx));
+        row.push(Btn::plaintext(label).build(ctx, format!("show only {}", label), None));
         col.push(Widget::row(row).margin_below(7));
     }
     let last = col.pop().unwrap();
@@ -1,12 +1,12 @@
 use crate::app::App;
 use crate::common::Warping;
-use crate::game::{State, Transition};
+use crate::game::{State, Transition, WizardState};
 use crate::helpers::ID;
 use ezgui::{
-    hotkey, Autocomplete, Btn, Color, Composite, Drawable, EventCtx, GeomBatch, GfxCtx, Key, Line,
-    Outcome, Text, Widget,
+    hotkey, Autocomplete, Btn, Choice, Color, Composite, Drawable, EventCtx, GeomBatch, GfxCtx,
+    Key, Line, Outcome, Text, Widget,
 };
-use map_model::RoadID;
+use map_model::{BuildingID, RoadID};
 use std::collections::HashSet;
 
 // TODO Canonicalize names, handling abbreviations like east/e and street/st
@@ -25,6 +25,9 @@ impl Navigator {
                             .build(ctx, "close", hotkey(Key::Escape))
                             .align_right(),
                     ]),
+                    Btn::text_fg("Search for an address instead")
+                        .build(ctx, "search addresses", None)
+                        .margin_below(5),
                     Autocomplete::new(
                         ctx,
                         app.primary
@@ -50,6 +53,9 @@ impl State for Navigator {
                 "close" => {
                     return Transition::Pop;
                 }
+                "search addresses" => {
+                    return Transition::Replace(SearchBuildings::new(ctx, app));
+                }
                 _ => unreachable!(),
             },
             None => {}
@@ -196,3 +202,92 @@ impl State for CrossStreet {
         self.composite.draw(g);
     }
 }
+
+struct SearchBuildings {
+    composite: Composite,
+}
+
+impl SearchBuildings {
+    fn new(ctx: &mut EventCtx, app: &App) -> Box<dyn State> {
+        Box::new(SearchBuildings {
+            composite: Composite::new(
+                Widget::col(vec![
+                    Widget::row(vec![
+                        Line("Enter an address").small_heading().draw(ctx),
+                        Btn::text_fg("X")
+                            .build(ctx, "close", hotkey(Key::Escape))
+                            .align_right(),
+                    ]),
+                    Autocomplete::new(
+                        ctx,
+                        app.primary
+                            .map
+                            .all_buildings()
+                            .iter()
+                            .filter(|b| !b.address.starts_with("???"))
+                            .map(|b| (b.address.clone(), b.id))
+                            .collect(),
+                    )
+                    .named("address"),
+                ])
+                .bg(app.cs.panel_bg),
+            )
+            .build(ctx),
+        })
+    }
+}
+
+impl State for SearchBuildings {
+    fn event(&mut self, ctx: &mut EventCtx, app: &mut App) -> Transition {
+        match self.composite.event(ctx) {
+            Some(Outcome::Clicked(x)) => match x.as_ref() {
+                "close" => {
+                    return Transition::Pop;
+                }
+                _ => unreachable!(),
+            },
+            None => {}
+        }
+        if let Some(bldgs) = self.composite.autocomplete_done("address") {
+            if bldgs.is_empty() {
+                return Transition::Pop;
+            }
+            if bldgs.len() == 1 {
+                return Transition::Replace(warp_to_bldg(ctx, app, bldgs[0]));
+            }
+            // Ambiguous, so let the player pick
+            return Transition::Replace(WizardState::new(Box::new(move |wiz, ctx, app| {
+                let (_, b) = wiz.wrap(ctx).choose("Which building?", || {
+                    let mut choices: Vec<(String, BuildingID)> = bldgs
+                        .iter()
+                        .map(|b| (app.primary.map.get_b(*b).address.clone(), *b))
+                        .collect();
+                    choices.sort();
+                    Choice::from(choices)
+                })?;
+                Some(Transition::Replace(warp_to_bldg(ctx, app, b)))
+            })));
+        }
+
+        if self.composite.clicked_outside(ctx) {
+            return Transition::Pop;
+        }
+
+        Transition::Keep
+    }
+
+    fn draw(&self, g: &mut GfxCtx, app: &App) {
+        State::grey_out_map(g, app);
+        self.composite.draw(g);
+    }
+}
+
+fn warp_to_bldg(ctx: &mut EventCtx, app: &mut App, b: BuildingID) -> Box<dyn State> {
+    Warping::new(
+        ctx,
+        app.primary.map.get_b(b).label_center,
+        Some(app.opts.min_zoom_for_detail),
+        Some(ID::Building(b)),
+        &mut app.primary,
+    )
+}
@@ -3,19 +3,23 @@ mod objects;
 mod polygons;
 
 use crate::app::{App, ShowLayers, ShowObject};
-use crate::common::{tool_panel, CommonState, ContextualActions};
+use crate::common::{tool_panel, CommonState, ContextualActions, Warping};
 use crate::game::{msg, DrawBaselayer, State, Transition, WizardState};
-use crate::helpers::ID;
+use crate::helpers::{color_for_mode, ID};
 use crate::managed::{WrappedComposite, WrappedOutcome};
 use crate::render::{calculate_corners, DrawOptions};
 use abstutil::Timer;
 use ezgui::{
-    hotkey, lctrl, Btn, Checkbox, Color, Composite, Drawable, EventCtx, EventLoopMode, GeomBatch,
-    GfxCtx, HorizontalAlignment, Key, Line, Outcome, Text, VerticalAlignment, Widget, Wizard,
+    hotkey, lctrl, Btn, Checkbox, Choice, Color, Composite, Drawable, EventCtx, EventLoopMode,
+    GeomBatch, GfxCtx, HorizontalAlignment, Key, Line, Outcome, Text, VerticalAlignment, Widget,
+    Wizard,
 };
-use geom::Pt2D;
-use map_model::{ControlTrafficSignal, NORMAL_LANE_THICKNESS};
-use sim::{AgentID, Sim, TripID};
+use geom::{ArrowCap, Pt2D};
+use map_model::{
+    AreaID, BuildingID, ControlTrafficSignal, IntersectionID, LaneID, LaneType, ParkingLotID,
+    RoadID, NORMAL_LANE_THICKNESS,
+};
+use sim::{AgentID, PedestrianID, Sim, TripID};
 use std::collections::HashSet;
 
 pub struct DebugMode {
@@ -26,7 +30,9 @@ pub struct DebugMode {
     hidden: HashSet<ID>,
     layers: ShowLayers,
     search_results: Option<SearchResults>,
+    lane_type_highlight: Option<(LaneType, usize, Drawable)>,
     all_routes: Option<(usize, Drawable)>,
+    blackhole_redirects: Option<(usize, Drawable)>,
 
     highlighted_agents: Option<(ID, Drawable)>,
 }
@@ -51,16 +57,23 @@ impl DebugMode {
                     Checkbox::text(ctx, "show labels", hotkey(Key::Num5), false).margin_below(5),
                     Checkbox::text(ctx, "show route for all agents", hotkey(Key::R), false)
                         .margin_below(5),
+                    Checkbox::text(ctx, "show parking blackhole redirects", None, false)
+                        .margin_below(5),
                     Widget::col(
                         vec![
                             (lctrl(Key::H), "unhide everything"),
                             (None, "screenshot everything"),
                             (hotkey(Key::Slash), "search OSM metadata"),
                             (lctrl(Key::Slash), "clear OSM search results"),
+                            (None, "highlight lanes by type"),
+                            (None, "clear lane type highlight"),
+                            (None, "search for an object by ID"),
                             (hotkey(Key::O), "save sim state"),
                             (hotkey(Key::Y), "load previous sim state"),
                             (hotkey(Key::U), "load next sim state"),
                             (None, "pick a savestate to load"),
+                            (None, "export sim state"),
+                            (None, "pick an exported sim state to load"),
                             (None, "find bad traffic signals"),
                         ]
                         .into_iter()
@@ -81,7 +94,9 @@ impl DebugMode {
             hidden: HashSet::new(),
             layers: ShowLayers::new(),
             search_results: None,
+            lane_type_highlight: None,
             all_routes: None,
+            blackhole_redirects: None,
             highlighted_agents: None,
         }
     }
@@ -97,12 +112,25 @@ impl DebugMode {
                 results.query, results.num_matches
             )));
         }
+        if let Some((lt, n, _)) = self.lane_type_highlight {
+            txt.add(Line(format!(
+                "Highlighting {} {}s",
+                abstutil::prettyprint_usize(n),
+                lt.short_name()
+            )));
+        }
         if let Some((n, _)) = self.all_routes {
             txt.add(Line(format!(
                 "Showing {} routes",
                 abstutil::prettyprint_usize(n)
             )));
         }
+        if let Some((n, _)) = self.blackhole_redirects {
+            txt.add(Line(format!(
+                "Showing {} parking blackhole redirects",
+                abstutil::prettyprint_usize(n)
+            )));
+        }
         self.composite
             .replace(ctx, "current info", txt.draw(ctx).named("current info"));
     }
@@ -181,6 +209,17 @@ impl State for DebugMode {
                 "pick a savestate to load" => {
                     return Transition::Push(WizardState::new(Box::new(load_savestate)));
                 }
+                "export sim state" => {
+                    let path =
+                        ctx.loading_screen("export sim state", |_, _| app.primary.sim.export());
+                    return Transition::Push(msg(
+                        "Exported sim state",
+                        vec![format!("Saved to {}", path)],
+                    ));
+                }
+                "pick an exported sim state to load" => {
+                    return Transition::Push(WizardState::new(Box::new(load_export)));
+                }
                 "unhide everything" => {
                     self.hidden.clear();
                     app.primary.current_selection = app.calculate_current_selection(
@@ -200,6 +239,16 @@ impl State for DebugMode {
                     self.search_results = None;
                     self.reset_info(ctx);
                 }
+                "highlight lanes by type" => {
+                    return Transition::Push(WizardState::new(Box::new(highlight_lane_type)));
+                }
+                "clear lane type highlight" => {
+                    self.lane_type_highlight = None;
+                    self.reset_info(ctx);
+                }
+                "search for an object by ID" => {
+                    return Transition::Push(WizardState::new(Box::new(search_id)));
+                }
                 "screenshot everything" => {
                     let bounds = app.primary.map.get_bounds();
                     assert!(bounds.min_x == 0.0 && bounds.min_y == 0.0);
@@ -234,6 +283,20 @@ impl State for DebugMode {
                 self.reset_info(ctx);
             }
         }
+        if self
+            .composite
+            .is_checked("show parking blackhole redirects")
+        {
+            if self.blackhole_redirects.is_none() {
+                self.blackhole_redirects = Some(calc_blackhole_redirects(ctx, app));
+                self.reset_info(ctx);
+            }
+        } else {
+            if self.blackhole_redirects.is_some() {
+                self.blackhole_redirects = None;
+                self.reset_info(ctx);
+            }
+        }
 
         match app.primary.current_selection {
             Some(ID::Intersection(_)) | Some(ID::Car(_)) => {
@@ -300,6 +363,9 @@ impl State for DebugMode {
         if let Some(ref results) = self.search_results {
             g.redraw(&results.draw);
         }
+        if let Some((_, _, ref draw)) = self.lane_type_highlight {
+            g.redraw(draw);
+        }
         if let Some((_, ref draw)) = self.highlighted_agents {
             g.redraw(draw);
         }
@@ -308,6 +374,9 @@ impl State for DebugMode {
         if let Some((_, ref draw)) = self.all_routes {
             g.redraw(draw);
         }
+        if let Some((_, ref draw)) = self.blackhole_redirects {
+            g.redraw(draw);
+        }
 
         if !g.is_screencap() {
             self.composite.draw(g);
@@ -377,6 +446,91 @@ fn search_osm(wiz: &mut Wizard, ctx: &mut EventCtx, app: &mut App) -> Option<Tra
     })))
 }
 
+fn highlight_lane_type(wiz: &mut Wizard, ctx: &mut EventCtx, app: &mut App) -> Option<Transition> {
+    let (_, lt) = wiz.wrap(ctx).choose("Highlight which type of lane?", || {
+        vec![
+            LaneType::Driving,
+            LaneType::Parking,
+            LaneType::Sidewalk,
+            LaneType::Biking,
+            LaneType::Bus,
+            LaneType::SharedLeftTurn,
+            LaneType::Construction,
+        ]
+        .into_iter()
+        .map(|lt| Choice::new(lt.short_name(), lt))
+        .collect()
+    })?;
+
+    let mut batch = GeomBatch::new();
+    let mut cnt = 0;
+    for l in app.primary.map.all_lanes() {
+        if l.lane_type == lt {
+            cnt += 1;
+            batch.push(
+                Color::CYAN.alpha(0.8),
+                l.lane_center_pts.make_polygons(l.width),
+            );
+        }
+    }
+    let draw = batch.upload(ctx);
+
+    Some(Transition::PopWithData(Box::new(move |state, ctx, _| {
+        let mut mode = state.downcast_mut::<DebugMode>().unwrap();
+        mode.lane_type_highlight = Some((lt, cnt, draw));
+        mode.reset_info(ctx);
+    })))
+}
+
+fn search_id(wiz: &mut Wizard, ctx: &mut EventCtx, app: &mut App) -> Option<Transition> {
+    let line = wiz
+        .wrap(ctx)
+        .input_string("Search for what ID? (Like BuildingID(9) or CarID(30, Car))")?;
+    match parse_id(app, &line).and_then(|id| id.canonical_point(&app.primary).map(|pt| (id, pt))) {
+        Some((id, pt)) => {
+            app.primary.current_selection = Some(id.clone());
+            Some(Transition::Replace(Warping::new(
+                ctx,
+                pt,
+                Some(10.0),
+                Some(id),
+                &mut app.primary,
+            )))
+        }
+        None => Some(Transition::Replace(msg(
+            "Bad ID",
+            vec![format!("Can't find {}", line)],
+        ))),
+    }
+}
+
+// Understands the Debug format of IDs, as they usually appear in bug reports and logs.
+fn parse_id(app: &App, line: &str) -> Option<ID> {
+    let line = line.trim();
+    let open = line.find('(')?;
+    let close = line.rfind(')')?;
+    let name = line[..open].trim().trim_end_matches("ID");
+    let idx = line[open + 1..close]
+        .split(',')
+        .next()?
+        .trim()
+        .parse::<usize>()
+        .ok()?;
+    Some(match name {
+        "Road" => ID::Road(RoadID(idx)),
+        "Lane" => ID::Lane(LaneID(idx)),
+        "Intersection" => ID::Intersection(IntersectionID(idx)),
+        "Building" => ID::Building(BuildingID(idx)),
+        "ParkingLot" => ID::ParkingLot(ParkingLotID(idx)),
+        "Area" => ID::Area(AreaID(idx)),
+        "Pedestrian" => ID::Pedestrian(PedestrianID(idx)),
+        "Car" => ID::Car(app.primary.sim.lookup_car_id(idx)?),
+        _ => {
+            return None;
+        }
+    })
+}
+
 struct SearchResults {
     query: String,
     num_matches: usize,
@@ -398,6 +552,22 @@ fn load_savestate(wiz: &mut Wizard, ctx: &mut EventCtx, app: &mut App) -> Option
     Some(Transition::Pop)
 }
 
+fn load_export(wiz: &mut Wizard, ctx: &mut EventCtx, app: &mut App) -> Option<Transition> {
+    let ss = wiz
+        .wrap(ctx)
+        .choose_string("Load which exported sim state?", || {
+            abstutil::list_all_objects(app.primary.sim.export_dir())
+        })?;
+    let ss_path = format!("{}/{}.bin", app.primary.sim.export_dir(), ss);
+
+    ctx.loading_screen("load exported sim state", |ctx, mut timer| {
+        app.primary.sim = Sim::load_export(ss_path, &app.primary.map, &mut timer)
+            .expect("Can't load exported sim state");
+        app.recalculate_current_selection(ctx);
+    });
+    Some(Transition::Pop)
+}
+
 fn calc_all_routes(ctx: &EventCtx, app: &mut App) -> (usize, Drawable) {
     let trips: Vec<TripID> = app
         .primary
@@ -416,12 +586,41 @@ fn calc_all_routes(ctx: &EventCtx, app: &mut App) -> (usize, Drawable) {
             sim.trip_to_agent(trip)
                 .ok()
                 .and_then(|agent| sim.trace_route(agent, map, None))
-                .map(|trace| trace.make_polygons(NORMAL_LANE_THICKNESS))
+                .map(|trace| {
+                    (
+                        sim.trip_info(trip).3,
+                        trace.make_polygons(NORMAL_LANE_THICKNESS),
+                    )
+                })
         })
     {
-        if let Some(t) = maybe_trace {
+        if let Some((mode, t)) = maybe_trace {
             cnt += 1;
-            batch.push(app.cs.route, t);
+            batch.push(color_for_mode(app, mode).alpha(0.8), t);
+        }
+    }
+    (cnt, ctx.upload(batch))
+}
+
+// Draw an arrow from every lane with a parking blackhole to the lane that it redirects to.
+fn calc_blackhole_redirects(ctx: &EventCtx, app: &App) -> (usize, Drawable) {
+    let map = &app.primary.map;
+    let mut batch = GeomBatch::new();
+    let mut cnt = 0;
+    for l in map.all_lanes() {
+        if let Some(redirect) = l.parking_blackhole {
+            if let Some(line) = geom::Line::maybe_new(
+                l.lane_center_pts.middle(),
+                map.get_l(redirect).lane_center_pts.middle(),
+            ) {
+                cnt += 1;
+                batch.push(
+                    Color::RED.alpha(0.8),
+                    line.to_polyline()
+                        .make_arrow(NORMAL_LANE_THICKNESS, ArrowCap::Triangle)
+                        .unwrap(),
+                );
+            }
         }
     }
     (cnt, ctx.upload(batch))
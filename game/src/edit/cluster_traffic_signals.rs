@@ -29,7 +29,7 @@ impl ClusterTrafficSignalEditor {
             )
             .aligned(HorizontalAlignment::Center, VerticalAlignment::Top)
             .build(ctx),
-            groups: DrawUberTurnGroup::new(ic, &app.primary.map),
+            groups: DrawUberTurnGroup::new(ic, &app.primary.map, app.opts.turn_icon_arrow_length),
             group_selected: None,
             members: ic.members.clone(),
         })
@@ -9,7 +9,7 @@ use ezgui::{
     hotkey, Btn, Color, Composite, EventCtx, GfxCtx, HorizontalAlignment, Key, Outcome,
     RewriteColor, TextExt, VerticalAlignment, Widget,
 };
-use map_model::{EditCmd, LaneID, LaneType, Map};
+use map_model::{EditCmd, LaneID, LaneType, Map, Road, RoadID};
 use std::collections::BTreeSet;
 
 pub struct LaneEditor {
@@ -80,6 +80,33 @@ impl LaneEditor {
                 .centered_horiz(),
             Widget::row(row).centered().margin_below(5),
             change_speed_limit(ctx, parent.speed_limit).margin_below(5),
+            Btn::text_fg("Close entire road for construction")
+                .build(ctx, "close road for construction", hotkey(Key::X))
+                .centered_horiz()
+                .margin_below(5),
+            Widget::row(vec![
+                Btn::text_fg("Add another lane like this")
+                    .build(ctx, "add lane", hotkey(Key::A))
+                    .margin_right(5),
+                Btn::text_fg("Remove this lane").build(ctx, "remove lane", hotkey(Key::Backspace)),
+            ])
+            .centered()
+            .margin_below(5),
+            if is_one_way(parent) {
+                Btn::text_fg("Make this road two-way").build(
+                    ctx,
+                    "make road two-way",
+                    hotkey(Key::O),
+                )
+            } else {
+                Btn::text_fg("Make this road one-way, in this lane's direction").build(
+                    ctx,
+                    "make road one-way",
+                    hotkey(Key::O),
+                )
+            }
+            .centered_horiz()
+            .margin_below(5),
             Widget::row(vec![
                 Btn::text_fg("Finish").build_def(ctx, hotkey(Key::Escape)),
                 // TODO Handle reverting speed limit too...
@@ -147,6 +174,19 @@ impl State for LaneEditor {
                         try_change_lane_type(self.l, LaneType::Construction, map)
                     }
                     "reverse lane direction" => try_reverse(self.l, map),
+                    "add lane" => try_add_lane(self.l, map),
+                    "remove lane" => {
+                        // This lane won't be around to keep editing
+                        return match try_remove_lane(self.l, map) {
+                            Ok(cmd) => {
+                                let mut edits = app.primary.map.get_edits().clone();
+                                edits.commands.push(cmd);
+                                apply_map_edits(ctx, app, edits);
+                                Transition::Pop
+                            }
+                            Err(err) => Transition::Push(msg("Error", vec![err])),
+                        };
+                    }
                     "Finish" => {
                         return Transition::Pop;
                     }
@@ -158,6 +198,31 @@ impl State for LaneEditor {
                             try_reverse(self.l, map)
                         }
                     }
+                    "close road for construction" | "make road one-way" | "make road two-way" => {
+                        let cmds = match x.as_ref() {
+                            "close road for construction" => {
+                                try_close_road(map.get_l(self.l).parent, map)
+                            }
+                            "make road one-way" => try_make_one_way(self.l, map),
+                            _ => try_make_two_way(map.get_l(self.l).parent, map),
+                        };
+                        match cmds {
+                            Ok(cmds) => {
+                                let mut edits = app.primary.map.get_edits().clone();
+                                edits.commands.extend(cmds);
+                                apply_map_edits(ctx, app, edits);
+                                return Transition::Replace(Box::new(LaneEditor::new(
+                                    ctx,
+                                    app,
+                                    self.l,
+                                    self.mode.clone(),
+                                )));
+                            }
+                            Err(err) => {
+                                return Transition::Push(msg("Error", vec![err]));
+                            }
+                        }
+                    }
                     _ => unreachable!(),
                 };
                 match result {
@@ -279,9 +344,113 @@ pub fn try_change_lane_type(l: LaneID, new_lt: LaneType, map: &Map) -> Result<Ed
     })
 }
 
+// Sidewalks stay open, so pedestrians can still get around.
+fn try_close_road(r: RoadID, map: &Map) -> Result<Vec<EditCmd>, String> {
+    let road = map.get_r(r);
+    if !road.all_bus_stops(map).is_empty() {
+        return Err("You can't close a road with a bus stop".to_string());
+    }
+    let cmds: Vec<EditCmd> = road
+        .all_lanes()
+        .into_iter()
+        .filter(|l| {
+            let lt = map.get_l(*l).lane_type;
+            lt != LaneType::Sidewalk && lt != LaneType::Construction
+        })
+        .map(|l| EditCmd::ChangeLaneType {
+            id: l,
+            lt: LaneType::Construction,
+            orig_lt: map.get_l(l).lane_type,
+        })
+        .collect();
+    if cmds.is_empty() {
+        return Err(format!("{} is already closed", road.get_name()));
+    }
+    Ok(cmds)
+}
+
+fn is_one_way(r: &Road) -> bool {
+    !r.children_forwards
+        .iter()
+        .any(|(_, lt)| lt.is_for_moving_vehicles())
+        || !r
+            .children_backwards
+            .iter()
+            .any(|(_, lt)| lt.is_for_moving_vehicles())
+}
+
+// Reverse all of the vehicle lanes going against this lane. Only lanes next to the center line can
+// be reversed, so go from the center outwards.
+fn try_make_one_way(l: LaneID, map: &Map) -> Result<Vec<EditCmd>, String> {
+    let r = map.get_parent(l);
+    if !r.all_bus_stops(map).is_empty() {
+        return Err("You can't change the direction of a road with a bus stop".to_string());
+    }
+    if lane_count_changed(r.id, map) {
+        return Err(LANE_COUNT_AND_REVERSE.to_string());
+    }
+    let other_side = if r.dir_and_offset(l).0 {
+        &r.children_backwards
+    } else {
+        &r.children_forwards
+    };
+    let num_reversed = other_side
+        .iter()
+        .take_while(|(_, lt)| lt.is_for_moving_vehicles())
+        .count();
+    if num_reversed == 0 {
+        return Err(format!("{} is already one-way", r.get_name()));
+    }
+    if other_side[num_reversed..]
+        .iter()
+        .any(|(_, lt)| lt.is_for_moving_vehicles())
+    {
+        return Err(
+            "Some lanes going the other way aren't next to the center line, so they can't be \
+             reversed"
+                .to_string(),
+        );
+    }
+    Ok(other_side[0..num_reversed]
+        .iter()
+        .map(|(id, _)| EditCmd::ReverseLane {
+            l: *id,
+            dst_i: map.get_l(*id).src_i,
+        })
+        .collect())
+}
+
+// Reverse the lane next to the center line.
+fn try_make_two_way(r: RoadID, map: &Map) -> Result<Vec<EditCmd>, String> {
+    let road = map.get_r(r);
+    let side = if road
+        .children_forwards
+        .iter()
+        .any(|(_, lt)| lt.is_for_moving_vehicles())
+    {
+        &road.children_forwards
+    } else {
+        &road.children_backwards
+    };
+    if side
+        .iter()
+        .filter(|(_, lt)| lt.is_for_moving_vehicles())
+        .count()
+        < 2
+    {
+        return Err(format!(
+            "{} only has one lane for vehicles, so it can't be two-way",
+            road.get_name()
+        ));
+    }
+    Ok(vec![try_reverse(side[0].0, map)?])
+}
+
 fn try_reverse(l: LaneID, map: &Map) -> Result<EditCmd, String> {
     let lane = map.get_l(l);
-    if !lane.lane_type.is_for_moving_vehicles() {
+    if lane_count_changed(lane.parent, map) {
+        Err(LANE_COUNT_AND_REVERSE.to_string())
+    } else if !lane.lane_type.is_for_moving_vehicles() {
         Err(format!("You can't reverse a {:?} lane", lane.lane_type))
     } else if map.get_r(lane.parent).dir_and_offset(l).1 != 0 {
         Err(format!(
@@ -294,3 +463,116 @@ fn try_reverse(l: LaneID, map: &Map) -> Result<EditCmd, String> {
         })
     }
 }
+
+// Edits remember lanes by their position along the road. Reversing lanes and adding or removing
+// them both shift those positions around, so don't mix them on one road.
+const LANE_COUNT_AND_REVERSE: &str =
+    "You can't reverse lanes and add or remove lanes on the same road";
+
+fn lane_count_changed(r: RoadID, map: &Map) -> bool {
+    map.get_edits().commands.iter().any(|cmd| match cmd {
+        EditCmd::AddLane { r: x, .. } | EditCmd::RemoveLane { r: x, .. } => *x == r,
+        _ => false,
+    })
+}
+
+fn any_lane_reversed(r: RoadID, map: &Map) -> bool {
+    map.get_edits().commands.iter().any(|cmd| match cmd {
+        EditCmd::ReverseLane { l, .. } => map.get_l(*l).parent == r,
+        _ => false,
+    })
+}
+
+// The new lane goes just outside of this one.
+fn try_add_lane(l: LaneID, map: &Map) -> Result<EditCmd, String> {
+    let lane = map.get_l(l);
+    let r = map.get_parent(l);
+    if any_lane_reversed(r.id, map) {
+        return Err(LANE_COUNT_AND_REVERSE.to_string());
+    }
+    if lane.lane_type == LaneType::Parking {
+        return Err("You can only have one parking lane on the same side of the road".to_string());
+    }
+    let (fwd, idx) = r.dir_and_offset(l);
+    Ok(EditCmd::AddLane {
+        l: LaneID(map.all_lanes().len()),
+        r: r.id,
+        fwd,
+        idx: idx + 1,
+        lt: lane.lane_type,
+    })
+}
+
+fn try_remove_lane(l: LaneID, map: &Map) -> Result<EditCmd, String> {
+    let lane = map.get_l(l);
+    let r = map.get_parent(l);
+    if any_lane_reversed(r.id, map) {
+        return Err(LANE_COUNT_AND_REVERSE.to_string());
+    }
+    // Saving the edits would have to describe a lane that's gone.
+    if map.get_edits().commands.iter().any(|cmd| match cmd {
+        EditCmd::ChangeLaneType { id, .. } => *id == l,
+        EditCmd::ReverseLane { l: x, .. } => *x == l,
+        _ => false,
+    }) {
+        return Err("Undo the other changes to this lane before removing it".to_string());
+    }
+
+    if !lane.bus_stops.is_empty()
+        || map
+            .all_bus_stops()
+            .values()
+            .any(|bs| bs.driving_pos.lane() == l || bs.sidewalk_pos.lane() == l)
+    {
+        return Err("You can't remove a lane with a bus stop".to_string());
+    }
+
+    let (fwd, idx) = r.dir_and_offset(l);
+    let (mut proposed_lts, other_side) = if fwd {
+        (r.get_lane_types().0, r.get_lane_types().1)
+    } else {
+        (r.get_lane_types().1, r.get_lane_types().0)
+    };
+    proposed_lts.remove(idx);
+    if proposed_lts.is_empty() && other_side.is_empty() {
+        return Err("This is the only lane on the road".to_string());
+    }
+
+    // Don't let players orphan a bus stop.
+    if !r.all_bus_stops(map).is_empty()
+        && !proposed_lts
+            .iter()
+            .any(|lt| *lt == LaneType::Driving || *lt == LaneType::Bus)
+    {
+        return Err("You need a driving or bus lane for the bus stop!".to_string());
+    }
+
+    let all_types: BTreeSet<LaneType> = other_side
+        .into_iter()
+        .chain(proposed_lts.into_iter())
+        .collect();
+    if all_types.contains(&LaneType::Parking) && !all_types.contains(&LaneType::Driving) {
+        return Err("A parking lane needs a driving lane somewhere on the same road".to_string());
+    }
+
+    if map.all_buildings().iter().any(|b| {
+        b.parking
+            .as_ref()
+            .map(|p| p.driving_pos.lane() == l)
+            .unwrap_or(false)
+    }) || map
+        .all_parking_lots()
+        .iter()
+        .any(|pl| pl.driving_pos.lane() == l)
+    {
+        return Err("Some driveways lead to this lane, so it can't be removed".to_string());
+    }
+
+    Ok(EditCmd::RemoveLane {
+        l,
+        r: r.id,
+        fwd,
+        idx,
+        lt: lane.lane_type,
+    })
+}
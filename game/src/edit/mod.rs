@@ -2,6 +2,7 @@ mod bulk;
 mod cluster_traffic_signals;
 mod lanes;
 mod stop_signs;
+mod summary;
 mod traffic_signals;
 
 pub use self::cluster_traffic_signals::ClusterTrafficSignalEditor;
@@ -14,7 +15,7 @@ use crate::debug::DebugMode;
 use crate::game::{msg, State, Transition, WizardState};
 use crate::helpers::ID;
 use crate::managed::{WrappedComposite, WrappedOutcome};
-use crate::render::{DrawIntersection, DrawLane, DrawRoad};
+use crate::render::{DrawIntersection, DrawLane, DrawMap, DrawRoad};
 use crate::sandbox::{GameplayMode, SandboxMode, TimeWarpScreen};
 use abstutil::Timer;
 use ezgui::{
@@ -93,8 +94,28 @@ impl EditMode {
             if app.primary.map.get_edits().edits_name != "untitled edits" {
                 app.primary.map.save_edits();
             }
-            if app.opts.resume_after_edit {
-                if self.mode.reset_after_edits() {
+            let edits = app.primary.map.get_edits();
+            let lanes_changed = edits.added_lanes != self.orig_edits.added_lanes
+                || edits.removed_lanes != self.orig_edits.removed_lanes;
+            // Cars parked on a removed lane have nowhere to go, so like other edits without
+            // resume_after_edit, this only takes effect the next day.
+            let cars_stranded = edits
+                .removed_lanes
+                .difference(&self.orig_edits.removed_lanes)
+                .any(|l| {
+                    let lane = app.primary.map.get_l(*l);
+                    lane.is_parking()
+                        && old_sim.get_free_onstreet_spots(*l).len() < lane.number_parking_spots()
+                });
+            // Agents in the middle of a trip might be stranded on lanes that now go the other
+            // way, so that also waits for the next day.
+            let lanes_reversed = edits.reversed_lanes != self.orig_edits.reversed_lanes;
+            if cars_stranded || lanes_reversed {
+                Transition::PopThenReplace(Box::new(SandboxMode::new(ctx, app, self.mode.clone())))
+            } else if app.opts.resume_after_edit {
+                // The sim's per-lane state is fixed when it's created, so adding or removing
+                // lanes means starting over and warping back.
+                if lanes_changed || self.mode.reset_after_edits() {
                     Transition::PopThenReplaceThenPush(
                         Box::new(SandboxMode::new(ctx, app, self.mode.clone())),
                         TimeWarpScreen::new(ctx, app, old_sim.time(), false),
@@ -155,6 +176,10 @@ impl State for EditMode {
         if app.opts.dev && ctx.input.new_was_pressed(&lctrl(Key::D).unwrap()) {
             return Transition::Push(Box::new(DebugMode::new(ctx, app)));
         }
+        // The same key as the "edit map" button in the sandbox, to quickly flip back
+        if ctx.input.new_was_pressed(&lctrl(Key::E).unwrap()) {
+            return self.quit(ctx, app);
+        }
 
         match self.top_center.event(ctx) {
             Some(Outcome::Clicked(x)) => match x.as_ref() {
@@ -181,12 +206,18 @@ impl State for EditMode {
                         self.mode.clone(),
                     ));
                 }
+                "revert to last save" => {
+                    return Transition::Push(revert_to_last_save());
+                }
                 "save edits as" | "save edits" => {
                     return Transition::Push(WizardState::new(Box::new(|wiz, ctx, app| {
                         save_edits_as(&mut wiz.wrap(ctx), app)?;
                         Some(Transition::Pop)
                     })));
                 }
+                "see all changes" => {
+                    return Transition::Push(summary::EditSummary::new(ctx, app));
+                }
                 "undo" => {
                     let mut edits = app.primary.map.get_edits().clone();
                     let id = cmd_to_id(&edits.commands.pop().unwrap());
@@ -351,6 +382,42 @@ pub fn save_edits_as(wizard: &mut WrappedWizard, app: &mut App) -> Option<()> {
     Some(())
 }
 
+// Reload the named edits from disk, throwing away anything since they were last saved.
+fn revert_to_last_save() -> Box<dyn State> {
+    WizardState::new(Box::new(move |wiz, ctx, app| {
+        let mut wizard = wiz.wrap(ctx);
+        let name = app.primary.map.get_edits().edits_name.clone();
+        let discard = "discard changes";
+        if wizard
+            .choose_string(
+                &format!("Throw away changes since \"{}\" was last saved?", name),
+                || vec!["keep changes", discard],
+            )?
+            .as_str()
+            != discard
+        {
+            return Some(Transition::Pop);
+        }
+
+        let current = app.primary.map.get_edits().clone();
+        // We need to clear out the current edits first, or from_permanent won't work.
+        apply_map_edits(ctx, app, MapEdits::new());
+        match MapEdits::load(&app.primary.map, &name, &mut Timer::throwaway()) {
+            Ok(edits) => {
+                apply_map_edits(ctx, app, edits);
+                Some(Transition::Pop)
+            }
+            Err(err) => {
+                apply_map_edits(ctx, app, current);
+                Some(Transition::Replace(msg(
+                    "Error",
+                    vec![format!("Couldn't load \"{}\": {}", name, err)],
+                )))
+            }
+        }
+    }))
+}
+
 fn make_load_edits(app: &App, btn: ScreenRectangle, mode: GameplayMode) -> Box<dyn State> {
     let current_edits_name = app.primary.map.get_edits().edits_name.clone();
 
@@ -374,6 +441,8 @@ fn make_load_edits(app: &App, btn: ScreenRectangle, mode: GameplayMode) -> Box<d
 
         // We need to clear out the current edits first, or from_permanent won't work.
         apply_map_edits(wizard.ctx, app, MapEdits::new());
+        // Whatever gets loaded, it's no longer the proposal
+        app.primary.proposal = None;
 
         let (_, new_edits) = wizard.choose_exact(
             (
@@ -448,9 +517,30 @@ fn make_topcenter(ctx: &mut EventCtx, app: &App, mode: &GameplayMode) -> Composi
 pub fn apply_map_edits(ctx: &mut EventCtx, app: &mut App, edits: MapEdits) {
     let mut timer = Timer::new("apply map edits");
 
+    let orig_lanes = (
+        app.primary.map.get_edits().added_lanes.clone(),
+        app.primary.map.get_edits().removed_lanes.clone(),
+    );
     let (roads_changed, turns_deleted, turns_added, mut modified_intersections) =
         app.primary.map.apply_edits(edits, &mut timer);
 
+    // Adding or removing lanes changes lane IDs and what's selectable, so just start over.
+    if orig_lanes.0 != app.primary.map.get_edits().added_lanes
+        || orig_lanes.1 != app.primary.map.get_edits().removed_lanes
+    {
+        app.primary.draw_map = DrawMap::new(
+            &app.primary.map,
+            &app.primary.current_flags,
+            &app.cs,
+            ctx,
+            &mut timer,
+        );
+        if app.layer.as_ref().and_then(|l| l.name()) == Some("map edits") {
+            app.layer = Some(Box::new(crate::layer::map::Static::edits(ctx, app)));
+        }
+        return;
+    }
+
     for r in roads_changed {
         let road = app.primary.map.get_r(r);
         app.primary.draw_map.roads[r.0] =
@@ -657,6 +747,13 @@ fn make_changelist(ctx: &mut EventCtx, app: &App) -> Composite {
             Btn::text_fg("Autosaved!").inactive(ctx)
         }
         .margin_below(10),
+        if edits.edits_name != "untitled edits" {
+            Btn::text_fg("Revert to last save")
+                .build(ctx, "revert to last save", None)
+                .margin_below(10)
+        } else {
+            Widget::nothing()
+        },
         Text::from_multiline(vec![
             Line(format!("{} lane types changed", edits.original_lts.len())),
             Line(format!("{} lanes reversed", edits.reversed_lanes.len())),
@@ -672,6 +769,18 @@ fn make_changelist(ctx: &mut EventCtx, app: &App) -> Composite {
         .draw(ctx)
         .margin_below(10),
     ];
+    if let Some(ref proposal) = app.primary.proposal {
+        let (added, removed) = edits.diff_against(proposal);
+        col.push(
+            format!(
+                "{} edits beyond the proposal, {} of its edits undone",
+                added.len(),
+                removed.len()
+            )
+            .draw_text(ctx)
+            .margin_below(10),
+        );
+    }
 
     for (idx, cmd) in edits.commands.iter().rev().take(5).enumerate() {
         col.push(
@@ -685,6 +794,13 @@ fn make_changelist(ctx: &mut EventCtx, app: &App) -> Composite {
     if edits.commands.len() > 5 {
         col.push(format!("{} more...", edits.commands.len()).draw_text(ctx));
     }
+    if !edits.commands.is_empty() {
+        col.push(
+            Btn::text_fg("See all changes")
+                .build(ctx, "see all changes", None)
+                .margin_above(10),
+        );
+    }
 
     Composite::new(Widget::col(col).padding(16).bg(app.cs.panel_bg))
         .aligned(HorizontalAlignment::Right, VerticalAlignment::Center)
@@ -697,5 +813,7 @@ fn cmd_to_id(cmd: &EditCmd) -> ID {
         EditCmd::ReverseLane { l, .. } => ID::Lane(*l),
         EditCmd::ChangeSpeedLimit { id, .. } => ID::Road(*id),
         EditCmd::ChangeIntersection { i, .. } => ID::Intersection(*i),
+        // The lane might not exist anymore
+        EditCmd::AddLane { r, .. } | EditCmd::RemoveLane { r, .. } => ID::Road(*r),
     }
 }
@@ -0,0 +1,142 @@
+use crate::app::App;
+use crate::common::Warping;
+use crate::edit::{apply_map_edits, cmd_to_id};
+use crate::game::{DrawBaselayer, State, Transition};
+use ezgui::{
+    hotkey, Btn, Composite, EventCtx, GfxCtx, HorizontalAlignment, Key, Line, Outcome, TextExt,
+    VerticalAlignment, Widget,
+};
+use map_model::{EditCmd, IntersectionID, Map, RoadID};
+
+// Lists every change in the current edits, so individual ones can be reverted.
+pub struct EditSummary {
+    composite: Composite,
+}
+
+impl EditSummary {
+    pub fn new(ctx: &mut EventCtx, app: &App) -> Box<dyn State> {
+        let edits = app.primary.map.get_edits();
+        let mut col = vec![Widget::row(vec![
+            Line(format!("All changes in {}", edits.edits_name))
+                .small_heading()
+                .draw(ctx),
+            Btn::plaintext("X")
+                .build(ctx, "close", hotkey(Key::Escape))
+                .align_right(),
+        ])
+        .margin_below(10)];
+        if edits.commands.is_empty() {
+            col.push("Nothing changed yet".draw_text(ctx));
+        }
+        for (idx, cmd) in edits.commands.iter().enumerate() {
+            col.push(
+                Widget::row(vec![
+                    format!("{}) {}", idx + 1, cmd.short_name())
+                        .draw_text(ctx)
+                        .centered_vert()
+                        .margin_right(10),
+                    Btn::text_fg("show")
+                        .build(ctx, format!("show #{}", idx + 1), None)
+                        .margin_right(5),
+                    if can_revert(&edits.commands, idx, &app.primary.map) {
+                        Btn::text_fg("revert").build(ctx, format!("revert #{}", idx + 1), None)
+                    } else {
+                        Btn::text_fg("revert").inactive(ctx)
+                    },
+                ])
+                .margin_below(5),
+            );
+        }
+
+        Box::new(EditSummary {
+            composite: Composite::new(Widget::col(col).bg(app.cs.panel_bg).padding(16))
+                .max_size_percent(50, 80)
+                .aligned(HorizontalAlignment::Center, VerticalAlignment::Center)
+                .build(ctx),
+        })
+    }
+}
+
+impl State for EditSummary {
+    fn event(&mut self, ctx: &mut EventCtx, app: &mut App) -> Transition {
+        match self.composite.event(ctx) {
+            Some(Outcome::Clicked(x)) => {
+                if x == "close" {
+                    return Transition::Pop;
+                }
+                if x.starts_with("revert #") {
+                    let idx = x["revert #".len()..].parse::<usize>().unwrap() - 1;
+                    // Later commands get reapplied without this one. can_revert made sure none of
+                    // them depend on it.
+                    let mut edits = app.primary.map.get_edits().clone();
+                    edits.commands.remove(idx);
+                    apply_map_edits(ctx, app, edits);
+                    return Transition::Replace(EditSummary::new(ctx, app));
+                }
+                let idx = x["show #".len()..].parse::<usize>().unwrap() - 1;
+                let id = cmd_to_id(&app.primary.map.get_edits().commands[idx]);
+                return Transition::Push(Warping::new(
+                    ctx,
+                    id.canonical_point(&app.primary).unwrap(),
+                    Some(10.0),
+                    Some(id),
+                    &mut app.primary,
+                ));
+            }
+            None => {}
+        }
+        Transition::Keep
+    }
+
+    fn draw_baselayer(&self) -> DrawBaselayer {
+        DrawBaselayer::PreviousState
+    }
+
+    fn draw(&self, g: &mut GfxCtx, _: &App) {
+        self.composite.draw(g);
+    }
+}
+
+// What a command changes, for figuring out which commands depend on each other
+#[derive(PartialEq)]
+enum Touches {
+    // The lanes of a road
+    Lanes(RoadID),
+    SpeedLimit(RoadID),
+    Intersection(IntersectionID),
+    // Added lanes take the next unused ID, so they depend on every earlier added lane
+    NewLaneIDs,
+}
+
+fn touches(cmd: &EditCmd, map: &Map) -> Vec<Touches> {
+    // Changing the number or direction of lanes also changes the turns at both ends
+    let with_ends = |r: RoadID| {
+        let road = map.get_r(r);
+        vec![
+            Touches::Lanes(r),
+            Touches::Intersection(road.src_i),
+            Touches::Intersection(road.dst_i),
+        ]
+    };
+    match cmd {
+        EditCmd::ChangeLaneType { id, .. } => vec![Touches::Lanes(map.get_l(*id).parent)],
+        EditCmd::ReverseLane { l, .. } => with_ends(map.get_l(*l).parent),
+        EditCmd::ChangeSpeedLimit { id, .. } => vec![Touches::SpeedLimit(*id)],
+        EditCmd::ChangeIntersection { i, .. } => vec![Touches::Intersection(*i)],
+        EditCmd::AddLane { r, .. } => {
+            let mut list = with_ends(*r);
+            list.push(Touches::NewLaneIDs);
+            list
+        }
+        EditCmd::RemoveLane { r, .. } => with_ends(*r),
+    }
+}
+
+// Reverting a command reapplies everything after it, which only works if none of those later
+// commands touch the same things. Lane indices and original lane types would go stale otherwise.
+fn can_revert(commands: &Vec<EditCmd>, idx: usize, map: &Map) -> bool {
+    let this = touches(&commands[idx], map);
+    commands[idx + 1..]
+        .iter()
+        .all(|later| touches(later, map).iter().all(|t| !this.contains(t)))
+}
@@ -1,7 +1,8 @@
 use crate::app::{App, ShowEverything};
-use crate::common::CommonState;
+use crate::common::{ColorLegend, CommonState, Warping};
 use crate::edit::{apply_map_edits, close_intersection, StopSignEditor};
 use crate::game::{msg, DrawBaselayer, State, Transition, WizardState};
+use crate::helpers::ID;
 use crate::render::{
     draw_signal_phase, make_signal_diagram, DrawOptions, DrawTurnGroup, BIG_ARROW_THICKNESS,
 };
@@ -9,13 +10,13 @@ use crate::sandbox::{spawn_agents_around, GameplayMode, SpeedControls, TimePanel
 use abstutil::Timer;
 use ezgui::{
     hotkey, lctrl, Btn, Choice, Color, Composite, EventCtx, EventLoopMode, GeomBatch, GfxCtx,
-    HorizontalAlignment, Key, Line, Outcome, RewriteColor, Text, TextExt, VerticalAlignment,
-    Widget,
+    HorizontalAlignment, Key, Line, Outcome, RewriteColor, Spinner, Text, TextExt,
+    VerticalAlignment, Widget,
 };
 use geom::{ArrowCap, Distance, Duration};
 use map_model::{
-    ControlStopSign, ControlTrafficSignal, EditCmd, EditIntersection, IntersectionID, Phase,
-    TurnGroupID, TurnPriority,
+    describe_group, ControlStopSign, ControlTrafficSignal, EditCmd, EditIntersection,
+    IntersectionID, Map, MapEdits, Phase, TurnGroupID, TurnPriority, TurnType,
 };
 use std::collections::BTreeSet;
 
@@ -25,6 +26,7 @@ pub struct TrafficSignalEditor {
     current_phase: usize,
     composite: Composite,
     pub top_panel: Composite,
+    legend: Composite,
     mode: GameplayMode,
 
     groups: Vec<DrawTurnGroup>,
@@ -34,6 +36,13 @@ pub struct TrafficSignalEditor {
     // The first ControlTrafficSignal is the original
     pub command_stack: Vec<ControlTrafficSignal>,
     pub redo_stack: Vec<ControlTrafficSignal>,
+    // The edits and state of this intersection from before this editor was opened. When the
+    // editor is finished, all of the intermediate changes collapse into one command.
+    orig_edits: MapEdits,
+    orig_edits_i: EditIntersection,
+
+    // Problems from importing a timing plan, to explain after the wizard closes
+    import_problems: Vec<String>,
 }
 
 impl TrafficSignalEditor {
@@ -48,12 +57,16 @@ impl TrafficSignalEditor {
             i: id,
             current_phase: 0,
             composite: make_signal_diagram(ctx, app, id, 0, true),
-            top_panel: make_top_panel(ctx, app, false, false),
+            top_panel: make_top_panel(ctx, app, 0, 0),
+            legend: make_legend(ctx, app),
             mode,
-            groups: DrawTurnGroup::for_i(id, &app.primary.map),
+            groups: DrawTurnGroup::for_i(id, &app.primary.map, app.opts.turn_icon_arrow_length),
             group_selected: None,
             command_stack: Vec::new(),
             redo_stack: Vec::new(),
+            orig_edits: app.primary.map.get_edits().clone(),
+            orig_edits_i: app.primary.map.get_i_edit(id),
+            import_problems: Vec::new(),
         }
     }
 
@@ -70,12 +83,36 @@ impl TrafficSignalEditor {
                 .scroll_to_member(ctx, format!("phase {}", idx + 1));
         }
     }
+
+    // Both clicking and the P/Y/B keys edit one turn group in the current phase this way
+    fn set_group_priority(
+        &mut self,
+        ctx: &mut EventCtx,
+        app: &mut App,
+        id: TurnGroupID,
+        pri: TurnPriority,
+    ) {
+        let orig_signal = app.primary.map.get_traffic_signal(self.i);
+        let mut new_signal = orig_signal.clone();
+        new_signal.phases[self.current_phase].edit_group(&orig_signal.turn_groups[&id], pri);
+        self.command_stack.push(orig_signal.clone());
+        self.redo_stack.clear();
+        self.top_panel = make_top_panel(ctx, app, self.command_stack.len(), 0);
+        change_traffic_signal(new_signal, ctx, app);
+        self.change_phase(self.current_phase, ctx, app);
+    }
 }
 
 impl State for TrafficSignalEditor {
     fn event(&mut self, ctx: &mut EventCtx, app: &mut App) -> Transition {
         let orig_signal = app.primary.map.get_traffic_signal(self.i);
 
+        if !self.import_problems.is_empty() {
+            let mut lines = vec!["Imported the timing plan, with some problems:".to_string()];
+            lines.extend(self.import_problems.drain(..));
+            return Transition::Push(msg("Imported timing plan", lines));
+        }
+
         ctx.canvas_movement();
 
         // TODO Buttons for these...
@@ -92,14 +129,20 @@ impl State for TrafficSignalEditor {
         match self.composite.event(ctx) {
             Some(Outcome::Clicked(x)) => match x {
                 x if x == "Edit entire signal" => {
-                    return Transition::Push(edit_entire_signal(app, self.i, self.mode.clone()));
+                    return Transition::Push(edit_entire_signal(
+                        app,
+                        self.i,
+                        self.mode.clone(),
+                        self.orig_edits.clone(),
+                        self.orig_edits_i.clone(),
+                    ));
                 }
                 x if x.starts_with("change duration of phase ") => {
                     let idx = x["change duration of phase ".len()..]
                         .parse::<usize>()
                         .unwrap()
                         - 1;
-                    return Transition::Push(change_duration(app, self.i, idx));
+                    return Transition::Push(ChangeDuration::new(ctx, app, self.i, idx));
                 }
                 x if x.starts_with("delete phase ") => {
                     let idx = x["delete phase ".len()..].parse::<usize>().unwrap() - 1;
@@ -109,7 +152,7 @@ impl State for TrafficSignalEditor {
                     let num_phases = new_signal.phases.len();
                     self.command_stack.push(orig_signal.clone());
                     self.redo_stack.clear();
-                    self.top_panel = make_top_panel(ctx, app, true, false);
+                    self.top_panel = make_top_panel(ctx, app, self.command_stack.len(), 0);
                     change_traffic_signal(new_signal, ctx, app);
                     // Don't use change_phase; it tries to preserve scroll
                     self.current_phase = if idx == num_phases { idx - 1 } else { idx };
@@ -124,7 +167,7 @@ impl State for TrafficSignalEditor {
                     new_signal.phases.swap(idx, idx - 1);
                     self.command_stack.push(orig_signal.clone());
                     self.redo_stack.clear();
-                    self.top_panel = make_top_panel(ctx, app, true, false);
+                    self.top_panel = make_top_panel(ctx, app, self.command_stack.len(), 0);
                     change_traffic_signal(new_signal, ctx, app);
                     self.change_phase(idx - 1, ctx, app);
                     return Transition::Keep;
@@ -136,7 +179,7 @@ impl State for TrafficSignalEditor {
                     new_signal.phases.swap(idx, idx + 1);
                     self.command_stack.push(orig_signal.clone());
                     self.redo_stack.clear();
-                    self.top_panel = make_top_panel(ctx, app, true, false);
+                    self.top_panel = make_top_panel(ctx, app, self.command_stack.len(), 0);
                     change_traffic_signal(new_signal, ctx, app);
                     self.change_phase(idx + 1, ctx, app);
                     return Transition::Keep;
@@ -147,7 +190,7 @@ impl State for TrafficSignalEditor {
                     let len = new_signal.phases.len();
                     self.command_stack.push(orig_signal.clone());
                     self.redo_stack.clear();
-                    self.top_panel = make_top_panel(ctx, app, true, false);
+                    self.top_panel = make_top_panel(ctx, app, self.command_stack.len(), 0);
                     change_traffic_signal(new_signal, ctx, app);
                     self.change_phase(len - 1, ctx, app);
                     return Transition::Keep;
@@ -203,28 +246,47 @@ impl State for TrafficSignalEditor {
                         pri
                     ),
                 ) {
-                    let mut new_signal = orig_signal.clone();
-                    new_signal.phases[self.current_phase]
-                        .edit_group(&orig_signal.turn_groups[&id], pri);
-                    self.command_stack.push(orig_signal.clone());
-                    self.redo_stack.clear();
-                    self.top_panel = make_top_panel(ctx, app, true, false);
-                    change_traffic_signal(new_signal, ctx, app);
-                    self.change_phase(self.current_phase, ctx, app);
+                    self.set_group_priority(ctx, app, id, pri);
                     return Transition::KeepWithMouseover;
                 }
             }
+
+            // Set the priority directly, instead of cycling through them
+            let phase = &orig_signal.phases[self.current_phase];
+            let current = phase.get_priority_of_group(id);
+            let direct = if ctx.input.new_was_pressed(&hotkey(Key::P).unwrap()) {
+                Some(TurnPriority::Protected)
+                    .filter(|_| phase.could_be_protected(id, &orig_signal.turn_groups))
+            } else if ctx.input.new_was_pressed(&hotkey(Key::Y).unwrap()) {
+                Some(TurnPriority::Yield).filter(|_| !id.crosswalk)
+            } else if ctx.input.new_was_pressed(&hotkey(Key::B).unwrap()) {
+                Some(TurnPriority::Banned)
+            } else {
+                None
+            };
+            if let Some(pri) = direct.filter(|pri| *pri != current) {
+                self.set_group_priority(ctx, app, id, pri);
+                return Transition::KeepWithMouseover;
+            }
         }
 
         match self.top_panel.event(ctx) {
             Some(Outcome::Clicked(x)) => match x.as_ref() {
                 "Finish" => {
-                    return check_for_missing_groups(
-                        orig_signal.clone(),
-                        &mut self.composite,
-                        app,
-                        ctx,
-                    );
+                    if let Some(t) =
+                        check_for_missing_groups(orig_signal.clone(), &mut self.composite, app, ctx)
+                    {
+                        return t;
+                    }
+                    coalesce_edits(ctx, app, self.i, &self.orig_edits, &self.orig_edits_i);
+                    return Transition::Pop;
+                }
+                "Check for problems" => {
+                    let mut lines = find_problems(orig_signal, &app.primary.map);
+                    if lines.is_empty() {
+                        lines.push("No problems found".to_string());
+                    }
+                    return Transition::Push(msg("Problems with this signal", lines));
                 }
                 "Export" => {
                     let ts = orig_signal.export(&app.primary.map);
@@ -242,19 +304,25 @@ impl State for TrafficSignalEditor {
                         .map
                         .recalculate_pathfinding_after_edits(&mut Timer::throwaway());
 
-                    return Transition::Push(make_previewer(self.i, self.current_phase));
+                    return Transition::Push(make_previewer(
+                        self.i,
+                        self.current_phase,
+                        self.mode.clone(),
+                    ));
                 }
                 "undo" => {
                     self.redo_stack.push(orig_signal.clone());
                     change_traffic_signal(self.command_stack.pop().unwrap(), ctx, app);
-                    self.top_panel = make_top_panel(ctx, app, !self.command_stack.is_empty(), true);
+                    self.top_panel =
+                        make_top_panel(ctx, app, self.command_stack.len(), self.redo_stack.len());
                     self.change_phase(0, ctx, app);
                     return Transition::Keep;
                 }
                 "redo" => {
                     self.command_stack.push(orig_signal.clone());
                     change_traffic_signal(self.redo_stack.pop().unwrap(), ctx, app);
-                    self.top_panel = make_top_panel(ctx, app, true, !self.redo_stack.is_empty());
+                    self.top_panel =
+                        make_top_panel(ctx, app, self.command_stack.len(), self.redo_stack.len());
                     self.change_phase(0, ctx, app);
                     return Transition::Keep;
                 }
@@ -391,6 +459,7 @@ impl State for TrafficSignalEditor {
 
         self.composite.draw(g);
         self.top_panel.draw(g);
+        self.legend.draw(g);
         if let Some((id, _)) = self.group_selected {
             let osd = if id.crosswalk {
                 Text::from(Line(format!(
@@ -405,19 +474,41 @@ impl State for TrafficSignalEditor {
                 )))
             };
             CommonState::draw_custom_osd(g, app, osd);
+
+            let members = &signal.turn_groups[&id].members;
+            let mut txt = Text::from(Line(if members.len() == 1 {
+                "1 turn".to_string()
+            } else {
+                format!("{} turns", members.len())
+            }));
+            if members
+                .iter()
+                .any(|t| app.primary.map.get_t(*t).turn_type == TurnType::Crosswalk)
+            {
+                txt.append(Line(", includes crosswalk"));
+            }
+            g.draw_mouse_tooltip(txt);
         } else {
             CommonState::draw_osd(g, app);
         }
     }
 }
 
-pub fn make_top_panel(ctx: &mut EventCtx, app: &App, can_undo: bool, can_redo: bool) -> Composite {
+pub fn make_top_panel(
+    ctx: &mut EventCtx,
+    app: &App,
+    num_undo: usize,
+    num_redo: usize,
+) -> Composite {
     let row = vec![
         Btn::text_fg("Finish")
             .build_def(ctx, hotkey(Key::Escape))
             .margin_right(5),
         Btn::text_fg("Preview").build_def(ctx, lctrl(Key::P)),
-        (if can_undo {
+        Btn::text_fg("Check for problems")
+            .build_def(ctx, None)
+            .margin_left(5),
+        (if num_undo > 0 {
             Btn::svg_def("../data/system/assets/tools/undo.svg").build(ctx, "undo", lctrl(Key::Z))
         } else {
             Widget::draw_svg_transform(
@@ -427,7 +518,7 @@ pub fn make_top_panel(ctx: &mut EventCtx, app: &App, can_undo: bool, can_redo: b
             )
         })
         .margin(15),
-        (if can_redo {
+        (if num_redo > 0 {
             Btn::svg_def("../data/system/assets/tools/redo.svg").build(
                 ctx,
                 "redo",
@@ -442,6 +533,14 @@ pub fn make_top_panel(ctx: &mut EventCtx, app: &App, can_undo: bool, can_redo: b
             )
         })
         .margin(15),
+        format!(
+            "{} to undo, {} to redo",
+            abstutil::prettyprint_usize(num_undo),
+            abstutil::prettyprint_usize(num_redo)
+        )
+        .draw_text(ctx)
+        .centered_vert()
+        .margin_right(15),
         if app.opts.dev {
             Btn::text_fg("Export")
                 .tooltip(Text::from_multiline(vec![
@@ -462,10 +561,35 @@ pub fn make_top_panel(ctx: &mut EventCtx, app: &App, can_undo: bool, can_redo: b
         .build(ctx)
 }
 
+// Explains the colors of the turn icons
+fn make_legend(ctx: &mut EventCtx, app: &App) -> Composite {
+    Composite::new(
+        Widget::col(vec![
+            Line("Turn icons").small_heading().draw(ctx).margin_below(5),
+            ColorLegend::row(ctx, app.cs.signal_protected_turn, "protected"),
+            ColorLegend::row(ctx, app.cs.signal_permitted_turn, "permitted (must yield)"),
+            ColorLegend::row(ctx, app.cs.signal_banned_turn, "banned"),
+            ColorLegend::row(
+                ctx,
+                Color::WHITE,
+                "selected; the background shows what clicking changes it to",
+            ),
+            Line("Or press P, Y, or B to make the selected turn protected, permitted, or banned")
+                .small()
+                .draw(ctx)
+                .margin_above(5),
+        ])
+        .bg(app.cs.panel_bg)
+        .padding(10),
+    )
+    .aligned(HorizontalAlignment::Right, VerticalAlignment::Bottom)
+    .build(ctx)
+}
+
 pub fn change_traffic_signal(signal: ControlTrafficSignal, ctx: &mut EventCtx, app: &mut App) {
     let mut edits = app.primary.map.get_edits().clone();
-    // TODO Only record one command for the entire session. Otherwise, we can exit this editor and
-    // undo a few times, potentially ending at an invalid state!
+    // Avoid piling up commands while the editor is open. When it's finished, coalesce_edits
+    // collapses the session into one command.
     let old = if let Some(prev) = edits.commands.last().and_then(|cmd| match cmd {
         EditCmd::ChangeIntersection {
             i,
@@ -496,7 +620,36 @@ pub fn change_traffic_signal(signal: ControlTrafficSignal, ctx: &mut EventCtx, a
     apply_map_edits(ctx, app, edits);
 }
 
-fn edit_entire_signal(app: &App, i: IntersectionID, mode: GameplayMode) -> Box<dyn State> {
+// Record one command for the entire session, so undoing later can never land in an intermediate
+// state. Every way out of the editor has to call this.
+fn coalesce_edits(
+    ctx: &mut EventCtx,
+    app: &mut App,
+    i: IntersectionID,
+    orig_edits: &MapEdits,
+    orig_edits_i: &EditIntersection,
+) {
+    let mut edits = orig_edits.clone();
+    let new = app.primary.map.get_i_edit(i);
+    if *orig_edits_i != new {
+        edits.commands.push(EditCmd::ChangeIntersection {
+            i,
+            old: orig_edits_i.clone(),
+            new,
+        });
+    }
+    if edits.commands != app.primary.map.get_edits().commands {
+        apply_map_edits(ctx, app, edits);
+    }
+}
+
+fn edit_entire_signal(
+    app: &App,
+    i: IntersectionID,
+    mode: GameplayMode,
+    orig_edits: MapEdits,
+    orig_edits_i: EditIntersection,
+) -> Box<dyn State> {
     let has_sidewalks = app
         .primary
         .map
@@ -504,6 +657,19 @@ fn edit_entire_signal(app: &App, i: IntersectionID, mode: GameplayMode) -> Box<d
         .iter()
         .any(|t| t.between_sidewalks());
     let current_offset = app.primary.map.get_traffic_signal(i).offset;
+    let default_signal =
+        ControlTrafficSignal::get_possible_policies(&app.primary.map, i, &mut Timer::throwaway())
+            .remove(0)
+            .1;
+    // How many phases would resetting throw away?
+    let num_customized_phases = app
+        .primary
+        .map
+        .get_traffic_signal(i)
+        .phases
+        .iter()
+        .filter(|p| !default_signal.phases.contains(p))
+        .count();
 
     WizardState::new(Box::new(move |wiz, ctx, app| {
         let use_template = "use template";
@@ -511,6 +677,8 @@ fn edit_entire_signal(app: &App, i: IntersectionID, mode: GameplayMode) -> Box<d
         let stop_sign = "convert to stop signs";
         let close = "close intersection for construction";
         let offset = "edit signal offset";
+        let export = "export timing plan";
+        let import = "import a timing plan";
         let reset = "reset to default";
 
         let mut choices = vec![use_template];
@@ -523,6 +691,8 @@ fn edit_entire_signal(app: &App, i: IntersectionID, mode: GameplayMode) -> Box<d
             choices.push(close);
         }
         choices.push(offset);
+        choices.push(export);
+        choices.push(import);
         choices.push(reset);
 
         let mut wizard = wiz.wrap(ctx);
@@ -542,7 +712,7 @@ fn edit_entire_signal(app: &App, i: IntersectionID, mode: GameplayMode) -> Box<d
                         .command_stack
                         .push(app.primary.map.get_traffic_signal(editor.i).clone());
                     editor.redo_stack.clear();
-                    editor.top_panel = make_top_panel(ctx, app, true, false);
+                    editor.top_panel = make_top_panel(ctx, app, editor.command_stack.len(), 0);
                     change_traffic_signal(new_signal, ctx, app);
                     editor.change_phase(0, ctx, app);
                 })))
@@ -555,13 +725,14 @@ fn edit_entire_signal(app: &App, i: IntersectionID, mode: GameplayMode) -> Box<d
                     if new_signal.convert_to_ped_scramble() {
                         editor.command_stack.push(orig_signal.clone());
                         editor.redo_stack.clear();
-                        editor.top_panel = make_top_panel(ctx, app, true, false);
+                        editor.top_panel = make_top_panel(ctx, app, editor.command_stack.len(), 0);
                         change_traffic_signal(new_signal, ctx, app);
                         editor.change_phase(0, ctx, app);
                     }
                 })))
             }
             x if x == stop_sign => {
+                coalesce_edits(ctx, app, i, &orig_edits, &orig_edits_i);
                 let mut edits = app.primary.map.get_edits().clone();
                 edits.commands.push(EditCmd::ChangeIntersection {
                     i,
@@ -576,7 +747,10 @@ fn edit_entire_signal(app: &App, i: IntersectionID, mode: GameplayMode) -> Box<d
                     mode.clone(),
                 ))))
             }
-            x if x == close => Some(close_intersection(ctx, app, i, false)),
+            x if x == close => {
+                coalesce_edits(ctx, app, i, &orig_edits, &orig_edits_i);
+                Some(close_intersection(ctx, app, i, false))
+            }
             x if x == offset => {
                 let new_duration = wizard.input_usize_prefilled(
                     "What should the offset of this traffic signal be (seconds)?",
@@ -587,26 +761,76 @@ fn edit_entire_signal(app: &App, i: IntersectionID, mode: GameplayMode) -> Box<d
                     let mut signal = app.primary.map.get_traffic_signal(editor.i).clone();
                     editor.command_stack.push(signal.clone());
                     editor.redo_stack.clear();
-                    editor.top_panel = make_top_panel(ctx, app, true, false);
+                    editor.top_panel = make_top_panel(ctx, app, editor.command_stack.len(), 0);
                     signal.offset = Duration::seconds(new_duration as f64);
                     change_traffic_signal(signal, ctx, app);
                     editor.change_phase(editor.current_phase, ctx, app);
                 })))
             }
+            x if x == export => {
+                let name = wizard.input_string("Name this timing plan")?;
+                let path = abstutil::path_signal_timing(app.primary.map.get_name(), &name);
+                abstutil::write_json(path.clone(), app.primary.map.get_traffic_signal(i));
+                Some(Transition::Replace(msg(
+                    "Exported timing plan",
+                    vec![format!("Saved to {}", path)],
+                )))
+            }
+            x if x == import => {
+                let name = wizard.choose_string("Import which timing plan?", || {
+                    abstutil::list_all_objects(abstutil::path_all_signal_timings(
+                        app.primary.map.get_name(),
+                    ))
+                })?;
+                let path = abstutil::path_signal_timing(app.primary.map.get_name(), &name);
+                match abstutil::maybe_read_json::<ControlTrafficSignal>(
+                    path.clone(),
+                    &mut Timer::throwaway(),
+                )
+                .map_err(|err| format!("Couldn't read {}: {}", path, err))
+                .and_then(|plan| plan.transfer(&app.primary.map, i))
+                {
+                    Ok((new_signal, problems)) => {
+                        Some(Transition::PopWithData(Box::new(move |state, ctx, app| {
+                            let editor = state.downcast_mut::<TrafficSignalEditor>().unwrap();
+                            editor
+                                .command_stack
+                                .push(app.primary.map.get_traffic_signal(editor.i).clone());
+                            editor.redo_stack.clear();
+                            editor.top_panel =
+                                make_top_panel(ctx, app, editor.command_stack.len(), 0);
+                            change_traffic_signal(new_signal, ctx, app);
+                            // Don't use change_phase; it tries to preserve scroll
+                            editor.current_phase = 0;
+                            editor.composite =
+                                make_signal_diagram(ctx, app, editor.i, editor.current_phase, true);
+                            editor.import_problems = problems;
+                        })))
+                    }
+                    Err(err) => Some(Transition::Replace(msg(
+                        "Can't import timing plan",
+                        vec![err],
+                    ))),
+                }
+            }
             x if x == reset => {
+                if num_customized_phases >= 2 {
+                    let confirm = format!("Discard {} customized phases", num_customized_phases);
+                    let answer = wizard.choose_string(
+                        "This signal differs a lot from the default. Really reset it?",
+                        || vec![confirm.clone(), "Never mind".to_string()],
+                    )?;
+                    if answer != confirm {
+                        return Some(Transition::Pop);
+                    }
+                }
+                let new_signal = default_signal.clone();
                 Some(Transition::PopWithData(Box::new(move |state, ctx, app| {
                     let editor = state.downcast_mut::<TrafficSignalEditor>().unwrap();
                     let orig_signal = app.primary.map.get_traffic_signal(editor.i);
-                    let new_signal = ControlTrafficSignal::get_possible_policies(
-                        &app.primary.map,
-                        editor.i,
-                        &mut Timer::throwaway(),
-                    )
-                    .remove(0)
-                    .1;
                     editor.command_stack.push(orig_signal.clone());
                     editor.redo_stack.clear();
-                    editor.top_panel = make_top_panel(ctx, app, true, false);
+                    editor.top_panel = make_top_panel(ctx, app, editor.command_stack.len(), 0);
                     change_traffic_signal(new_signal, ctx, app);
                     // Don't use change_phase; it tries to preserve scroll
                     editor.current_phase = 0;
@@ -619,40 +843,147 @@ fn edit_entire_signal(app: &App, i: IntersectionID, mode: GameplayMode) -> Box<d
     }))
 }
 
-fn change_duration(app: &App, i: IntersectionID, idx: usize) -> Box<dyn State> {
-    let current_duration = app.primary.map.get_traffic_signal(i).phases[idx].duration;
-
-    WizardState::new(Box::new(move |wiz, ctx, _| {
-        let new_duration = wiz.wrap(ctx).input_something(
-            "How long should this phase be (seconds)?",
-            Some(format!("{}", current_duration.inner_seconds() as usize)),
-            Box::new(|line| {
-                line.parse::<usize>()
-                    .ok()
-                    .and_then(|n| if n != 0 { Some(n) } else { None })
-            }),
-        )?;
-        Some(Transition::PopWithData(Box::new(move |state, ctx, app| {
-            let editor = state.downcast_mut::<TrafficSignalEditor>().unwrap();
-            let orig_signal = app.primary.map.get_traffic_signal(editor.i);
-
-            let mut new_signal = orig_signal.clone();
-            new_signal.phases[idx].duration = Duration::seconds(new_duration as f64);
-            editor.command_stack.push(orig_signal.clone());
-            editor.redo_stack.clear();
-            editor.top_panel = make_top_panel(ctx, app, true, false);
-            change_traffic_signal(new_signal, ctx, app);
-            editor.change_phase(idx, ctx, app);
-        })))
-    }))
+struct ChangeDuration {
+    composite: Composite,
+    idx: usize,
+}
+
+impl ChangeDuration {
+    fn new(ctx: &mut EventCtx, app: &App, i: IntersectionID, idx: usize) -> Box<dyn State> {
+        let current = app.primary.map.get_traffic_signal(i).phases[idx].duration;
+        let max = if current > Duration::minutes(5) {
+            current
+        } else {
+            Duration::minutes(5)
+        };
+        Box::new(ChangeDuration {
+            composite: Composite::new(
+                Widget::col(vec![
+                    Widget::row(vec![
+                        Line("How long should this phase be?")
+                            .small_heading()
+                            .draw(ctx),
+                        Btn::text_fg("X")
+                            .build(ctx, "close", hotkey(Key::Escape))
+                            .align_right(),
+                    ]),
+                    Widget::row(vec![
+                        "Duration:".draw_text(ctx).margin_right(10),
+                        Spinner::duration(
+                            ctx,
+                            (Duration::seconds(1.0), max),
+                            Duration::seconds(5.0),
+                            current,
+                        )
+                        .named("duration"),
+                    ])
+                    .margin_below(10),
+                    Btn::text_bg2("Apply").build_def(ctx, hotkey(Key::Enter)),
+                ])
+                .bg(app.cs.panel_bg)
+                .padding(10),
+            )
+            .build(ctx),
+            idx,
+        })
+    }
+}
+
+impl State for ChangeDuration {
+    fn event(&mut self, ctx: &mut EventCtx, _: &mut App) -> Transition {
+        match self.composite.event(ctx) {
+            Some(Outcome::Clicked(x)) => match x.as_ref() {
+                "close" => {
+                    return Transition::Pop;
+                }
+                "Apply" => {
+                    let new_duration = self.composite.duration_spinner("duration");
+                    let idx = self.idx;
+                    return Transition::PopWithData(Box::new(move |state, ctx, app| {
+                        let editor = state.downcast_mut::<TrafficSignalEditor>().unwrap();
+                        let orig_signal = app.primary.map.get_traffic_signal(editor.i);
+
+                        let mut new_signal = orig_signal.clone();
+                        new_signal.phases[idx].duration = new_duration;
+                        editor.command_stack.push(orig_signal.clone());
+                        editor.redo_stack.clear();
+                        editor.top_panel = make_top_panel(ctx, app, editor.command_stack.len(), 0);
+                        change_traffic_signal(new_signal, ctx, app);
+                        editor.change_phase(idx, ctx, app);
+                    }));
+                }
+                _ => unreachable!(),
+            },
+            None => {}
+        }
+        if self.composite.clicked_outside(ctx) {
+            return Transition::Pop;
+        }
+        Transition::Keep
+    }
+
+    fn draw(&self, g: &mut GfxCtx, app: &App) {
+        State::grey_out_map(g, app);
+        self.composite.draw(g);
+    }
+}
+
+// Phases shorter than this probably don't give anybody enough time to move
+const SHORT_PHASE: Duration = Duration::const_seconds(10.0);
+
+// Catches things that validate() and check_for_missing_groups() allow, but that probably aren't
+// intended.
+fn find_problems(signal: &ControlTrafficSignal, map: &Map) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut ever_allowed = BTreeSet::new();
+    for (idx, phase) in signal.phases.iter().enumerate() {
+        if phase.protected_groups.is_empty() && phase.yield_groups.is_empty() {
+            problems.push(format!("Phase {} doesn't let anything move", idx + 1));
+        }
+        if phase.duration < SHORT_PHASE {
+            problems.push(format!("Phase {} only lasts {}", idx + 1, phase.duration));
+        }
+        for g in &phase.yield_groups {
+            // Nothing protected to yield to, so why not protect it?
+            if !phase.protected_groups.is_empty()
+                && phase.could_be_protected(*g, &signal.turn_groups)
+            {
+                problems.push(format!(
+                    "Phase {}: {} yields, but doesn't conflict with anything protected",
+                    idx + 1,
+                    describe_group(g, map)
+                ));
+            }
+        }
+        ever_allowed.extend(phase.protected_groups.iter().cloned());
+        ever_allowed.extend(phase.yield_groups.iter().cloned());
+    }
+    for g in signal.turn_groups.keys() {
+        if !ever_allowed.contains(g) {
+            problems.push(format!(
+                "{} is never allowed",
+                capitalize(describe_group(g, map))
+            ));
+        }
+    }
+    problems
+}
+
+fn capitalize(s: String) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => s,
+    }
 }
 
+// If some turn groups are missing, adds them in a new phase and returns a transition to explain.
 fn check_for_missing_groups(
     mut signal: ControlTrafficSignal,
     composite: &mut Composite,
     app: &mut App,
     ctx: &mut EventCtx,
-) -> Transition {
+) -> Option<Transition> {
     let mut missing: BTreeSet<TurnGroupID> = signal.turn_groups.keys().cloned().collect();
     for phase in &signal.phases {
         for g in &phase.protected_groups {
@@ -667,7 +998,7 @@ fn check_for_missing_groups(
         if let Err(err) = signal.validate() {
             panic!("Edited traffic signal {} finalized with errors: {}", i, err);
         }
-        return Transition::Pop;
+        return None;
     }
     let num_missing = missing.len();
     let mut phase = Phase::new();
@@ -683,7 +1014,7 @@ fn check_for_missing_groups(
     change_traffic_signal(signal, ctx, app);
     *composite = make_signal_diagram(ctx, app, id, 0, true);
 
-    Transition::Push(msg(
+    Some(Transition::Push(msg(
         "Error: missing turns",
         vec![
             format!("{} turns are missing from this traffic signal", num_missing),
@@ -691,13 +1022,14 @@ fn check_for_missing_groups(
              them."
                 .to_string(),
         ],
-    ))
+    )))
 }
 
 // TODO I guess it's valid to preview without all turns possible. Some agents are just sad.
-fn make_previewer(i: IntersectionID, phase: usize) -> Box<dyn State> {
+fn make_previewer(i: IntersectionID, phase: usize, mode: GameplayMode) -> Box<dyn State> {
     WizardState::new(Box::new(move |wiz, ctx, app| {
         let random = "random agents around just this intersection".to_string();
+        let all_edited = "the whole scenario, with every edited signal".to_string();
         let right_now = format!(
             "change the traffic signal live at {}",
             app.suspended_sim.as_ref().unwrap().time()
@@ -706,7 +1038,7 @@ fn make_previewer(i: IntersectionID, phase: usize) -> Box<dyn State> {
             .wrap(ctx)
             .choose_string(
                 "Preview the traffic signal with what kind of traffic?",
-                || vec![random.clone(), right_now.clone()],
+                || vec![random.clone(), all_edited.clone(), right_now.clone()],
             )?
             .as_str()
         {
@@ -727,31 +1059,87 @@ fn make_previewer(i: IntersectionID, phase: usize) -> Box<dyn State> {
 
                 spawn_agents_around(i, app);
             }
+            x if x == all_edited => {
+                // Phases aren't lined up at just one intersection; everything runs from the start
+                ctx.loading_screen("instantiate scenario", |_, timer| {
+                    let scenario = mode
+                        .scenario(
+                            &app.primary.map,
+                            app.primary.current_flags.num_agents,
+                            app.primary.current_flags.sim_flags.make_rng(),
+                            timer,
+                        )
+                        .filter(|s| !s.people.is_empty());
+                    if let Some(scenario) = scenario {
+                        scenario.instantiate(
+                            &mut app.primary.sim,
+                            &app.primary.map,
+                            &mut app.primary.current_flags.sim_flags.make_rng(),
+                            timer,
+                        );
+                        app.primary
+                            .sim
+                            .tiny_step(&app.primary.map, &mut app.primary.sim_cb);
+                    } else {
+                        // Some modes don't have a scenario to run
+                        for i in edited_signals(app, i) {
+                            spawn_agents_around(i, app);
+                        }
+                    }
+                });
+            }
             x if x == right_now => {
                 app.primary.sim = app.suspended_sim.as_ref().unwrap().clone();
             }
             _ => unreachable!(),
         };
         Some(Transition::Replace(Box::new(PreviewTrafficSignal::new(
-            ctx, app,
+            ctx,
+            app,
+            edited_signals(app, i),
         ))))
     }))
 }
 
 // TODO Show diagram, auto-sync the phase.
 // TODO Auto quit after things are gone?
+// The signal being edited goes first
+fn edited_signals(app: &App, current: IntersectionID) -> Vec<IntersectionID> {
+    let map = &app.primary.map;
+    let mut signals = vec![current];
+    for i in map.get_edits().original_intersections.keys() {
+        if *i != current && map.maybe_get_traffic_signal(*i).is_some() {
+            signals.push(*i);
+        }
+    }
+    signals
+}
+
 struct PreviewTrafficSignal {
     composite: Composite,
     speed: SpeedControls,
     time_panel: TimePanel,
+
+    // Edited signals to cycle through while watching
+    signals: Vec<IntersectionID>,
+    current_signal: usize,
 }
 
 impl PreviewTrafficSignal {
-    fn new(ctx: &mut EventCtx, app: &App) -> PreviewTrafficSignal {
+    fn new(ctx: &mut EventCtx, app: &App, signals: Vec<IntersectionID>) -> PreviewTrafficSignal {
         PreviewTrafficSignal {
             composite: Composite::new(
                 Widget::col(vec![
                     "Previewing traffic signal".draw_text(ctx),
+                    if signals.len() > 1 {
+                        Btn::text_fg(format!("next edited signal ({} total)", signals.len())).build(
+                            ctx,
+                            "next edited signal",
+                            hotkey(Key::N),
+                        )
+                    } else {
+                        Widget::nothing()
+                    },
                     Btn::text_fg("back to editing").build_def(ctx, hotkey(Key::Escape)),
                 ])
                 .bg(app.cs.panel_bg)
@@ -761,6 +1149,8 @@ impl PreviewTrafficSignal {
             .build(ctx),
             speed: SpeedControls::new(ctx, app),
             time_panel: TimePanel::new(ctx, app),
+            signals,
+            current_signal: 0,
         }
     }
 }
@@ -775,6 +1165,17 @@ impl State for PreviewTrafficSignal {
                     app.primary.clear_sim();
                     return Transition::Pop;
                 }
+                "next edited signal" => {
+                    self.current_signal = (self.current_signal + 1) % self.signals.len();
+                    let i = self.signals[self.current_signal];
+                    return Transition::Push(Warping::new(
+                        ctx,
+                        app.primary.map.get_i(i).polygon.center(),
+                        Some(10.0),
+                        Some(ID::Intersection(i)),
+                        &mut app.primary,
+                    ));
+                }
                 _ => unreachable!(),
             },
             None => {}
@@ -3,7 +3,7 @@ use crate::options::Options;
 use crate::pregame::TitleScreen;
 use crate::render::DrawOptions;
 use crate::sandbox::{GameplayMode, SandboxMode};
-use ezgui::{Canvas, Drawable, EventCtx, EventLoopMode, GfxCtx, Wizard, GUI};
+use ezgui::{Canvas, Drawable, EventCtx, EventLoopMode, GfxCtx, Key, Wizard, GUI};
 use geom::Polygon;
 
 // This is the top-level of the GUI logic. This module should just manage interactions between the
@@ -24,17 +24,15 @@ impl Game {
     ) -> Game {
         let title = !opts.dev
             && !flags.sim_flags.load.contains("data/player/save")
+            && flags.sim_flags.load_savestate.is_none()
             && !flags.sim_flags.load.contains("data/system/scenarios")
             && maybe_mode.is_none();
         let mut app = App::new(flags, opts, ctx, title);
 
         // Handle savestates
-        let savestate = if app
-            .primary
-            .current_flags
-            .sim_flags
-            .load
-            .starts_with("../data/player/saves/")
+        let sim_flags = &app.primary.current_flags.sim_flags;
+        let savestate = if sim_flags.load.starts_with("../data/player/saves/")
+            || sim_flags.load_savestate.is_some()
         {
             assert!(maybe_mode.is_none());
             Some(app.primary.clear_sim())
@@ -78,6 +76,18 @@ impl GUI for Game {
     fn event(&mut self, ctx: &mut EventCtx) -> EventLoopMode {
         self.app.per_obj.reset();
 
+        if ctx
+            .input
+            .unimportant_key_pressed(Key::F12, "save a screenshot")
+        {
+            return EventLoopMode::ScreenCaptureCurrentShot {
+                path: abstutil::path_player_screenshot(
+                    self.app.primary.map.get_name(),
+                    &chrono::Local::now().format("%Y-%m-%d_%H-%M-%S").to_string(),
+                ),
+            };
+        }
+
         let transition = self.states.last_mut().unwrap().event(ctx, &mut self.app);
         // If we fall through, there's a new state that we need to wakeup.
         match transition {
@@ -1,10 +1,10 @@
 use crate::app::App;
 use crate::info::{header_btns, make_table, make_tabs, Details, Tab};
 use crate::render::DrawPedestrian;
-use ezgui::{Btn, Color, EventCtx, Line, Text, TextExt, Widget};
+use ezgui::{hotkey, Btn, Color, EventCtx, Key, Line, Text, TextExt, Widget};
 use geom::{Angle, Circle, Distance, Speed, Time};
 use map_model::{BuildingID, LaneID, Traversable, SIDEWALK_THICKNESS};
-use sim::{DrawPedestrianInput, PedestrianID, PersonID, TripMode, TripResult};
+use sim::{CarID, DrawPedestrianInput, PedestrianID, PersonID, TripEndpoint, TripMode, TripResult};
 use std::collections::BTreeMap;
 
 pub fn info(ctx: &mut EventCtx, app: &App, details: &mut Details, id: BuildingID) -> Vec<Widget> {
@@ -89,9 +89,59 @@ pub fn info(ctx: &mut EventCtx, app: &App, details: &mut Details, id: BuildingID
         rows.push(txt.draw(ctx))
     }
 
+    if details
+        .bldg_owners
+        .as_ref()
+        .map(|(b, _)| *b != id)
+        .unwrap_or(true)
+    {
+        details.bldg_owners = Some((id, find_owners(app, id)));
+    }
+    let cars = owned_parked_cars(app, &details.bldg_owners.as_ref().unwrap().1);
+    if !cars.is_empty() {
+        rows.push(Widget::row(vec![
+            format!("{} parked cars owned by people here", cars.len())
+                .draw_text(ctx)
+                .centered_vert()
+                .margin_right(10),
+            Btn::text_fg("next car").build(ctx, "jump to next owned car", hotkey(Key::C)),
+        ]));
+    }
+
     rows
 }
 
+// Anybody whose day starts at the building. People don't have a home yet, so this is the closest
+// thing to the building's residents.
+fn find_owners(app: &App, id: BuildingID) -> Vec<PersonID> {
+    app.primary
+        .sim
+        .get_all_people()
+        .iter()
+        .filter(|person| {
+            person
+                .trips
+                .get(0)
+                .map(|t| app.primary.sim.trip_info(*t).1 == TripEndpoint::Bldg(id))
+                .unwrap_or(false)
+        })
+        .map(|person| person.id)
+        .collect()
+}
+
+// Which of the owners' vehicles are parked right now
+pub fn owned_parked_cars(app: &App, owners: &Vec<PersonID>) -> Vec<CarID> {
+    let mut cars = Vec::new();
+    for p in owners {
+        for v in &app.primary.sim.get_person(*p).vehicles {
+            if app.primary.sim.lookup_parked_car(v.id).is_some() {
+                cars.push(v.id);
+            }
+        }
+    }
+    cars
+}
+
 pub fn people(ctx: &mut EventCtx, app: &App, details: &mut Details, id: BuildingID) -> Vec<Widget> {
     let mut rows = header(ctx, app, details, id, Tab::BldgPeople(id));
 
@@ -93,7 +93,7 @@ fn bus_header(
         .sim
         .canonical_pt_for_agent(AgentID::Car(id), &app.primary.map)
     {
-        ctx.canvas.center_on_map_pt(pt);
+        details.center_on = Some(pt);
     }
 
     let mut rows = vec![];
@@ -141,6 +141,7 @@ fn delays_over_time(ctx: &mut EventCtx, app: &App, id: BusRouteID) -> Widget {
             pts: delays_per_stop
                 .remove(&route.stops[idx2])
                 .unwrap_or_else(Vec::new),
+            dashed: false,
         });
     }
     Widget::col(vec![
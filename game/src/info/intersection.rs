@@ -2,8 +2,11 @@ use crate::app::App;
 use crate::helpers::color_for_mode;
 use crate::info::{header_btns, make_tabs, throughput, DataOptions, Details, Tab};
 use abstutil::prettyprint_usize;
-use ezgui::{Color, EventCtx, GeomBatch, Line, PlotOptions, ScatterPlot, Series, Text, Widget};
-use geom::{ArrowCap, Distance, Duration, PolyLine, Time};
+use ezgui::{
+    Band, Btn, Color, EventCtx, GeomBatch, Line, LinePlot, PlotOptions, ScatterPlot, Series, Text,
+    TextExt, Widget,
+};
+use geom::{ArrowCap, Distance, Duration, Histogram, PolyLine, Statistic, Time};
 use map_model::{IntersectionID, IntersectionType};
 use sim::TripMode;
 use std::collections::{BTreeMap, BTreeSet};
@@ -21,6 +24,19 @@ pub fn info(ctx: &EventCtx, app: &App, details: &mut Details, id: IntersectionID
         // TODO The spacing is ignored, so use -
         txt.add(Line(format!("- {}", r)));
     }
+    if let Some(approaches) = app.primary.sim.get_analytics().approach_delays.get(&id) {
+        if let Some((r, (total, count))) = approaches
+            .iter()
+            .max_by_key(|(_, (total, count))| *total / (*count as f64))
+        {
+            txt.add(Line(""));
+            txt.add(Line(format!(
+                "Worst approach: {} (avg {})",
+                app.primary.map.get_r(*r).get_name(),
+                *total / (*count as f64)
+            )));
+        }
+    }
     if app.opts.dev {
         txt.add(Line(format!("OSM node ID: {}", i.orig_id.osm_node_id)).secondary());
     }
@@ -100,11 +116,38 @@ pub fn delay(
     assert!(i.is_traffic_signal());
     rows.push(opts.to_controls(ctx, app).margin_below(10));
 
-    rows.push(delay_plot(ctx, app, id, opts));
+    rows.push(delay_plot(ctx, app, id, opts).margin_below(10));
+    rows.push(delay_percentiles_plot(ctx, app, id, opts).margin_below(10));
+    rows.push(Btn::text_fg("export delays to CSV").build_def(ctx, None));
 
     rows
 }
 
+// Write every delay measured so far, for plotting elsewhere. Returns the path written.
+pub fn export_delays(app: &App, i: IntersectionID) -> Result<String, std::io::Error> {
+    let path = abstutil::path_intersection_delays(app.primary.map.get_name(), i.0);
+    if let Some(dir) = std::path::Path::new(&path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut lines = vec!["time_seconds,delay_seconds,mode".to_string()];
+    let now = app.primary.sim.time();
+    if let Some(list) = app.primary.sim.get_analytics().intersection_delays.get(&i) {
+        for (t, dt, mode) in list {
+            if *t > now {
+                break;
+            }
+            lines.push(format!(
+                "{},{},{}",
+                t.inner_seconds(),
+                dt.inner_seconds(),
+                mode.noun()
+            ));
+        }
+    }
+    std::fs::write(&path, lines.join("\n"))?;
+    Ok(path)
+}
+
 pub fn current_demand(
     ctx: &mut EventCtx,
     app: &App,
@@ -210,6 +253,7 @@ fn delay_plot(ctx: &EventCtx, app: &App, i: IntersectionID, opts: &DataOptions)
             label: mode.noun().to_string(),
             color: color_for_mode(app, mode),
             pts,
+            dashed: false,
         })
         .collect();
     Widget::col(vec![
@@ -225,6 +269,7 @@ fn delay_plot(ctx: &EventCtx, app: &App, i: IntersectionID, opts: &DataOptions)
                 max_x: Some(limit),
                 max_y: None,
                 disabled: opts.disabled_series(),
+                band: None,
             },
         ),
     ])
@@ -233,6 +278,88 @@ fn delay_plot(ctx: &EventCtx, app: &App, i: IntersectionID, opts: &DataOptions)
     .outline(2.0, Color::WHITE)
 }
 
+// Summarize delay per hour. The 85th percentile is commonly used in traffic engineering.
+fn delay_percentiles_plot(
+    ctx: &EventCtx,
+    app: &App,
+    i: IntersectionID,
+    opts: &DataOptions,
+) -> Widget {
+    let data = if opts.show_before {
+        app.prebaked()
+    } else {
+        app.primary.sim.get_analytics()
+    };
+    let limit = if opts.show_end_of_day {
+        app.primary.sim.get_end_of_day()
+    } else {
+        app.primary.sim.time()
+    };
+    let mut per_hour: Vec<Histogram<Duration>> = Vec::new();
+    if let Some(list) = data.intersection_delays.get(&i) {
+        for (t, dt, mode) in list {
+            if *t > limit {
+                break;
+            }
+            if opts.disabled_modes.contains(mode) {
+                continue;
+            }
+            let hour = t.get_hours();
+            while per_hour.len() <= hour {
+                per_hour.push(Histogram::new());
+            }
+            per_hour[hour].add(*dt);
+        }
+    }
+
+    let select = |stat: Statistic| -> Vec<(Time, Duration)> {
+        per_hour
+            .iter()
+            .enumerate()
+            .filter(|(_, hgram)| hgram.count() > 0)
+            .map(|(hour, hgram)| {
+                (
+                    Time::START_OF_DAY + Duration::hours(hour),
+                    hgram.select(stat),
+                )
+            })
+            .collect()
+    };
+    let series = vec![
+        (Statistic::P50, Color::WHITE),
+        (Statistic::Percentile(85), Color::ORANGE),
+    ]
+    .into_iter()
+    .map(|(stat, color)| Series {
+        label: stat.to_string(),
+        color,
+        pts: select(stat),
+        dashed: false,
+    })
+    .collect();
+    // Show the full spread as a shaded range, instead of more lines
+    let mut plot_opts = PlotOptions::fixed();
+    plot_opts.band = Some(Band {
+        color: Color::WHITE.alpha(0.2),
+        lower: select(Statistic::Min),
+        upper: select(Statistic::Max),
+    });
+
+    Widget::col(vec![
+        Line("Delay per hour")
+            .small_heading()
+            .draw(ctx)
+            .margin_below(10),
+        LinePlot::new(ctx, series, plot_opts),
+        "The shaded range covers the minimum to maximum delay"
+            .draw_text(ctx)
+            .margin_above(10),
+    ])
+    .padding(10)
+    .bg(app.cs.inner_panel)
+    .outline(2.0, Color::WHITE)
+}
+
 fn header(
     ctx: &EventCtx,
     app: &App,
@@ -3,6 +3,7 @@ use crate::info::{header_btns, make_table, make_tabs, throughput, DataOptions, D
 use abstutil::prettyprint_usize;
 use ezgui::{Btn, EventCtx, Line, LinePlot, PlotOptions, Series, Text, TextExt, Widget};
 use map_model::{LaneID, OriginalLane};
+use sim::TripMode;
 use std::collections::HashSet;
 
 pub fn info(ctx: &EventCtx, app: &App, details: &mut Details, id: LaneID) -> Vec<Widget> {
@@ -18,12 +19,15 @@ pub fn info(ctx: &EventCtx, app: &App, details: &mut Details, id: LaneID) -> Vec
     }
 
     if l.is_parking() {
+        let capacity = l.number_parking_spots();
+        let free = app.primary.sim.get_free_onstreet_spots(l.id).len();
         kv.push((
             "Parking",
             format!(
-                "{} / {} spots available",
-                app.primary.sim.get_free_onstreet_spots(l.id).len(),
-                l.number_parking_spots()
+                "{} of {} spots occupied ({} available)",
+                capacity - free,
+                capacity,
+                free
             ),
         ));
     } else {
@@ -44,6 +48,7 @@ pub fn info(ctx: &EventCtx, app: &App, details: &mut Details, id: LaneID) -> Vec
                 l.id,
                 capacity,
             ),
+            dashed: false,
         }];
         if app.has_prebaked().is_some() {
             series.push(Series {
@@ -54,6 +59,7 @@ pub fn info(ctx: &EventCtx, app: &App, details: &mut Details, id: LaneID) -> Vec
                     l.id,
                     capacity,
                 ),
+                dashed: false,
             });
         }
         rows.push("Parking spots available".draw_text(ctx).margin_above(10));
@@ -65,6 +71,7 @@ pub fn info(ctx: &EventCtx, app: &App, details: &mut Details, id: LaneID) -> Vec
                 max_x: None,
                 max_y: Some(capacity),
                 disabled: HashSet::new(),
+                band: None,
             },
         ));
     }
@@ -175,6 +182,19 @@ pub fn traffic(
         "Since midnight: {} agents crossed",
         prettyprint_usize(app.primary.sim.get_analytics().road_thruput.total_for(r.id))
     )));
+    for mode in TripMode::all() {
+        txt.add(Line(format!(
+            "  {}: {}",
+            mode.noun(),
+            prettyprint_usize(
+                app.primary
+                    .sim
+                    .get_analytics()
+                    .road_thruput
+                    .total_for_mode(r.id, mode)
+            )
+        )));
+    }
     rows.push(txt.draw(ctx));
 
     rows.push(opts.to_controls(ctx, app).margin_below(10));
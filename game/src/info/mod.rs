@@ -9,7 +9,7 @@ mod trip;
 
 use crate::app::App;
 use crate::common::Warping;
-use crate::game::Transition;
+use crate::game::{msg, Transition};
 use crate::helpers::{color_for_mode, hotkey_btn, ID};
 use crate::sandbox::{SandboxMode, TimeWarpScreen};
 use ezgui::{
@@ -17,7 +17,7 @@ use ezgui::{
     HorizontalAlignment, Key, Line, LinePlot, Outcome, PlotOptions, Series, TextExt,
     VerticalAlignment, Widget,
 };
-use geom::{Circle, Distance, Time};
+use geom::{Circle, Distance, Pt2D, Time};
 use map_model::{AreaID, BuildingID, BusStopID, IntersectionID, LaneID, ParkingLotID};
 use sim::{
     AgentID, Analytics, CarID, ParkingSpot, PedestrianID, PersonID, PersonState, TripID, TripMode,
@@ -38,9 +38,17 @@ pub struct InfoPanel {
     hyperlinks: HashMap<String, Tab>,
     warpers: HashMap<String, ID>,
     time_warpers: HashMap<String, (TripID, Time)>,
+    timeline_exports: HashMap<String, (TripID, bool)>,
+    replays: HashMap<String, TripID>,
 
     // For drawing the OSD only
     cached_actions: Vec<Key>,
+    // Keep the camera centered on the object, if it moves
+    follow: bool,
+    // Which car "jump to next owned car" visits next
+    next_owned_car: usize,
+    // See Details
+    bldg_owners: Option<(BuildingID, Vec<PersonID>)>,
 }
 
 // TODO We need a separate, weaker form of PartialEq for this to detect when we're on the "current"
@@ -188,14 +196,34 @@ pub struct Details {
     pub hyperlinks: HashMap<String, Tab>,
     pub warpers: HashMap<String, ID>,
     pub time_warpers: HashMap<String, (TripID, Time)>,
+    // Trip timelines that can be saved as images, and whether they use prebaked phases
+    pub timeline_exports: HashMap<String, (TripID, bool)>,
+    // Restart the sim with just this trip
+    pub replays: HashMap<String, TripID>,
+    // Where the camera should be centered, if the panel is following the object
+    pub center_on: Option<Pt2D>,
+    // People whose day starts at a building. Finding them means looking through everybody, so
+    // it's done once when the panel opens, then kept through live updates.
+    pub bldg_owners: Option<(BuildingID, Vec<PersonID>)>,
 }
 
 impl InfoPanel {
     pub fn new(
+        ctx: &mut EventCtx,
+        app: &App,
+        tab: Tab,
+        ctx_actions: &mut dyn ContextualActions,
+    ) -> InfoPanel {
+        InfoPanel::new_with_follow(ctx, app, tab, ctx_actions, true, None)
+    }
+
+    fn new_with_follow(
         ctx: &mut EventCtx,
         app: &App,
         mut tab: Tab,
         ctx_actions: &mut dyn ContextualActions,
+        follow: bool,
+        bldg_owners: Option<(BuildingID, Vec<PersonID>)>,
     ) -> InfoPanel {
         let mut details = Details {
             unzoomed: GeomBatch::new(),
@@ -203,6 +231,10 @@ impl InfoPanel {
             hyperlinks: HashMap::new(),
             warpers: HashMap::new(),
             time_warpers: HashMap::new(),
+            timeline_exports: HashMap::new(),
+            replays: HashMap::new(),
+            center_on: None,
+            bldg_owners,
         };
 
         let (mut col, main_tab) = match tab {
@@ -315,6 +347,14 @@ impl InfoPanel {
             }
         }
 
+        if let Some(pt) = details.center_on {
+            // Toggles following; see event()
+            col.push(Checkbox::text(ctx, "follow", hotkey(Key::V), follow).margin(5));
+            if follow {
+                ctx.canvas.center_on_map_pt(pt);
+            }
+        }
+
         InfoPanel {
             tab,
             time: app.primary.sim.time(),
@@ -332,7 +372,12 @@ impl InfoPanel {
             hyperlinks: details.hyperlinks,
             warpers: details.warpers,
             time_warpers: details.time_warpers,
+            timeline_exports: details.timeline_exports,
+            replays: details.replays,
             cached_actions,
+            follow,
+            next_owned_car: 0,
+            bldg_owners: details.bldg_owners,
         }
     }
 
@@ -353,8 +398,16 @@ impl InfoPanel {
 
         // Live update?
         if app.primary.sim.time() != self.time || ctx_actions.is_paused() != self.is_paused {
-            let mut new = InfoPanel::new(ctx, app, self.tab.clone(), ctx_actions);
+            let mut new = InfoPanel::new_with_follow(
+                ctx,
+                app,
+                self.tab.clone(),
+                ctx_actions,
+                self.follow,
+                self.bldg_owners.take(),
+            );
             new.composite.restore(ctx, &self.composite);
+            new.next_owned_car = self.next_owned_car;
             *self = new;
             return (false, None);
         }
@@ -393,6 +446,23 @@ impl InfoPanel {
                     } else {
                         return (false, None);
                     }
+                } else if let Some((trip, prebaked)) = self.timeline_exports.get(&action) {
+                    let path = abstutil::path_player_timeline(
+                        app.primary.map.get_name(),
+                        &format!(
+                            "trip_{}_{}",
+                            trip.0,
+                            chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")
+                        ),
+                    );
+                    let result = trip::timeline_image(ctx, app, *trip, *prebaked).save_png(&path);
+                    (
+                        false,
+                        Some(Transition::Push(match result {
+                            Ok(()) => msg("Saved timeline", vec![format!("Saved to {}", path)]),
+                            Err(err) => msg("Couldn't save timeline", vec![err]),
+                        })),
+                    )
                 } else if action.starts_with("examine trip phase") {
                     // Don't do anything! Just using buttons for convenient tooltips.
                     (false, None)
@@ -407,6 +477,17 @@ impl InfoPanel {
                             &mut app.primary,
                         ))),
                     )
+                } else if app.primary.frozen
+                    && (self.time_warpers.contains_key(&action)
+                        || self.replays.contains_key(&action))
+                {
+                    (
+                        false,
+                        Some(Transition::Push(msg(
+                            "Error",
+                            vec!["Time is frozen. Unfreeze it from the speed controls first."],
+                        ))),
+                    )
                 } else if let Some((trip, time)) = self.time_warpers.get(&action) {
                     let trip = *trip;
                     let time = *time;
@@ -436,6 +517,52 @@ impl InfoPanel {
                             },
                         ))),
                     )
+                } else if let Some(trip) = self.replays.get(&action).cloned() {
+                    (
+                        false,
+                        Some(Transition::ReplaceWithData(Box::new(
+                            move |state, ctx, app| {
+                                let sandbox = state.downcast::<SandboxMode>().ok().unwrap();
+                                trip::replay_in_isolation(ctx, app, sandbox, trip)
+                            },
+                        ))),
+                    )
+                } else if action == "jump to next owned car" {
+                    // The building tab found the owners when it was built
+                    let owners = match self.bldg_owners {
+                        Some((_, ref owners)) => owners,
+                        None => unreachable!(),
+                    };
+                    let pts: Vec<Pt2D> = building::owned_parked_cars(app, owners)
+                        .into_iter()
+                        .filter_map(|c| ID::Car(c).canonical_point(&app.primary))
+                        .collect();
+                    if pts.is_empty() {
+                        return (false, None);
+                    }
+                    // Cars might've left since last time
+                    let pt = pts[self.next_owned_car % pts.len()];
+                    self.next_owned_car = (self.next_owned_car + 1) % pts.len();
+                    (
+                        false,
+                        Some(Transition::Push(Warping::new(
+                            ctx,
+                            pt,
+                            Some(10.0),
+                            None,
+                            &mut app.primary,
+                        ))),
+                    )
+                } else if action == "export delays to CSV" {
+                    let i = match maybe_id {
+                        Some(ID::Intersection(i)) => i,
+                        _ => unreachable!(),
+                    };
+                    let result = match intersection::export_delays(app, i) {
+                        Ok(path) => msg("Exported delays", vec![format!("Wrote {}", path)]),
+                        Err(err) => msg("Export failed", vec![err.to_string()]),
+                    };
+                    (false, Some(Transition::Push(result)))
                 } else if action == "copy OriginalLane" {
                     // TODO Not happy about this :(
                     lane::copy_orig_lane(
@@ -454,10 +581,24 @@ impl InfoPanel {
                 }
             }
             None => {
+                if self.composite.has_widget("follow")
+                    && self.composite.is_checked("follow") != self.follow
+                {
+                    self.follow = !self.follow;
+                    return (false, None);
+                }
+
                 // Maybe a non-click action should change the tab. Aka, checkboxes/dropdowns/etc on
                 // a tab.
                 if let Some(new_tab) = self.tab.changed_settings(&self.composite) {
-                    let mut new = InfoPanel::new(ctx, app, new_tab, ctx_actions);
+                    let mut new = InfoPanel::new_with_follow(
+                        ctx,
+                        app,
+                        new_tab,
+                        ctx_actions,
+                        self.follow,
+                        self.bldg_owners.take(),
+                    );
                     new.composite.restore(ctx, &self.composite);
                     *self = new;
                 }
@@ -521,15 +662,17 @@ fn throughput<F: Fn(&Analytics) -> Vec<(TripMode, Vec<(Time, usize)>)>>(
             label: m.noun().to_string(),
             color: color_for_mode(app, m),
             pts,
+            dashed: false,
         })
         .collect::<Vec<_>>();
     if opts.show_before {
-        // TODO Ahh these colors don't show up differently at all.
+        // The colors alone don't show up differently enough, so dash the baseline.
         for (m, pts) in get_data(app.prebaked()) {
             series.push(Series {
                 label: m.noun().to_string(),
                 color: color_for_mode(app, m).alpha(0.3),
                 pts,
+                dashed: true,
             });
         }
     }
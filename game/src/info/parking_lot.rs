@@ -27,6 +27,7 @@ pub fn info(ctx: &mut EventCtx, app: &App, details: &mut Details, id: ParkingLot
             pl.id,
             capacity,
         ),
+        dashed: false,
     }];
     if app.has_prebaked().is_some() {
         series.push(Series {
@@ -37,6 +38,7 @@ pub fn info(ctx: &mut EventCtx, app: &App, details: &mut Details, id: ParkingLot
                 pl.id,
                 capacity,
             ),
+            dashed: false,
         });
     }
     rows.push("Parking spots available".draw_text(ctx).margin_above(10));
@@ -48,6 +50,7 @@ pub fn info(ctx: &mut EventCtx, app: &App, details: &mut Details, id: ParkingLot
             max_x: None,
             max_y: Some(capacity),
             disabled: HashSet::new(),
+            band: None,
         },
     ));
 
@@ -36,6 +36,41 @@ pub fn trips(
     let sim = &app.primary.sim;
     let person = sim.get_person(id);
 
+    // Step through the person's day, one trip at a time
+    if open_trips.len() == 1 {
+        let current = *open_trips.keys().next().unwrap();
+        let idx = person.trips.iter().position(|t| *t == current).unwrap();
+        rows.push(
+            Widget::row(vec![
+                if idx == 0 {
+                    Btn::text_fg("<").inactive(ctx)
+                } else {
+                    details.hyperlinks.insert(
+                        "previous trip".to_string(),
+                        Tab::PersonTrips(id, OpenTrip::single(person.trips[idx - 1])),
+                    );
+                    Btn::text_fg("<").build(ctx, "previous trip", None)
+                }
+                .margin_right(10),
+                format!("Trip {}/{}", idx + 1, person.trips.len())
+                    .draw_text(ctx)
+                    .centered_vert()
+                    .margin_right(10),
+                if idx == person.trips.len() - 1 {
+                    Btn::text_fg(">").inactive(ctx)
+                } else {
+                    details.hyperlinks.insert(
+                        "next trip".to_string(),
+                        Tab::PersonTrips(id, OpenTrip::single(person.trips[idx + 1])),
+                    );
+                    Btn::text_fg(">").build(ctx, "next trip", None)
+                },
+            ])
+            .centered_horiz()
+            .margin_below(10),
+        );
+    }
+
     // I'm sorry for bad variable names
     let mut wheres_waldo = true;
     for (idx, t) in person.trips.iter().enumerate() {
@@ -112,7 +147,9 @@ pub fn trips(
                 (
                     "broken",
                     Color::hex("#EB3223"),
-                    open_trips.get(t).map(|_| trip::aborted(ctx, app, *t)),
+                    open_trips
+                        .get(t)
+                        .map(|_| trip::aborted(ctx, app, *t, details)),
                 )
             }
             TripResult::TripDoesntExist => unreachable!(),
@@ -449,7 +486,7 @@ pub fn parked_car(
     if let Some(p) = app.primary.sim.lookup_parked_car(id) {
         match p.spot {
             ParkingSpot::Onstreet(_, _) | ParkingSpot::Lot(_, _) => {
-                ctx.canvas.center_on_map_pt(
+                details.center_on = Some(
                     app.primary
                         .sim
                         .canonical_pt_for_agent(AgentID::Car(id), &app.primary.map)
@@ -457,8 +494,7 @@ pub fn parked_car(
                 );
             }
             ParkingSpot::Offstreet(b, _) => {
-                ctx.canvas
-                    .center_on_map_pt(app.primary.map.get_b(b).polygon.center());
+                details.center_on = Some(app.primary.map.get_b(b).polygon.center());
                 rows.push(
                     format!("Parked inside {}", app.primary.map.get_b(b).address).draw_text(ctx),
                 );
@@ -483,8 +519,7 @@ fn header(
 
     let (current_trip, (descr, maybe_icon)) = match app.primary.sim.get_person(id).state {
         PersonState::Inside(b) => {
-            ctx.canvas
-                .center_on_map_pt(app.primary.map.get_b(b).label_center);
+            details.center_on = Some(app.primary.map.get_b(b).label_center);
             building::draw_occupants(details, app, b, Some(id));
             (
                 None,
@@ -495,7 +530,7 @@ fn header(
             Some(t),
             if let Some(a) = app.primary.sim.trip_to_agent(t).ok() {
                 if let Some(pt) = app.primary.sim.canonical_pt_for_agent(a, &app.primary.map) {
-                    ctx.canvas.center_on_map_pt(pt);
+                    details.center_on = Some(pt);
                 }
                 match a {
                     AgentID::Pedestrian(_) => (
@@ -1,6 +1,9 @@
 use crate::app::App;
+use crate::game::{msg, State};
 use crate::helpers::{color_for_trip_phase, ID};
 use crate::info::{make_table, Details, Tab};
+use crate::sandbox::{GameplayMode, SandboxMode, TimeWarpScreen};
+use abstutil::Timer;
 use ezgui::{
     Btn, Color, EventCtx, GeomBatch, Line, LinePlot, PlotOptions, RewriteColor, Series, Text,
     TextExt, Widget,
@@ -8,7 +11,9 @@ use ezgui::{
 use geom::{ArrowCap, Distance, Duration, PolyLine, Polygon, Pt2D, Time};
 use map_model::{Map, Path, PathStep};
 use maplit::btreemap;
-use sim::{AgentID, PersonID, TripEndpoint, TripID, TripPhase, TripPhaseType, VehicleType};
+use sim::{
+    AgentID, PersonID, Scenario, TripEndpoint, TripID, TripPhase, TripPhaseType, VehicleType,
+};
 use std::collections::BTreeMap;
 
 #[derive(Clone)]
@@ -119,6 +124,9 @@ pub fn ongoing(
             ]),
         ]));
     }
+    if let Some(legs) = transit_legs(ctx, app, &phases, col_width) {
+        col.push(legs);
+    }
 
     col.push(make_timeline(
         ctx,
@@ -127,6 +135,7 @@ pub fn ongoing(
         open_trip,
         details,
         phases,
+        false,
         Some(props.dist_crossed / props.total_dist),
     ));
 
@@ -163,19 +172,20 @@ pub fn future(
 
         let phases = app.prebaked().get_trip_phases(trip, &app.primary.map);
         col.push(make_timeline(
-            ctx, app, trip, open_trip, details, phases, None,
+            ctx, app, trip, open_trip, details, phases, true, None,
         ));
     } else {
-        // TODO Warp buttons. make_table is showing its age.
-        let (_, _, name1) = endpoint(&trip_start, &app.primary.map);
-        let (_, _, name2) = endpoint(&trip_end, &app.primary.map);
         col.extend(make_table(
             ctx,
-            vec![
-                ("Departure", start_time.ampm_tostring()),
-                ("From", name1),
-                ("To", name2),
-            ],
+            vec![("Departure", start_time.ampm_tostring())],
+        ));
+        col.extend(endpoint_rows(
+            ctx,
+            app,
+            trip,
+            &trip_start,
+            &trip_end,
+            details,
         ));
 
         col.push(
@@ -204,7 +214,8 @@ pub fn finished(
     details: &mut Details,
 ) -> Widget {
     let (start_time, _, _, _) = app.primary.sim.trip_info(trip);
-    let phases = if open_trips[&trip].show_after {
+    let prebaked = !open_trips[&trip].show_after;
+    let phases = if !prebaked {
         app.primary
             .sim
             .get_analytics()
@@ -279,8 +290,29 @@ pub fn finished(
                 .force_width_pct(ctx, col_width),
             waiting.to_string().draw_text(ctx),
         ]));
+
+        if let Some(walked) = parking_walk(app, trip, &phases, details) {
+            col.push(Widget::row(vec![
+                Widget::row(vec![Line("Walk from parking").secondary().draw(ctx)])
+                    .force_width_pct(ctx, col_width),
+                walked.describe_rounded().draw_text(ctx),
+            ]));
+        }
+
+        if let Some(legs) = transit_legs(ctx, app, &phases, col_width) {
+            col.push(legs);
+        }
     }
 
+    details
+        .replays
+        .insert(format!("replay {} in isolation", trip), trip);
+    col.push(
+        Btn::text_fg("replay this trip in isolation")
+            .build(ctx, format!("replay {} in isolation", trip), None)
+            .margin_above(5),
+    );
+
     col.push(make_timeline(
         ctx,
         app,
@@ -288,13 +320,14 @@ pub fn finished(
         open_trips.get_mut(&trip).unwrap(),
         details,
         phases,
+        prebaked,
         None,
     ));
 
     Widget::col(col)
 }
 
-pub fn aborted(ctx: &mut EventCtx, app: &App, trip: TripID) -> Widget {
+pub fn aborted(ctx: &mut EventCtx, app: &App, trip: TripID, details: &mut Details) -> Widget {
     let (start_time, trip_start, trip_end, _) = app.primary.sim.trip_info(trip);
 
     let mut col = vec![Text::from_multiline(vec![
@@ -303,21 +336,49 @@ pub fn aborted(ctx: &mut EventCtx, app: &App, trip: TripID) -> Widget {
     ])
     .draw(ctx)];
 
-    // TODO Warp buttons. make_table is showing its age.
-    let (_, _, name1) = endpoint(&trip_start, &app.primary.map);
-    let (_, _, name2) = endpoint(&trip_end, &app.primary.map);
     col.extend(make_table(
         ctx,
-        vec![
-            ("Departure", start_time.ampm_tostring()),
-            ("From", name1),
-            ("To", name2),
-        ],
+        vec![("Departure", start_time.ampm_tostring())],
+    ));
+    col.extend(endpoint_rows(
+        ctx,
+        app,
+        trip,
+        &trip_start,
+        &trip_end,
+        details,
     ));
 
     Widget::col(col)
 }
 
+// Like make_table, but the endpoints are buttons that warp there.
+fn endpoint_rows(
+    ctx: &mut EventCtx,
+    app: &App,
+    trip: TripID,
+    trip_start: &TripEndpoint,
+    trip_end: &TripEndpoint,
+    details: &mut Details,
+) -> Vec<Widget> {
+    let mut rows = Vec::new();
+    for (label, action, endpt) in vec![
+        ("From", format!("jump to start of {}", trip), trip_start),
+        ("To", format!("jump to goal of {}", trip), trip_end),
+    ] {
+        let (id, _, name) = endpoint(endpt, &app.primary.map);
+        details.warpers.insert(action.clone(), id);
+        rows.push(Widget::row(vec![
+            Line(label).secondary().draw(ctx),
+            Btn::text_bg2(name)
+                .build(ctx, action, None)
+                .centered_vert()
+                .align_right(),
+        ]));
+    }
+    rows
+}
+
 fn make_timeline(
     ctx: &mut EventCtx,
     app: &App,
@@ -325,6 +386,8 @@ fn make_timeline(
     open_trip: &mut OpenTrip,
     details: &mut Details,
     phases: Vec<TripPhase>,
+    // Were the phases taken from the prebaked results?
+    prebaked: bool,
     progress_along_path: Option<f64>,
 ) -> Widget {
     let map = &app.primary.map;
@@ -485,28 +548,7 @@ fn make_timeline(
             }
         }
         normal.append(
-            GeomBatch::screenspace_svg(
-                ctx.prerender,
-                match p.phase_type {
-                    TripPhaseType::Driving => "../data/system/assets/timeline/driving.svg",
-                    TripPhaseType::Walking => "../data/system/assets/timeline/walking.svg",
-                    TripPhaseType::Biking => "../data/system/assets/timeline/biking.svg",
-                    TripPhaseType::Parking => "../data/system/assets/timeline/parking.svg",
-                    TripPhaseType::WaitingForBus(_, _) => {
-                        "../data/system/assets/timeline/waiting_for_bus.svg"
-                    }
-                    TripPhaseType::RidingBus(_, _, _) => {
-                        "../data/system/assets/timeline/riding_bus.svg"
-                    }
-                    TripPhaseType::Aborted | TripPhaseType::Finished => unreachable!(),
-                    TripPhaseType::DelayedStart => {
-                        "../data/system/assets/timeline/delayed_start.svg"
-                    }
-                    // TODO What icon should represent this?
-                    TripPhaseType::Remote => "../data/system/assets/timeline/delayed_start.svg",
-                },
-            )
-            .centered_on(
+            GeomBatch::screenspace_svg(ctx.prerender, phase_icon(p.phase_type)).centered_on(
                 // TODO Hardcoded layouting...
                 Pt2D::new(0.5 * phase_width, -20.0 * ctx.get_scale_factor()),
             ),
@@ -626,6 +668,15 @@ fn make_timeline(
     if path_impossible {
         col.push("Map edits have disconnected the path taken before".draw_text(ctx));
     }
+
+    details
+        .timeline_exports
+        .insert(format!("save timeline of {}", trip), (trip, prebaked));
+    col.push(
+        Btn::text_fg("save timeline as image")
+            .build(ctx, format!("save timeline of {}", trip), None)
+            .margin_above(5),
+    );
     // TODO This just needs too much more work
     if false {
         col.extend(elevation);
@@ -633,6 +684,252 @@ fn make_timeline(
     Widget::col(col)
 }
 
+// Restart the simulation with only the person taking this trip, then watch it from the start.
+pub fn replay_in_isolation(
+    ctx: &mut EventCtx,
+    app: &mut App,
+    sandbox: Box<SandboxMode>,
+    trip: TripID,
+) -> Vec<Box<dyn State>> {
+    let person = app.primary.sim.trip_to_person(trip);
+    let trip_idx = app
+        .primary
+        .sim
+        .get_person(person)
+        .trips
+        .iter()
+        .position(|t| *t == trip)
+        .unwrap();
+    let (start_time, _, _, _) = app.primary.sim.trip_info(trip);
+
+    let spec = sandbox
+        .gameplay_mode
+        .scenario(
+            &app.primary.map,
+            app.primary.current_flags.num_agents,
+            app.primary.current_flags.sim_flags.make_rng(),
+            &mut Timer::new("load scenario"),
+        )
+        .and_then(|s| s.people.into_iter().find(|p| p.id == person));
+    let mut spec = match spec {
+        Some(spec) => spec,
+        None => {
+            return vec![
+                sandbox,
+                msg(
+                    "Can't replay this trip",
+                    vec!["This person isn't part of the scenario"],
+                ),
+            ];
+        }
+    };
+
+    let mode = GameplayMode::Freeform(sandbox.gameplay_mode.map_path());
+    let mut sandbox = ctx.loading_screen("replay trip", |ctx, timer| {
+        let sandbox = SandboxMode::new(ctx, app, mode);
+        // Freeform already seeded the buses
+        let mut scenario = Scenario::empty(&app.primary.map, "replay");
+        spec.id = PersonID(app.primary.sim.get_all_people().len());
+        scenario.people.push(spec);
+        scenario.instantiate(
+            &mut app.primary.sim,
+            &app.primary.map,
+            &mut app.primary.current_flags.sim_flags.make_rng(),
+            timer,
+        );
+        app.primary
+            .sim
+            .tiny_step(&app.primary.map, &mut app.primary.sim_cb);
+        Box::new(sandbox)
+    });
+
+    let new_person = PersonID(app.primary.sim.get_all_people().len() - 1);
+    let new_trip = app.primary.sim.get_person(new_person).trips[trip_idx];
+    let mut actions = sandbox.contextual_actions();
+    sandbox.controls.common.as_mut().unwrap().launch_info_panel(
+        ctx,
+        app,
+        Tab::PersonTrips(new_person, OpenTrip::single(new_trip)),
+        &mut actions,
+    );
+
+    vec![sandbox, TimeWarpScreen::new(ctx, app, start_time, false)]
+}
+
+// For trips using transit, list each walk, wait, and ride with how long it took
+// Draws the trip's timeline above the route it took, so it can be saved as an image. Only built
+// on request, since tracing the routes is expensive.
+pub fn timeline_image(ctx: &EventCtx, app: &App, trip: TripID, prebaked: bool) -> GeomBatch {
+    let map = &app.primary.map;
+    let phases = if prebaked {
+        app.prebaked().get_trip_phases(trip, map)
+    } else {
+        app.primary.sim.get_analytics().get_trip_phases(trip, map)
+    };
+    let (start_time, _, _, _) = app.primary.sim.trip_info(trip);
+    let end_time = phases.last().as_ref().and_then(|p| p.end_time);
+    let total_duration_so_far = end_time.unwrap_or_else(|| app.primary.sim.time()) - start_time;
+
+    let total_width = 600.0;
+    let mut timeline = GeomBatch::new();
+    let mut route = GeomBatch::new();
+    let mut x = 0.0;
+    for p in phases {
+        let color = color_for_trip_phase(app, p.phase_type).alpha(0.7);
+        let duration = p.end_time.unwrap_or_else(|| app.primary.sim.time()) - p.start_time;
+        let phase_width = if total_duration_so_far == Duration::ZERO {
+            0.0
+        } else {
+            total_width * (duration / total_duration_so_far)
+        };
+        timeline.push(
+            color,
+            Polygon::rectangle(phase_width, 15.0).translate(x, 0.0),
+        );
+        timeline.append(
+            GeomBatch::mapspace_svg(ctx.prerender, phase_icon(p.phase_type))
+                .centered_on(Pt2D::new(x + 0.5 * phase_width, -20.0)),
+        );
+        x += phase_width;
+
+        if let Some((dist, ref path)) = p.path {
+            if let Some(trace) = path.trace(map, dist, None) {
+                route.push(color, trace.make_polygons(Distance::meters(10.0)));
+            }
+        }
+    }
+    timeline.append(
+        Text::from(Line(start_time.ampm_tostring()))
+            .render_to_batch(ctx.prerender)
+            .translate(0.0, 20.0),
+    );
+    if let Some(t) = end_time {
+        let txt = Text::from(Line(t.ampm_tostring())).render_to_batch(ctx.prerender);
+        let width = txt.get_dims().width;
+        timeline.append(txt.translate(total_width - width, 20.0));
+    }
+
+    let mut image = timeline.autocrop();
+    let top = image.get_dims().height + 20.0;
+    // Shrink or grow the route to match the width of the timeline
+    let route = route.autocrop();
+    let width = route.get_dims().width.max(1.0);
+    image.append(route.scale(total_width / width).translate(0.0, top));
+    let dims = image.get_dims();
+    let mut bg = GeomBatch::from(vec![(
+        app.cs.panel_bg,
+        Polygon::rectangle(dims.width, dims.height),
+    )]);
+    bg.append(image);
+    bg
+}
+
+fn phase_icon(phase_type: TripPhaseType) -> &'static str {
+    match phase_type {
+        TripPhaseType::Driving => "../data/system/assets/timeline/driving.svg",
+        TripPhaseType::Walking => "../data/system/assets/timeline/walking.svg",
+        TripPhaseType::Biking => "../data/system/assets/timeline/biking.svg",
+        TripPhaseType::Parking => "../data/system/assets/timeline/parking.svg",
+        TripPhaseType::WaitingForBus(_, _) => "../data/system/assets/timeline/waiting_for_bus.svg",
+        TripPhaseType::RidingBus(_, _, _) => "../data/system/assets/timeline/riding_bus.svg",
+        TripPhaseType::Aborted | TripPhaseType::Finished => unreachable!(),
+        TripPhaseType::DelayedStart => "../data/system/assets/timeline/delayed_start.svg",
+        // TODO What icon should represent this?
+        TripPhaseType::Remote => "../data/system/assets/timeline/delayed_start.svg",
+    }
+}
+
+fn transit_legs(
+    ctx: &mut EventCtx,
+    app: &App,
+    phases: &Vec<TripPhase>,
+    col_width: usize,
+) -> Option<Widget> {
+    let map = &app.primary.map;
+    let is_transit = |p: &TripPhase| match p.phase_type {
+        TripPhaseType::WaitingForBus(_, _) | TripPhaseType::RidingBus(_, _, _) => true,
+        _ => false,
+    };
+    let first_transit = phases.iter().position(is_transit)?;
+    let last_transit = phases.iter().rposition(is_transit)?;
+
+    let mut legs = Vec::new();
+    for (idx, p) in phases.iter().enumerate() {
+        let label = match p.phase_type {
+            TripPhaseType::Walking => {
+                if idx < first_transit {
+                    "Walk to the stop".to_string()
+                } else if idx > last_transit {
+                    "Walk from the stop".to_string()
+                } else {
+                    "Walk to transfer".to_string()
+                }
+            }
+            TripPhaseType::WaitingForBus(r, _) => format!("Wait for bus {}", map.get_br(r).name),
+            TripPhaseType::RidingBus(r, _, _) => format!("Ride bus {}", map.get_br(r).name),
+            x => x.describe(map),
+        };
+        // The current leg of an ongoing trip
+        let end_time = p.end_time.unwrap_or_else(|| app.primary.sim.time());
+        legs.push(
+            Text::from_all(vec![
+                Line(label),
+                Line(format!(" {}", end_time - p.start_time)).secondary(),
+            ])
+            .draw(ctx),
+        );
+    }
+
+    Some(Widget::row(vec![
+        Widget::row(vec![Line("Transit legs").secondary().draw(ctx)])
+            .force_width_pct(ctx, col_width),
+        Widget::col(legs),
+    ]))
+}
+
+// If the trip ended by parking and walking to a building, draw a line from the parking spot to the
+// building and return the distance walked.
+fn parking_walk(
+    app: &App,
+    trip: TripID,
+    phases: &Vec<TripPhase>,
+    details: &mut Details,
+) -> Option<Distance> {
+    let map = &app.primary.map;
+    let b = match app.primary.sim.trip_info(trip).2 {
+        TripEndpoint::Bldg(b) => b,
+        _ => {
+            return None;
+        }
+    };
+    let idx = phases.windows(2).rposition(|pair| {
+        pair[0].phase_type == TripPhaseType::Parking && pair[1].phase_type == TripPhaseType::Walking
+    })?;
+    let (dist, ref path) = phases[idx + 1].path.as_ref()?;
+    let trace = path.trace(map, *dist, None)?;
+
+    let color = color_for_trip_phase(app, TripPhaseType::Parking);
+    if let Some(pl) = PolyLine::maybe_new(vec![trace.first_pt(), map.get_b(b).polygon.center()]) {
+        details.unzoomed.extend(
+            color,
+            pl.dashed_lines(
+                Distance::meters(5.0),
+                Distance::meters(10.0),
+                Distance::meters(5.0),
+            ),
+        );
+        details.zoomed.extend(
+            color,
+            pl.dashed_lines(
+                Distance::meters(1.0),
+                Distance::meters(2.0),
+                Distance::meters(1.0),
+            ),
+        );
+    }
+    Some(trace.length())
+}
+
 fn make_elevation(ctx: &EventCtx, color: Color, walking: bool, path: &Path, map: &Map) -> Widget {
     let mut pts: Vec<(Distance, Distance)> = Vec::new();
     let mut dist = Distance::ZERO;
@@ -663,6 +960,7 @@ fn make_elevation(ctx: &EventCtx, color: Color, walking: bool, path: &Path, map:
                     )
                 })
                 .collect(),
+            dashed: false,
         }],
         PlotOptions::fixed(),
     )
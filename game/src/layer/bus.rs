@@ -1,19 +1,24 @@
 use crate::app::App;
-use crate::common::ColorDiscrete;
+use crate::common::{ColorDiscrete, ColorLegend, ColorNetwork};
 use crate::layer::{Layer, LayerOutcome};
 use ezgui::{
     hotkey, Btn, Color, Composite, Drawable, EventCtx, GeomBatch, GfxCtx, HorizontalAlignment, Key,
     Line, Text, TextExt, VerticalAlignment, Widget,
 };
-use geom::{Circle, Distance, Pt2D, Time};
-use map_model::{BusRouteID, PathConstraints, PathRequest, PathStep};
+use geom::{Circle, Distance, Duration, Pt2D, Time};
+use map_model::{BusRoute, BusRouteID, LaneID, Map, PathConstraints, PathRequest, PathStep};
+use std::collections::BTreeSet;
+
+// Buses arriving at a stop closer together than this are bunched
+const BUNCHING_THRESHOLD: Duration = Duration::const_seconds(120.0);
 
 // TODO This maybe shouldn't be a layer
 pub struct ShowBusRoute {
     time: Time,
     route: BusRouteID,
     labels: Vec<(Text, Pt2D)>,
-    bus_locations: Vec<Pt2D>,
+    // Bunched buses are highlighted
+    bus_locations: Vec<(Pt2D, bool)>,
 
     composite: Composite,
     unzoomed: Drawable,
@@ -42,7 +47,7 @@ impl Layer for ShowBusRoute {
         } else {
             g.redraw(&self.zoomed);
         }
-        self.composite.draw(g);
+        Layer::draw_panel(g, app, &self.composite);
 
         // TODO Do this once
         let mut screen_batch = GeomBatch::new();
@@ -61,8 +66,11 @@ impl Layer for ShowBusRoute {
 
         let mut batch = GeomBatch::new();
         let radius = Distance::meters(20.0) / g.canvas.cam_zoom;
-        for pt in &self.bus_locations {
-            batch.push(Color::BLUE, Circle::new(*pt, radius).to_polygon());
+        for (pt, bunched) in &self.bus_locations {
+            batch.push(
+                if *bunched { Color::RED } else { Color::BLUE },
+                Circle::new(*pt, radius).to_polygon(),
+            );
         }
         batch.draw(g);
     }
@@ -76,37 +84,20 @@ impl ShowBusRoute {
         let map = &app.primary.map;
         let route = app.primary.map.get_br(id);
 
+        let bunched = app.primary.sim.get_analytics().bunched_buses(
+            app.primary.sim.time(),
+            id,
+            BUNCHING_THRESHOLD,
+        );
         let mut bus_locations = Vec::new();
-        for (_, pt) in app.primary.sim.location_of_buses(id, map) {
-            bus_locations.push(pt);
+        for (car, pt) in app.primary.sim.location_of_buses(id, map) {
+            bus_locations.push((pt, bunched.contains(&car)));
         }
+        let num_bunched = bus_locations.iter().filter(|(_, b)| *b).count();
 
         let mut colorer = ColorDiscrete::new(app, vec![("route", app.cs.unzoomed_bus)]);
-        for (stop1, stop2) in
-            route
-                .stops
-                .iter()
-                .zip(route.stops.iter().skip(1))
-                .chain(std::iter::once((
-                    route.stops.last().unwrap(),
-                    &route.stops[0],
-                )))
-        {
-            let bs1 = map.get_bs(*stop1);
-            let bs2 = map.get_bs(*stop2);
-            for step in map
-                .pathfind(PathRequest {
-                    start: bs1.driving_pos,
-                    end: bs2.driving_pos,
-                    constraints: PathConstraints::Bus,
-                })
-                .unwrap()
-                .get_steps()
-            {
-                if let PathStep::Lane(l) = step {
-                    colorer.add_l(*l, "route");
-                }
-            }
+        for l in route_lanes(map, route) {
+            colorer.add_l(l, "route");
         }
 
         let mut labels = Vec::new();
@@ -135,6 +126,16 @@ impl ShowBusRoute {
                             .align_right(),
                     ]),
                     format!("{} buses", bus_locations.len()).draw_text(ctx),
+                    if num_bunched > 0 {
+                        Line(format!(
+                            "{} bunched (arriving within {} of each other)",
+                            num_bunched, BUNCHING_THRESHOLD
+                        ))
+                        .fg(Color::RED)
+                        .draw(ctx)
+                    } else {
+                        Widget::nothing()
+                    },
                     legend,
                 ])
                 .padding(5)
@@ -146,3 +147,110 @@ impl ShowBusRoute {
         }
     }
 }
+
+// Every bus route at once, to see the coverage of the whole transit network
+pub struct BusRoutes {
+    composite: Composite,
+    unzoomed: Drawable,
+    zoomed: Drawable,
+}
+
+impl Layer for BusRoutes {
+    fn name(&self) -> Option<&'static str> {
+        Some("bus routes")
+    }
+    fn event(
+        &mut self,
+        ctx: &mut EventCtx,
+        _: &mut App,
+        minimap: &Composite,
+    ) -> Option<LayerOutcome> {
+        Layer::simple_event(ctx, minimap, &mut self.composite)
+    }
+    fn draw(&self, g: &mut GfxCtx, app: &App) {
+        Layer::draw_panel(g, app, &self.composite);
+        if g.canvas.cam_zoom < app.opts.min_zoom_for_detail {
+            g.redraw(&self.unzoomed);
+        } else {
+            g.redraw(&self.zoomed);
+        }
+    }
+    fn draw_minimap(&self, g: &mut GfxCtx) {
+        g.redraw(&self.unzoomed);
+    }
+}
+
+impl BusRoutes {
+    pub fn new(ctx: &mut EventCtx, app: &App) -> BusRoutes {
+        let map = &app.primary.map;
+        let mut colorer = ColorNetwork::new(app);
+        let mut legend = Vec::new();
+        for (idx, route) in map.get_all_bus_routes().iter().enumerate() {
+            let color = app.cs.rotating_color_agents(idx);
+            let roads: BTreeSet<_> = route_lanes(map, route)
+                .into_iter()
+                .map(|l| map.get_l(l).parent)
+                .collect();
+            for r in roads {
+                colorer.add_r(r, color);
+            }
+            legend.push(ColorLegend::row(ctx, color, &route.name));
+        }
+        if legend.is_empty() {
+            legend.push("No bus routes in this map".draw_text(ctx));
+        }
+
+        let (unzoomed, zoomed) = colorer.build(ctx);
+        BusRoutes {
+            composite: Composite::new(
+                Widget::col(vec![
+                    Widget::row(vec![
+                        Widget::draw_svg(ctx, "../data/system/assets/tools/layers.svg")
+                            .margin_right(10),
+                        "Bus routes".draw_text(ctx),
+                        Btn::plaintext("X")
+                            .build(ctx, "close", hotkey(Key::Escape))
+                            .align_right(),
+                    ]),
+                    Widget::col(legend),
+                ])
+                .padding(5)
+                .bg(app.cs.panel_bg),
+            )
+            .max_size_percent(30, 50)
+            .aligned(HorizontalAlignment::Right, VerticalAlignment::Center)
+            .build(ctx),
+            unzoomed,
+            zoomed,
+        }
+    }
+}
+
+// The lanes a bus follows between each stop, looping back to the first
+fn route_lanes(map: &Map, route: &BusRoute) -> Vec<LaneID> {
+    let mut lanes = Vec::new();
+    for (stop1, stop2) in route
+        .stops
+        .iter()
+        .zip(route.stops.iter().skip(1))
+        .chain(std::iter::once((
+            route.stops.last().unwrap(),
+            &route.stops[0],
+        )))
+    {
+        let bs1 = map.get_bs(*stop1);
+        let bs2 = map.get_bs(*stop2);
+        if let Some(path) = map.pathfind(PathRequest {
+            start: bs1.driving_pos,
+            end: bs2.driving_pos,
+            constraints: PathConstraints::Bus,
+        }) {
+            for step in path.get_steps() {
+                if let PathStep::Lane(l) = step {
+                    lanes.push(*l);
+                }
+            }
+        }
+    }
+    lanes
+}
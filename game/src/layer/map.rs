@@ -35,7 +35,7 @@ impl Layer for BikeNetwork {
         Layer::simple_event(ctx, minimap, &mut self.composite)
     }
     fn draw(&self, g: &mut GfxCtx, app: &App) {
-        self.composite.draw(g);
+        Layer::draw_panel(g, app, &self.composite);
         if g.canvas.cam_zoom < app.opts.min_zoom_for_detail {
             g.redraw(&self.unzoomed);
         } else {
@@ -57,7 +57,7 @@ impl BikeNetwork {
         let mut intersections_off = Counter::new();
         // Make sure all bikes lanes show up no matter what
         for l in app.primary.map.all_lanes() {
-            if l.is_biking() {
+            if l.is_biking() && !app.primary.map.is_lane_removed(l.id) {
                 on_bike_lanes.add(l.parent, 0);
                 intersections_on.add(l.src_i, 0);
                 intersections_on.add(l.src_i, 0);
@@ -170,7 +170,7 @@ impl Layer for Static {
         Layer::simple_event(ctx, minimap, &mut self.composite)
     }
     fn draw(&self, g: &mut GfxCtx, app: &App) {
-        self.composite.draw(g);
+        Layer::draw_panel(g, app, &self.composite);
         if g.canvas.cam_zoom < app.opts.min_zoom_for_detail {
             g.redraw(&self.unzoomed);
         } else {
@@ -229,7 +229,7 @@ impl Static {
             ],
         );
         for l in app.primary.map.all_lanes() {
-            if l.is_bus() {
+            if l.is_bus() && !app.primary.map.is_lane_removed(l.id) {
                 colorer.add_l(l.id, "bus lanes");
             }
         }
@@ -254,9 +254,20 @@ impl Static {
         );
 
         let edits = app.primary.map.get_edits();
-        for l in edits.original_lts.keys().chain(&edits.reversed_lanes) {
+        for l in edits
+            .original_lts
+            .keys()
+            .chain(&edits.reversed_lanes)
+            .chain(&edits.added_lanes)
+        {
             colorer.add_l(*l, "modified lane/intersection");
         }
+        for l in &edits.removed_lanes {
+            colorer.add_r(
+                app.primary.map.get_l(*l).parent,
+                "modified lane/intersection",
+            );
+        }
         for i in edits.original_intersections.keys() {
             colorer.add_i(*i, "modified lane/intersection");
         }
@@ -273,6 +284,11 @@ impl Static {
             Text::from_multiline(vec![
                 Line(format!("{} lane types changed", edits.original_lts.len())),
                 Line(format!("{} lanes reversed", edits.reversed_lanes.len())),
+                Line(format!(
+                    "{} lanes added, {} removed",
+                    edits.added_lanes.len(),
+                    edits.removed_lanes.len()
+                )),
                 Line(format!(
                     "{} speed limits changed",
                     edits.changed_speed_limits.len()
@@ -44,6 +44,12 @@ impl dyn Layer {
             None => None,
         }
     }
+
+    fn draw_panel(g: &mut GfxCtx, app: &App, composite: &Composite) {
+        if !app.hide_layer_panel {
+            composite.draw(g);
+        }
+    }
 }
 
 // TODO Just return a bool for closed? Less readable...
@@ -62,11 +68,21 @@ impl PickLayer {
             return None;
         }
 
+        if ctx.input.new_was_pressed(&hotkey(Key::H).unwrap()) {
+            app.hide_layer_panel = !app.hide_layer_panel;
+        }
+        // A hidden panel still handles its hotkeys, like Escape to close, but it isn't drawn, so
+        // don't let clicks land on it.
+        if app.hide_layer_panel && ctx.input.left_mouse_button_released() {
+            return None;
+        }
+
         // TODO Since the Layer is embedded in UI, we have to do this slight trick
         let mut layer = app.layer.take().unwrap();
         match layer.event(ctx, app, minimap) {
             Some(LayerOutcome::Close) => {
                 app.layer = None;
+                app.hide_layer_panel = false;
                 return None;
             }
             None => {}
@@ -113,7 +129,9 @@ impl PickLayer {
                     btn("parking occupancy", Key::P),
                     btn("bike network", Key::B),
                     btn("bus network", Key::U),
+                    btn("bus routes", Key::O),
                     btn("population map", Key::X),
+                    btn("trip demand", Key::G),
                 ]),
             ])
             .evenly_spaced(),
@@ -123,6 +141,8 @@ impl PickLayer {
             "Experimental".draw_text(ctx).margin_below(10),
             btn("amenities", Key::A),
             btn("backpressure", Key::Z),
+            btn("queue lengths", Key::Q),
+            btn("longest waiting", Key::W),
             btn("elevation", Key::S),
         ]);
         if app.primary.sim.get_pandemic_model().is_some() {
@@ -141,7 +161,9 @@ impl State for PickLayer {
     fn event(&mut self, ctx: &mut EventCtx, app: &mut App) -> Transition {
         match self.composite.event(ctx) {
             Some(Outcome::Clicked(x)) => match x.as_ref() {
-                "close" => {}
+                "close" => {
+                    return Transition::Pop;
+                }
                 "None" => {
                     app.layer = None;
                 }
@@ -162,12 +184,21 @@ impl State for PickLayer {
                 "backpressure" => {
                     app.layer = Some(Box::new(traffic::Backpressure::new(ctx, app)));
                 }
+                "queue lengths" => {
+                    app.layer = Some(Box::new(traffic::QueueLengths::new(ctx, app)));
+                }
+                "longest waiting" => {
+                    app.layer = Some(Box::new(traffic::LongestWaiting::new(ctx, app)));
+                }
                 "bike network" => {
                     app.layer = Some(Box::new(map::BikeNetwork::new(ctx, app)));
                 }
                 "bus network" => {
                     app.layer = Some(Box::new(map::Static::bus_network(ctx, app)));
                 }
+                "bus routes" => {
+                    app.layer = Some(Box::new(bus::BusRoutes::new(ctx, app)));
+                }
                 "elevation" => {
                     app.layer = Some(Box::new(elevation::Elevation::new(ctx, app)));
                 }
@@ -186,6 +217,9 @@ impl State for PickLayer {
                         },
                     )));
                 }
+                "trip demand" => {
+                    app.layer = Some(Box::new(population::TripDemand::new(ctx, app)));
+                }
                 "pandemic model" => {
                     app.layer = Some(Box::new(pandemic::Pandemic::new(
                         ctx,
@@ -205,6 +239,8 @@ impl State for PickLayer {
                 return Transition::Keep;
             }
         }
+        // Show the panel of whatever layer was just picked
+        app.hide_layer_panel = false;
         Transition::Pop
     }
 
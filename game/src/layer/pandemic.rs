@@ -54,7 +54,7 @@ impl Layer for Pandemic {
         None
     }
     fn draw(&self, g: &mut GfxCtx, app: &App) {
-        self.composite.draw(g);
+        Layer::draw_panel(g, app, &self.composite);
         if g.canvas.cam_zoom < app.opts.min_zoom_for_detail {
             g.redraw(&self.draw);
         }
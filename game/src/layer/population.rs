@@ -1,13 +1,13 @@
 use crate::app::App;
-use crate::common::{make_heatmap, HeatmapOptions};
+use crate::common::{make_heatmap, ColorLegend, ColorNetwork, HeatmapOptions};
 use crate::layer::{Layer, LayerOutcome};
-use abstutil::prettyprint_usize;
+use abstutil::{prettyprint_usize, Counter};
 use ezgui::{
     hotkey, Btn, Checkbox, Color, Composite, Drawable, EventCtx, GeomBatch, GfxCtx,
-    HorizontalAlignment, Key, Line, Outcome, VerticalAlignment, Widget,
+    HorizontalAlignment, Key, Line, Outcome, Text, TextExt, VerticalAlignment, Widget,
 };
 use geom::{Circle, Distance, Pt2D, Time};
-use sim::{GetDrawAgents, PersonState};
+use sim::{GetDrawAgents, PersonState, TripEndpoint};
 use std::collections::HashSet;
 
 // TODO Disable drawing unzoomed agents... or alternatively, implement this by asking Sim to
@@ -54,7 +54,7 @@ impl Layer for PopulationMap {
         None
     }
     fn draw(&self, g: &mut GfxCtx, app: &App) {
-        self.composite.draw(g);
+        Layer::draw_panel(g, app, &self.composite);
         if g.canvas.cam_zoom < app.opts.min_zoom_for_detail {
             g.redraw(&self.draw);
         }
@@ -177,3 +177,94 @@ fn make_controls(
         .aligned(HorizontalAlignment::Right, VerticalAlignment::Center)
         .build(ctx)
 }
+
+// Where do trips in the scenario start? Shows the demand sources.
+pub struct TripDemand {
+    // Only recalculate when somebody new is spawned
+    num_people: usize,
+    unzoomed: Drawable,
+    zoomed: Drawable,
+    composite: Composite,
+}
+
+impl Layer for TripDemand {
+    fn name(&self) -> Option<&'static str> {
+        Some("trip demand")
+    }
+    fn event(
+        &mut self,
+        ctx: &mut EventCtx,
+        app: &mut App,
+        minimap: &Composite,
+    ) -> Option<LayerOutcome> {
+        if app.primary.sim.get_all_people().len() != self.num_people {
+            *self = TripDemand::new(ctx, app);
+        }
+
+        Layer::simple_event(ctx, minimap, &mut self.composite)
+    }
+    fn draw(&self, g: &mut GfxCtx, app: &App) {
+        Layer::draw_panel(g, app, &self.composite);
+        if g.canvas.cam_zoom < app.opts.min_zoom_for_detail {
+            g.redraw(&self.unzoomed);
+        } else {
+            g.redraw(&self.zoomed);
+        }
+    }
+    fn draw_minimap(&self, g: &mut GfxCtx) {
+        g.redraw(&self.unzoomed);
+    }
+}
+
+impl TripDemand {
+    pub fn new(ctx: &mut EventCtx, app: &App) -> TripDemand {
+        let mut cnt_per_b = Counter::new();
+        let mut total = 0;
+        for person in app.primary.sim.get_all_people() {
+            for t in &person.trips {
+                if let (_, TripEndpoint::Bldg(b), _, _) = app.primary.sim.trip_info(*t) {
+                    cnt_per_b.inc(b);
+                    total += 1;
+                }
+            }
+        }
+
+        let composite = Composite::new(
+            Widget::col(vec![
+                Widget::row(vec![
+                    Widget::draw_svg(ctx, "../data/system/assets/tools/layers.svg")
+                        .margin_right(10),
+                    "Trip demand".draw_text(ctx),
+                    Btn::plaintext("X")
+                        .build(ctx, "close", hotkey(Key::Escape))
+                        .align_right(),
+                ]),
+                Text::from(
+                    Line(format!(
+                        "{} trips start from buildings",
+                        prettyprint_usize(total)
+                    ))
+                    .secondary(),
+                )
+                .wrap_to_pct(ctx, 15)
+                .draw(ctx),
+                ColorLegend::gradient(ctx, &app.cs.good_to_bad_red, vec!["fewest trips", "most"]),
+            ])
+            .padding(5)
+            .bg(app.cs.panel_bg),
+        )
+        .aligned(HorizontalAlignment::Right, VerticalAlignment::Center)
+        .build(ctx);
+
+        let mut colorer = ColorNetwork::new(app);
+        colorer.ranked_buildings(cnt_per_b, &app.cs.good_to_bad_red);
+        let (unzoomed, zoomed) = colorer.build(ctx);
+
+        TripDemand {
+            num_people: app.primary.sim.get_all_people().len(),
+            unzoomed,
+            zoomed,
+            composite,
+        }
+    }
+}
@@ -1,15 +1,15 @@
 use crate::app::App;
 use crate::common::{ColorLegend, ColorNetwork, ColorScale, DivergingScale};
 use crate::layer::{Layer, LayerOutcome};
-use abstutil::Counter;
+use abstutil::{prettyprint_usize, Counter};
 use ezgui::{
     hotkey, Btn, Checkbox, Color, Composite, Drawable, EventCtx, GeomBatch, GfxCtx,
     HorizontalAlignment, Key, Line, Outcome, Text, TextExt, VerticalAlignment, Widget,
 };
-use geom::{Distance, Duration, Polygon, Time};
-use map_model::{IntersectionID, Map, Traversable};
+use geom::{Circle, Distance, Duration, Polygon, Time};
+use map_model::{IntersectionID, LaneID, Map, Traversable};
 use maplit::btreeset;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 pub struct Backpressure {
     time: Time,
@@ -35,7 +35,7 @@ impl Layer for Backpressure {
         Layer::simple_event(ctx, minimap, &mut self.composite)
     }
     fn draw(&self, g: &mut GfxCtx, app: &App) {
-        self.composite.draw(g);
+        Layer::draw_panel(g, app, &self.composite);
         if g.canvas.cam_zoom < app.opts.min_zoom_for_detail {
             g.redraw(&self.unzoomed);
         } else {
@@ -149,7 +149,7 @@ impl Layer for Throughput {
         None
     }
     fn draw(&self, g: &mut GfxCtx, app: &App) {
-        self.composite.draw(g);
+        Layer::draw_panel(g, app, &self.composite);
         if g.canvas.cam_zoom < app.opts.min_zoom_for_detail {
             g.redraw(&self.unzoomed);
         } else {
@@ -338,7 +338,7 @@ impl Layer for Delay {
         None
     }
     fn draw(&self, g: &mut GfxCtx, app: &App) {
-        self.composite.draw(g);
+        Layer::draw_panel(g, app, &self.composite);
         if g.canvas.cam_zoom < app.opts.min_zoom_for_detail {
             g.redraw(&self.unzoomed);
         } else {
@@ -496,7 +496,7 @@ impl Layer for TrafficJams {
         Layer::simple_event(ctx, minimap, &mut self.composite)
     }
     fn draw(&self, g: &mut GfxCtx, app: &App) {
-        self.composite.draw(g);
+        Layer::draw_panel(g, app, &self.composite);
         if g.canvas.cam_zoom < app.opts.min_zoom_for_detail {
             g.redraw(&self.unzoomed);
         } else {
@@ -617,3 +617,191 @@ impl Jam {
         polygons
     }
 }
+
+// Labels the number of cars stopped on each lane. Only redrawn when some queue actually changes
+// length.
+pub struct QueueLengths {
+    time: Time,
+    queued: BTreeMap<LaneID, usize>,
+    // Each distinct length only has to be rendered once
+    rendered: HashMap<usize, GeomBatch>,
+    labels: Drawable,
+    composite: Composite,
+}
+
+impl Layer for QueueLengths {
+    fn name(&self) -> Option<&'static str> {
+        Some("queue lengths")
+    }
+    fn event(
+        &mut self,
+        ctx: &mut EventCtx,
+        app: &mut App,
+        minimap: &Composite,
+    ) -> Option<LayerOutcome> {
+        if app.primary.sim.time() != self.time {
+            self.time = app.primary.sim.time();
+            let queued = app.primary.sim.queued_cars_per_lane();
+            if queued != self.queued {
+                let rendered = std::mem::take(&mut self.rendered);
+                let mut new = QueueLengths::build(ctx, app, queued, rendered);
+                new.composite.restore(ctx, &self.composite);
+                *self = new;
+            }
+        }
+
+        Layer::simple_event(ctx, minimap, &mut self.composite)
+    }
+    fn draw(&self, g: &mut GfxCtx, app: &App) {
+        Layer::draw_panel(g, app, &self.composite);
+        g.redraw(&self.labels);
+    }
+    fn draw_minimap(&self, _: &mut GfxCtx) {}
+}
+
+impl QueueLengths {
+    pub fn new(ctx: &mut EventCtx, app: &App) -> QueueLengths {
+        QueueLengths::build(
+            ctx,
+            app,
+            app.primary.sim.queued_cars_per_lane(),
+            HashMap::new(),
+        )
+    }
+
+    fn build(
+        ctx: &mut EventCtx,
+        app: &App,
+        queued: BTreeMap<LaneID, usize>,
+        mut rendered: HashMap<usize, GeomBatch>,
+    ) -> QueueLengths {
+        let map = &app.primary.map;
+
+        let mut batch = GeomBatch::new();
+        for (l, num) in &queued {
+            let label = rendered.entry(*num).or_insert_with(|| {
+                Text::from(Line(format!("{} cars queued", num)).fg(Color::BLACK))
+                    .bg(Color::YELLOW)
+                    .render_to_batch(ctx.prerender)
+                    .scale(0.3)
+            });
+            batch.append(label.clone().centered_on(map.get_l(*l).last_pt()));
+        }
+
+        let composite = Composite::new(
+            Widget::col(vec![
+                Widget::row(vec![
+                    Widget::draw_svg(ctx, "../data/system/assets/tools/layers.svg")
+                        .margin_right(10),
+                    "Queue lengths".draw_text(ctx),
+                    Btn::plaintext("X")
+                        .build(ctx, "close", hotkey(Key::Escape))
+                        .align_right(),
+                ]),
+                format!(
+                    "{} cars queued on {} lanes",
+                    prettyprint_usize(queued.values().sum()),
+                    prettyprint_usize(queued.len())
+                )
+                .draw_text(ctx),
+                Text::from(Line("Zoom in to read the labels").secondary()).draw(ctx),
+            ])
+            .padding(5)
+            .bg(app.cs.panel_bg),
+        )
+        .aligned(HorizontalAlignment::Right, VerticalAlignment::Center)
+        .build(ctx);
+
+        QueueLengths {
+            time: app.primary.sim.time(),
+            queued,
+            rendered,
+            labels: ctx.upload(batch),
+            composite,
+        }
+    }
+}
+
+// Which trips are suffering the most right now? Only recalculated once a minute, since this is
+// expensive.
+pub struct LongestWaiting {
+    time: Time,
+    markers: Drawable,
+    composite: Composite,
+}
+
+impl Layer for LongestWaiting {
+    fn name(&self) -> Option<&'static str> {
+        Some("longest waiting")
+    }
+    fn event(
+        &mut self,
+        ctx: &mut EventCtx,
+        app: &mut App,
+        minimap: &Composite,
+    ) -> Option<LayerOutcome> {
+        let now = app.primary.sim.time();
+        if now < self.time || now - self.time >= Duration::minutes(1) {
+            let mut new = LongestWaiting::new(ctx, app);
+            new.composite.restore(ctx, &self.composite);
+            *self = new;
+        }
+
+        Layer::simple_event(ctx, minimap, &mut self.composite)
+    }
+    fn draw(&self, g: &mut GfxCtx, app: &App) {
+        Layer::draw_panel(g, app, &self.composite);
+        g.redraw(&self.markers);
+    }
+    fn draw_minimap(&self, g: &mut GfxCtx) {
+        g.redraw(&self.markers);
+    }
+}
+
+impl LongestWaiting {
+    pub fn new(ctx: &mut EventCtx, app: &App) -> LongestWaiting {
+        let mut batch = GeomBatch::new();
+        let mut col = vec![Widget::row(vec![
+            Widget::draw_svg(ctx, "../data/system/assets/tools/layers.svg").margin_right(10),
+            "Longest waiting".draw_text(ctx),
+            Btn::plaintext("X")
+                .build(ctx, "close", hotkey(Key::Escape))
+                .align_right(),
+        ])];
+
+        let worst = app.primary.sim.longest_waiting_trips(10);
+        if worst.is_empty() {
+            col.push("Nobody is stuck right now".draw_text(ctx));
+        }
+        for (trip, agent, waiting) in worst {
+            if let Some(pt) = app
+                .primary
+                .sim
+                .canonical_pt_for_agent(agent, &app.primary.map)
+            {
+                batch.push(
+                    Color::RED.alpha(0.5),
+                    Circle::new(pt, Distance::meters(15.0)).to_polygon(),
+                );
+            }
+            col.push(
+                Text::from_all(vec![
+                    Line(format!("{}: ", trip)),
+                    Line(format!("waiting {}", waiting)).secondary(),
+                ])
+                .draw(ctx),
+            );
+        }
+        col.push(
+            Text::from(Line(format!("As of {}", app.primary.sim.time())).secondary()).draw(ctx),
+        );
+
+        LongestWaiting {
+            time: app.primary.sim.time(),
+            markers: ctx.upload(batch),
+            composite: Composite::new(Widget::col(col).padding(5).bg(app.cs.panel_bg))
+                .aligned(HorizontalAlignment::Right, VerticalAlignment::Center)
+                .build(ctx),
+        }
+    }
+}
@@ -35,6 +35,7 @@ fn main() {
     };
     let mut opts = options::Options::default();
     opts.dev = args.enabled("--dev");
+    opts.screensaver = !args.enabled("--no_screensaver");
     if args.enabled("--lowzoom") {
         opts.min_zoom_for_detail = 1.0;
     }
@@ -88,7 +89,12 @@ fn main() {
             }
         }
         if mode.is_none() {
-            panic!("Invalid --challenge={}. Choices: {}", x, aliases.join(", "));
+            // Don't crash over a typo; just start normally.
+            println!(
+                "Invalid --challenge={}, ignoring it. Choices: {}",
+                x,
+                aliases.join(", ")
+            );
         }
     }
     if let Some(n) = args.optional_parse("--tutorial", |s| s.parse::<usize>()) {
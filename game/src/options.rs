@@ -1,10 +1,11 @@
 use crate::app::App;
 use crate::colors::ColorSchemeChoice;
 use crate::game::{State, Transition};
+use crate::sandbox::SandboxMode;
 use ezgui::{
     hotkey, Btn, Checkbox, Choice, Composite, EventCtx, GfxCtx, Key, Line, Outcome, TextExt, Widget,
 };
-use geom::Duration;
+use geom::{Distance, Duration};
 
 // TODO SimOptions stuff too
 #[derive(Clone)]
@@ -16,9 +17,18 @@ pub struct Options {
     pub color_scheme: ColorSchemeChoice,
     pub min_zoom_for_detail: f64,
     pub large_unzoomed_agents: bool,
+    // Size of the turn icons in the traffic signal editor. Bump this up for high-DPI displays.
+    pub turn_icon_arrow_length: Distance,
 
     pub time_increment: Duration,
     pub resume_after_edit: bool,
+    // Save battery when the player switches to another window
+    pub pause_on_focus_loss: bool,
+    pub show_results_when_done: bool,
+    // Slowly pan the camera around the title screen
+    pub screensaver: bool,
+    // Only in dev mode
+    pub show_perf_hud: bool,
 }
 
 impl Options {
@@ -31,9 +41,14 @@ impl Options {
             color_scheme: ColorSchemeChoice::Standard,
             min_zoom_for_detail: 4.0,
             large_unzoomed_agents: false,
+            turn_icon_arrow_length: Distance::meters(1.5),
 
             time_increment: Duration::minutes(10),
             resume_after_edit: true,
+            pause_on_focus_loss: false,
+            show_results_when_done: false,
+            screensaver: true,
+            show_perf_hud: false,
         }
     }
 }
@@ -53,6 +68,18 @@ pub struct OptionsPanel {
 
 impl OptionsPanel {
     pub fn new(ctx: &mut EventCtx, app: &App) -> OptionsPanel {
+        let mut font_sizes = vec![
+            Choice::new("small", 16),
+            Choice::new("normal", 21),
+            Choice::new("large", 26),
+            Choice::new("huge", 32),
+        ];
+        // Might've been set differently with --font_size
+        let font_size = ctx.get_default_font_size();
+        if !font_sizes.iter().any(|c| c.data == font_size) {
+            font_sizes.push(Choice::new(font_size.to_string(), font_size));
+        }
+
         OptionsPanel {
             composite: Composite::new(
                 Widget::col(vec![
@@ -65,6 +92,34 @@ impl OptionsPanel {
                     .margin_below(10),
                     Checkbox::text(ctx, "Enable developer mode", None, app.opts.dev)
                         .margin_below(10),
+                    Checkbox::text(
+                        ctx,
+                        "Show performance stats (in developer mode)",
+                        None,
+                        app.opts.show_perf_hud,
+                    )
+                    .margin_below(10),
+                    Checkbox::text(
+                        ctx,
+                        "Pause the simulation when the window loses focus",
+                        None,
+                        app.opts.pause_on_focus_loss,
+                    )
+                    .margin_below(10),
+                    Checkbox::text(
+                        ctx,
+                        "Show trip results when the simulation finishes",
+                        None,
+                        app.opts.show_results_when_done,
+                    )
+                    .margin_below(10),
+                    Checkbox::text(
+                        ctx,
+                        "Pan around the map on the title screen",
+                        None,
+                        app.opts.screensaver,
+                    )
+                    .margin_below(10),
                     "Camera controls".draw_text(ctx).margin_below(10),
                     Widget::col(vec![
                         Checkbox::text(
@@ -166,6 +221,11 @@ impl OptionsPanel {
                             ),
                         ])
                         .margin_below(10),
+                        Widget::row(vec![
+                            "Font size:".draw_text(ctx).margin_right(15),
+                            Widget::dropdown(ctx, "Font size", font_size, font_sizes),
+                        ])
+                        .margin_below(10),
                         Widget::row(vec![
                             "Camera zoom to switch to unzoomed view"
                                 .draw_text(ctx)
@@ -185,6 +245,23 @@ impl OptionsPanel {
                             ),
                         ])
                         .margin_below(10),
+                        Widget::row(vec![
+                            "Traffic signal editor icon size"
+                                .draw_text(ctx)
+                                .margin_right(15),
+                            Widget::dropdown(
+                                ctx,
+                                "turn icon size",
+                                app.opts.turn_icon_arrow_length,
+                                vec![
+                                    Choice::new("small", Distance::meters(1.0)),
+                                    Choice::new("normal", Distance::meters(1.5)),
+                                    Choice::new("large", Distance::meters(2.25)),
+                                    Choice::new("huge", Distance::meters(3.0)),
+                                ],
+                            ),
+                        ])
+                        .margin_below(10),
                         Checkbox::text(
                             ctx,
                             "Draw enlarged unzoomed agents",
@@ -216,6 +293,18 @@ impl State for OptionsPanel {
                 }
                 "Apply" => {
                     app.opts.dev = self.composite.is_checked("Enable developer mode");
+                    app.opts.show_perf_hud = self
+                        .composite
+                        .is_checked("Show performance stats (in developer mode)");
+                    app.opts.pause_on_focus_loss = self
+                        .composite
+                        .is_checked("Pause the simulation when the window loses focus");
+                    app.opts.show_results_when_done = self
+                        .composite
+                        .is_checked("Show trip results when the simulation finishes");
+                    app.opts.screensaver = self
+                        .composite
+                        .is_checked("Pan around the map on the title screen");
 
                     ctx.canvas.invert_scroll = self
                         .composite
@@ -249,10 +338,34 @@ impl State for OptionsPanel {
                         ctx.set_scale_factor(factor);
                     }
 
+                    let font_size = self.composite.dropdown_value("Font size");
+                    let font_size_changed = ctx.get_default_font_size() != font_size;
+                    if font_size_changed {
+                        ctx.set_default_font_size(font_size);
+                    }
+
+                    let icon_length = self.composite.dropdown_value("turn icon size");
+                    if app.opts.turn_icon_arrow_length != icon_length {
+                        app.opts.turn_icon_arrow_length = icon_length;
+                        if app.opts.traffic_signal_style == TrafficSignalStyle::Icons {
+                            for i in app.primary.draw_map.intersections.iter_mut() {
+                                *i.draw_traffic_signal.borrow_mut() = None;
+                            }
+                        }
+                    }
+
                     app.opts.min_zoom_for_detail = self.composite.dropdown_value("min zoom");
                     app.opts.large_unzoomed_agents =
                         self.composite.is_checked("Draw enlarged unzoomed agents");
 
+                    if font_size_changed {
+                        // Panels that already exist still have the old text, so rebuild them.
+                        return Transition::PopWithData(Box::new(|state, ctx, app| {
+                            if let Some(ref mut s) = state.downcast_mut::<SandboxMode>() {
+                                s.controls.recreate_panels(ctx, app);
+                            }
+                        }));
+                    }
                     return Transition::Pop;
                 }
                 _ => unreachable!(),
@@ -3,11 +3,13 @@ use crate::challenges::challenges_picker;
 use crate::devtools::DevToolsMode;
 use crate::edit::apply_map_edits;
 use crate::game::{msg, DrawBaselayer, State, Transition};
+use crate::helpers::nice_map_name;
 use crate::sandbox::gameplay::Tutorial;
 use crate::sandbox::{GameplayMode, SandboxMode};
+use abstutil::Timer;
 use ezgui::{
     hotkey, hotkeys, Btn, Color, Composite, EventCtx, EventLoopMode, GfxCtx, Key, Line, Outcome,
-    RewriteColor, Text, Widget,
+    RewriteColor, Text, TextExt, Widget,
 };
 use geom::{Duration, Line, Pt2D, Speed};
 use instant::Instant;
@@ -58,6 +60,9 @@ impl State for TitleScreen {
             None => {}
         }
 
+        if !app.opts.screensaver {
+            return Transition::Keep;
+        }
         self.screensaver
             .update(&mut self.rng, ctx, &app.primary.map);
         Transition::KeepWithMode(EventLoopMode::Animation)
@@ -70,11 +75,13 @@ impl State for TitleScreen {
 
 pub struct MainMenu {
     composite: Composite,
+    // The recent maps shown, to notice when playing some other map changes them
+    recent: Vec<String>,
 }
 
 impl MainMenu {
     pub fn new(ctx: &mut EventCtx, app: &App) -> Box<dyn State> {
-        let col = vec![
+        let mut col = vec![
             Btn::svg_def("../data/system/assets/pregame/quit.svg")
                 .build(ctx, "quit", hotkey(Key::Escape))
                 .align_left(),
@@ -138,6 +145,23 @@ impl MainMenu {
                 Btn::text_bg2("Internal Dev Tools").build_def(ctx, hotkey(Key::D)),
             ])
             .centered(),
+        ];
+        let recent = app.session.recent_maps.clone();
+        if !recent.is_empty() {
+            let mut row = vec!["Recent maps:"
+                .draw_text(ctx)
+                .centered_vert()
+                .margin_right(10)];
+            for name in &recent {
+                row.push(
+                    Btn::text_bg2(nice_map_name(name))
+                        .build(ctx, format!("play {}", name), None)
+                        .margin_right(10),
+                );
+            }
+            col.push(Widget::row(row).centered());
+        }
+        col.push(
             Widget::col(vec![
                 Widget::row(vec![
                     Btn::text_bg2("About").build_def(ctx, None).margin_right(20),
@@ -146,18 +170,24 @@ impl MainMenu {
                 built_info::time().draw(ctx),
             ])
             .centered(),
-        ];
+        );
 
         Box::new(MainMenu {
             composite: Composite::new(Widget::col(col).evenly_spaced())
                 .exact_size_percent(90, 85)
                 .build(ctx),
+            recent,
         })
     }
 }
 
 impl State for MainMenu {
     fn event(&mut self, ctx: &mut EventCtx, app: &mut App) -> Transition {
+        // Some other state on top of this one may have loaded a map
+        if self.recent != app.session.recent_maps {
+            return Transition::Replace(MainMenu::new(ctx, app));
+        }
+
         match self.composite.event(ctx) {
             Some(Outcome::Clicked(x)) => match x.as_ref() {
                 "quit" => {
@@ -169,26 +199,14 @@ impl State for MainMenu {
                 }
                 "Sandbox mode" => {
                     // We might've left with a synthetic map loaded.
-                    let map_path = if abstutil::list_all_objects(abstutil::path_all_maps())
+                    let map_name = if abstutil::list_all_objects(abstutil::path_all_maps())
                         .contains(app.primary.map.get_name())
                     {
-                        abstutil::path_map(app.primary.map.get_name())
+                        app.primary.map.get_name().to_string()
                     } else {
-                        abstutil::path_map("montlake")
+                        "montlake".to_string()
                     };
-                    let scenario = if abstutil::file_exists(abstutil::path_scenario(
-                        app.primary.map.get_name(),
-                        "weekday",
-                    )) {
-                        "weekday"
-                    } else {
-                        "random"
-                    };
-                    return Transition::Push(Box::new(SandboxMode::new(
-                        ctx,
-                        app,
-                        GameplayMode::PlayScenario(map_path, scenario.to_string()),
-                    )));
+                    return start_sandbox(ctx, app, map_name);
                 }
                 "Challenges" => {
                     return Transition::Push(challenges_picker(ctx, app));
@@ -214,6 +232,9 @@ impl State for MainMenu {
                 "Internal Dev Tools" => {
                     return Transition::Push(DevToolsMode::new(ctx, app));
                 }
+                x if x.starts_with("play ") => {
+                    return start_sandbox(ctx, app, x["play ".len()..].to_string());
+                }
                 _ => unreachable!(),
             },
             None => {}
@@ -409,6 +430,7 @@ impl State for Proposals {
                         }
                         match PermanentMapEdits::from_permanent(edits, &app.primary.map) {
                             Ok(edits) => {
+                                app.primary.proposal = Some(edits.clone());
                                 apply_map_edits(ctx, app, edits);
                                 app.primary
                                     .map
@@ -462,6 +484,45 @@ impl State for Proposals {
     }
 }
 
+fn start_sandbox(ctx: &mut EventCtx, app: &mut App, map_name: String) -> Transition {
+    let scenario = if abstutil::file_exists(abstutil::path_scenario(&map_name, "weekday")) {
+        "weekday"
+    } else {
+        "random"
+    };
+    let mode = GameplayMode::PlayScenario(abstutil::path_map(&map_name), scenario.to_string());
+    let sandbox = SandboxMode::new(ctx, app, mode);
+    // Loading a different map already remembered it, but the current one might be replayed
+    remember_recent_map(app);
+    Transition::Push(Box::new(sandbox))
+}
+
+const NUM_RECENT_MAPS: usize = 3;
+
+// Most recent first. Skips anything that's since been deleted.
+pub fn recent_maps() -> Vec<String> {
+    let all = abstutil::list_all_objects(abstutil::path_all_maps());
+    abstutil::maybe_read_json::<Vec<String>>(abstutil::path_recent_maps(), &mut Timer::throwaway())
+        .unwrap_or_else(|_| Vec::new())
+        .into_iter()
+        .filter(|name| all.contains(name))
+        .collect()
+}
+
+// Called whenever a map is loaded.
+pub fn remember_recent_map(app: &mut App) {
+    let map_name = app.primary.map.get_name().to_string();
+    // Synthetic maps can't be played again by name
+    if !abstutil::list_all_objects(abstutil::path_all_maps()).contains(&map_name) {
+        return;
+    }
+    let recent = &mut app.session.recent_maps;
+    recent.retain(|name| name != &map_name);
+    recent.insert(0, map_name);
+    recent.truncate(NUM_RECENT_MAPS);
+    abstutil::write_json(abstutil::path_recent_maps(), &app.session.recent_maps);
+}
+
 const SPEED: Speed = Speed::const_meters_per_second(20.0);
 
 struct Screensaver {
@@ -84,7 +84,8 @@ impl DrawMap {
                 DrawLane::new(
                     l,
                     map,
-                    flags.draw_lane_markings,
+                    // Markings depend on the rest of the road, which removed lanes aren't part of
+                    flags.draw_lane_markings && !map.is_lane_removed(l.id),
                     cs,
                     // TODO Really parallelize should give us something thread-safe that can at
                     // least take notes.
@@ -194,7 +195,9 @@ impl DrawMap {
             quadtree.insert_with_box(obj.get_id(), obj.get_outline(map).get_bounds().as_bbox());
         }
         for obj in &lanes {
-            quadtree.insert_with_box(obj.get_id(), obj.get_outline(map).get_bounds().as_bbox());
+            if !map.is_lane_removed(obj.id) {
+                quadtree.insert_with_box(obj.get_id(), obj.get_outline(map).get_bounds().as_bbox());
+            }
         }
         for obj in &intersections {
             quadtree.insert_with_box(obj.get_id(), obj.get_outline(map).get_bounds().as_bbox());
@@ -474,15 +477,31 @@ impl AgentColorScheme {
         panic!("Can't toggle category {}", name);
     }
 
-    fn color(&self, agent: &UnzoomedAgent) -> Option<Color> {
-        let category = match agent.vehicle_type {
-            Some(VehicleType::Car) => "Car".to_string(),
-            Some(VehicleType::Bike) => "Bike".to_string(),
-            Some(VehicleType::Bus) => "Bus".to_string(),
-            None => "Pedestrian".to_string(),
+    // Disable every other category, to isolate one type of agent.
+    pub fn show_only(&mut self, name: &str) {
+        for (n, _, enabled) in &mut self.rows {
+            *enabled = n == name;
+        }
+    }
+
+    // Should a zoomed-in agent be drawn? Anything besides agents is always shown.
+    pub fn shows(&self, id: &ID) -> bool {
+        let category = match id {
+            ID::Car(c) => category(Some(c.1)),
+            ID::Pedestrian(_) | ID::PedCrowd(_) => category(None),
+            _ => {
+                return true;
+            }
         };
+        self.rows
+            .iter()
+            .any(|(name, _, enabled)| *enabled && name == category)
+    }
+
+    fn color(&self, agent: &UnzoomedAgent) -> Option<Color> {
+        let category = category(agent.vehicle_type);
         for (name, color, enabled) in &self.rows {
-            if name == &category {
+            if name == category {
                 if *enabled {
                     return Some(*color);
                 }
@@ -493,6 +512,15 @@ impl AgentColorScheme {
     }
 }
 
+fn category(vehicle_type: Option<VehicleType>) -> &'static str {
+    match vehicle_type {
+        Some(VehicleType::Car) => "Car",
+        Some(VehicleType::Bike) => "Bike",
+        Some(VehicleType::Bus) => "Bus",
+        None => "Pedestrian",
+    }
+}
+
 fn osm_rank_to_color(cs: &ColorScheme, rank: usize) -> Color {
     if rank >= 16 {
         cs.unzoomed_highway
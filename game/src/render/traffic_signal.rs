@@ -10,6 +10,9 @@ use geom::{Angle, ArrowCap, Circle, Distance, Duration, Line, PolyLine, Polygon,
 use map_model::{IntersectionID, Phase, TurnPriority, SIDEWALK_THICKNESS};
 use std::collections::BTreeSet;
 
+// Above this, the signal diagram warns about the cycle length
+const LONG_CYCLE: Duration = Duration::const_seconds(120.0);
+
 // Only draws a box when time_left is present
 pub fn draw_signal_phase(
     prerender: &Prerender,
@@ -214,7 +217,7 @@ pub fn draw_signal_phase(
             }
         }
         TrafficSignalStyle::Icons => {
-            for g in DrawTurnGroup::for_i(i, &app.primary.map) {
+            for g in DrawTurnGroup::for_i(i, &app.primary.map, app.opts.turn_icon_arrow_length) {
                 batch.push(app.cs.signal_turn_block_bg, g.block.clone());
                 let arrow_color = match phase.get_priority_of_group(g.id) {
                     TurnPriority::Protected => app.cs.signal_protected_turn,
@@ -317,6 +320,15 @@ pub fn make_signal_diagram(
         txt.add(Line(format!("{} phases", signal.phases.len())).small_heading());
         txt.add(Line(format!("Signal offset: {}", signal.offset)));
         txt.add(Line(format!("One cycle lasts {}", signal.cycle_length())));
+        if signal.cycle_length() > LONG_CYCLE {
+            txt.add(
+                Line(format!(
+                    "Cycles longer than {} increase delay for everyone",
+                    LONG_CYCLE
+                ))
+                .fg(Color::RED),
+            );
+        }
         txt.draw(ctx)
     };
     let mut col = if edit_mode {
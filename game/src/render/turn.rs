@@ -1,9 +1,9 @@
 use geom::{Angle, ArrowCap, Distance, PolyLine, Polygon};
-use map_model::{IntersectionCluster, IntersectionID, LaneID, Map, TurnGroupID, UberTurnGroup};
+use map_model::{
+    IntersectionCluster, IntersectionID, LaneID, Map, TurnGroup, TurnGroupID, UberTurnGroup,
+};
 use std::collections::{HashMap, HashSet};
 
-const TURN_ICON_ARROW_LENGTH: Distance = Distance::const_meters(1.5);
-
 pub struct DrawTurnGroup {
     pub id: TurnGroupID,
     pub block: Polygon,
@@ -11,11 +11,21 @@ pub struct DrawTurnGroup {
 }
 
 impl DrawTurnGroup {
-    pub fn for_i(i: IntersectionID, map: &Map) -> Vec<DrawTurnGroup> {
-        // TODO Sort by angle here if we want some consistency
+    // arrow_length controls the size of each icon; see Options::turn_icon_arrow_length.
+    pub fn for_i(i: IntersectionID, map: &Map, arrow_length: Distance) -> Vec<DrawTurnGroup> {
+        // Sort by angle, so the icons are always stacked in the same order. The stable sort leaves
+        // ties in TurnGroupID order.
+        let mut groups: Vec<&TurnGroup> = map.get_traffic_signal(i).turn_groups.values().collect();
+        groups.sort_by(|a, b| {
+            a.angle
+                .normalized_degrees()
+                .partial_cmp(&b.angle.normalized_degrees())
+                .unwrap()
+        });
+
         let mut offset_per_lane: HashMap<LaneID, usize> = HashMap::new();
         let mut draw = Vec::new();
-        for group in map.get_traffic_signal(i).turn_groups.values() {
+        for group in groups {
             let offset = group
                 .members
                 .iter()
@@ -23,7 +33,7 @@ impl DrawTurnGroup {
                 .max()
                 .unwrap();
             let (pl, width) = group.src_center_and_width(map);
-            let (block, arrow) = make_geom(offset as f64, pl, width, group.angle);
+            let (block, arrow) = make_geom(offset as f64, pl, width, group.angle, arrow_length);
             let mut seen_lanes = HashSet::new();
             for t in &group.members {
                 if !seen_lanes.contains(&t.src) {
@@ -49,7 +59,11 @@ pub struct DrawUberTurnGroup {
 }
 
 impl DrawUberTurnGroup {
-    pub fn new(ic: &IntersectionCluster, map: &Map) -> Vec<DrawUberTurnGroup> {
+    pub fn new(
+        ic: &IntersectionCluster,
+        map: &Map,
+        arrow_length: Distance,
+    ) -> Vec<DrawUberTurnGroup> {
         let mut offset_per_lane: HashMap<LaneID, usize> = HashMap::new();
         let mut draw = Vec::new();
         for group in ic.uber_turn_groups(map) {
@@ -60,7 +74,7 @@ impl DrawUberTurnGroup {
                 .max()
                 .unwrap();
             let (pl, width) = group.src_center_and_width(map);
-            let (block, arrow) = make_geom(offset as f64, pl, width, group.angle());
+            let (block, arrow) = make_geom(offset as f64, pl, width, group.angle(), arrow_length);
             let mut seen_lanes = HashSet::new();
             for ut in &group.members {
                 if !seen_lanes.contains(&ut.entry()) {
@@ -80,8 +94,14 @@ impl DrawUberTurnGroup {
 }
 
 // Produces (block, arrow)
-fn make_geom(offset: f64, pl: PolyLine, width: Distance, angle: Angle) -> (Polygon, Polygon) {
-    let height = TURN_ICON_ARROW_LENGTH;
+fn make_geom(
+    offset: f64,
+    pl: PolyLine,
+    width: Distance,
+    angle: Angle,
+    arrow_length: Distance,
+) -> (Polygon, Polygon) {
+    let height = arrow_length;
     // Always extend the pl first to handle short entry lanes
     let extension = PolyLine::new(vec![
         pl.last_pt(),
@@ -95,10 +115,10 @@ fn make_geom(offset: f64, pl: PolyLine, width: Distance, angle: Angle) -> (Polyg
     let arrow = {
         let center = slice.middle();
         PolyLine::new(vec![
-            center.project_away(TURN_ICON_ARROW_LENGTH / 2.0, angle.opposite()),
-            center.project_away(TURN_ICON_ARROW_LENGTH / 2.0, angle),
+            center.project_away(arrow_length / 2.0, angle.opposite()),
+            center.project_away(arrow_length / 2.0, angle),
         ])
-        .make_arrow(Distance::meters(0.5), ArrowCap::Triangle)
+        .make_arrow(arrow_length / 3.0, ArrowCap::Triangle)
         .unwrap()
     };
 
@@ -3,9 +3,11 @@ use crate::common::Tab;
 use crate::game::{msg, DrawBaselayer, State, Transition};
 use crate::sandbox::dashboards::DashTab;
 use crate::sandbox::SandboxMode;
+use abstutil::prettyprint_usize;
 use ezgui::{
-    Btn, Composite, EventCtx, GfxCtx, Line, LinePlot, Outcome, PlotOptions, Series, Widget,
+    Btn, Composite, EventCtx, GfxCtx, Line, LinePlot, Outcome, PlotOptions, Series, TextExt, Widget,
 };
+use map_model::BusRouteID;
 
 pub struct ActiveTraffic {
     composite: Composite,
@@ -21,6 +23,7 @@ impl ActiveTraffic {
                 .sim
                 .get_analytics()
                 .active_agents(app.primary.sim.time()),
+            dashed: false,
         }];
         if app.has_prebaked().is_some() {
             active_agents.push(Series {
@@ -29,13 +32,29 @@ impl ActiveTraffic {
                 pts: app
                     .prebaked()
                     .active_agents(app.primary.sim.get_end_of_day()),
+                dashed: false,
             });
         }
 
+        // When is the city busiest?
+        let peak = active_agents[0]
+            .pts
+            .iter()
+            .max_by_key(|(_, cnt)| *cnt)
+            .map(|(t, cnt)| {
+                format!(
+                    "Peak so far: {} active agents at {}",
+                    prettyprint_usize(*cnt),
+                    t.ampm_tostring()
+                )
+            })
+            .unwrap_or_else(|| "No active agents yet".to_string());
+
         Box::new(ActiveTraffic {
             composite: Composite::new(
                 Widget::col(vec![
                     DashTab::ActiveTraffic.picker(ctx, app),
+                    peak.draw_text(ctx),
                     LinePlot::new(ctx, active_agents, PlotOptions::fixed()),
                 ])
                 .bg(app.cs.panel_bg)
@@ -65,18 +84,90 @@ impl State for ActiveTraffic {
     }
 }
 
+pub struct ParkingOccupancy {
+    composite: Composite,
+}
+
+impl ParkingOccupancy {
+    pub fn new(ctx: &mut EventCtx, app: &App) -> Box<dyn State> {
+        let (filled, available) = app.primary.sim.get_all_parking_spots();
+        let capacity = filled.len() + available.len();
+        let filled_pts = app
+            .primary
+            .sim
+            .get_analytics()
+            .parking_spots_filled(app.primary.sim.time(), filled.len());
+        let free_pts = filled_pts
+            .iter()
+            .map(|(t, cnt)| (*t, capacity.saturating_sub(*cnt)))
+            .collect();
+
+        let series = vec![
+            Series {
+                label: "Filled spots".to_string(),
+                color: app.cs.after_changes,
+                pts: filled_pts,
+                dashed: false,
+            },
+            Series {
+                label: "Free spots".to_string(),
+                color: app.cs.before_changes,
+                pts: free_pts,
+                dashed: false,
+            },
+        ];
+
+        Box::new(ParkingOccupancy {
+            composite: Composite::new(
+                Widget::col(vec![
+                    DashTab::ParkingOccupancy.picker(ctx, app),
+                    format!(
+                        "{} parking spots total, including on-street, off-street, and lots",
+                        prettyprint_usize(capacity)
+                    )
+                    .draw_text(ctx)
+                    .margin_below(10),
+                    LinePlot::new(ctx, series, PlotOptions::fixed()),
+                ])
+                .bg(app.cs.panel_bg)
+                .padding(10),
+            )
+            .exact_size_percent(90, 90)
+            .build(ctx),
+        })
+    }
+}
+
+impl State for ParkingOccupancy {
+    fn event(&mut self, ctx: &mut EventCtx, app: &mut App) -> Transition {
+        match self.composite.event(ctx) {
+            Some(Outcome::Clicked(x)) => DashTab::ParkingOccupancy.transition(ctx, app, &x),
+            None => Transition::Keep,
+        }
+    }
+
+    fn draw_baselayer(&self) -> DrawBaselayer {
+        DrawBaselayer::Custom
+    }
+
+    fn draw(&self, g: &mut GfxCtx, app: &App) {
+        g.clear(app.cs.grass);
+        self.composite.draw(g);
+    }
+}
+
 pub struct BusRoutes {
     composite: Composite,
 }
 
 impl BusRoutes {
     pub fn new(ctx: &mut EventCtx, app: &App) -> Box<dyn State> {
-        let mut routes: Vec<String> = app
+        let mut routes: Vec<(String, BusRouteID)> = app
             .primary
             .map
             .get_all_bus_routes()
             .iter()
-            .map(|r| r.name.clone())
+            .map(|r| (r.name.clone(), r.id))
             .collect();
         // TODO Sort first by length, then lexicographically
         routes.sort();
@@ -84,9 +175,22 @@ impl BusRoutes {
         let mut col = vec![
             DashTab::BusRoutes.picker(ctx, app),
             Line("Bus routes").small_heading().draw(ctx),
+            "Reliability is the percent of buses arriving at stops evenly spaced".draw_text(ctx),
         ];
-        for r in routes {
-            col.push(Btn::text_fg(r).build_def(ctx, None).margin(5));
+        for (name, id) in routes {
+            let reliability = match app
+                .primary
+                .sim
+                .get_analytics()
+                .route_reliability(app.primary.sim.time(), id)
+            {
+                Some(pct) => format!("reliability {}%", pct.round()),
+                None => "reliability unknown yet".to_string(),
+            };
+            col.push(Widget::row(vec![
+                Btn::text_fg(name).build_def(ctx, None).margin(5),
+                reliability.draw_text(ctx).centered_vert(),
+            ]));
         }
 
         Box::new(BusRoutes {
@@ -4,10 +4,19 @@ mod summaries;
 mod trip_table;
 
 use crate::app::App;
-use crate::game::Transition;
+use crate::game::{State, Transition};
 use ezgui::{hotkey, Btn, Color, EventCtx, Key, Widget};
 pub use trip_table::TripTable;
 
+// The most relevant dashboard once the simulation finishes
+pub fn results(ctx: &mut EventCtx, app: &App) -> Box<dyn State> {
+    if app.has_prebaked().is_some() {
+        summaries::TripSummaries::new(ctx, app, summaries::Filter::new())
+    } else {
+        TripTable::new(ctx, app)
+    }
+}
+
 // Oh the dashboards melted, but we still had the radio
 #[derive(PartialEq)]
 pub enum DashTab {
@@ -15,6 +24,7 @@ pub enum DashTab {
     TripSummaries,
     ParkingOverhead,
     ActiveTraffic,
+    ParkingOccupancy,
     BusRoutes,
 }
 
@@ -26,6 +36,7 @@ impl DashTab {
             ("trip summaries", DashTab::TripSummaries),
             ("parking overhead", DashTab::ParkingOverhead),
             ("active traffic", DashTab::ActiveTraffic),
+            ("parking occupancy", DashTab::ParkingOccupancy),
             ("bus routes", DashTab::BusRoutes),
         ] {
             if tab == DashTab::TripSummaries && app.has_prebaked().is_none() {
@@ -60,6 +71,7 @@ impl DashTab {
                 Transition::Replace(parking_overhead::ParkingOverhead::new(ctx, app))
             }
             "active traffic" => Transition::Replace(misc::ActiveTraffic::new(ctx, app)),
+            "parking occupancy" => Transition::Replace(misc::ParkingOccupancy::new(ctx, app)),
             "bus routes" => Transition::Replace(misc::BusRoutes::new(ctx, app)),
             _ => unreachable!(),
         }
@@ -1,15 +1,17 @@
-use crate::app::App;
+use crate::app::{App, ShowEverything};
+use crate::common::CommonState;
 use crate::game::{DrawBaselayer, State, Transition};
-use crate::helpers::{cmp_duration_shorter, color_for_mode, color_for_trip_phase};
+use crate::helpers::{cmp_duration_shorter, color_for_mode, color_for_trip_phase, ID};
 use crate::info::{OpenTrip, Tab};
 use crate::sandbox::dashboards::DashTab;
 use crate::sandbox::SandboxMode;
 use abstutil::prettyprint_usize;
 use ezgui::{
-    Btn, Checkbox, Color, Composite, EventCtx, Filler, GeomBatch, GfxCtx, Line, Outcome,
-    RewriteColor, ScreenDims, ScreenPt, Text, TextExt, Widget,
+    hotkey, Btn, Checkbox, Color, Composite, EventCtx, Filler, GeomBatch, GfxCtx,
+    HorizontalAlignment, Key, Line, Outcome, RewriteColor, ScreenDims, ScreenPt, Text, TextExt,
+    VerticalAlignment, Widget,
 };
-use geom::{Distance, Duration, Polygon, Pt2D, Time};
+use geom::{Distance, Duration, Histogram, Polygon, Pt2D, Statistic, Time};
 use sim::{TripEndpoint, TripID, TripMode};
 use std::collections::BTreeSet;
 
@@ -26,6 +28,9 @@ struct Options {
     modes: BTreeSet<TripMode>,
     off_map_starts: bool,
     off_map_ends: bool,
+    group_by_mode: bool,
+    // Only trips starting or ending at this building or border intersection
+    endpoint: Option<ID>,
     skip: usize,
 }
 
@@ -60,6 +65,8 @@ impl TripTable {
             modes: TripMode::all().into_iter().collect(),
             off_map_starts: true,
             off_map_ends: true,
+            group_by_mode: false,
+            endpoint: None,
             skip: 0,
         };
         Box::new(TripTable {
@@ -111,6 +118,14 @@ impl State for TripTable {
                     self.opts.skip += ROWS;
                     self.recalc(ctx, app);
                 }
+                "filter by place" => {
+                    return Transition::Push(PickEndpoint::new(ctx, app));
+                }
+                "clear place filter" => {
+                    self.opts.endpoint = None;
+                    self.opts.skip = 0;
+                    self.recalc(ctx, app);
+                }
                 x => {
                     if let Ok(idx) = x.parse::<usize>() {
                         let trip = TripID(idx);
@@ -151,6 +166,12 @@ impl State for TripTable {
                     self.opts.skip = 0;
                     self.recalc(ctx, app);
                 }
+                let group_by_mode = self.composite.is_checked("group by mode");
+                if self.opts.group_by_mode != group_by_mode {
+                    self.opts.group_by_mode = group_by_mode;
+                    self.opts.skip = 0;
+                    self.recalc(ctx, app);
+                }
             }
         };
 
@@ -194,6 +215,11 @@ fn make(ctx: &mut EventCtx, app: &App, opts: &Options) -> Composite {
             continue;
         };
         let (_, start, end, _) = sim.trip_info(*id);
+        if let Some(ref place) = opts.endpoint {
+            if endpoint_id(&start) != *place && endpoint_id(&end) != *place {
+                continue;
+            }
+        }
         if !opts.off_map_starts {
             if let TripEndpoint::Border(_, _) = start {
                 continue;
@@ -245,71 +271,7 @@ fn make(ctx: &mut EventCtx, app: &App, opts: &Options) -> Composite {
         data.reverse();
     }
     let total_rows = data.len();
-
-    // Render data
-    let mut rows = Vec::new();
-    for x in data.into_iter().skip(opts.skip).take(ROWS) {
-        let mut row = vec![
-            Text::from(Line(x.trip.0.to_string())).render_ctx(ctx),
-            Text::from(Line(x.mode.ongoing_verb()).fg(color_for_mode(app, x.mode))).render_ctx(ctx),
-            Text::from(Line(x.departure.ampm_tostring())).render_ctx(ctx),
-            Text::from(Line(x.duration_after.to_string())).render_ctx(ctx),
-        ];
-        if app.has_prebaked().is_some() {
-            row.push(
-                Text::from_all(cmp_duration_shorter(x.duration_after, x.duration_before))
-                    .render_ctx(ctx),
-            );
-            if x.duration_after == x.duration_before {
-                row.push(Text::from(Line("same")).render_ctx(ctx));
-            } else if x.duration_after < x.duration_before {
-                row.push(
-                    Text::from(Line(format!(
-                        "{}% faster",
-                        (100.0 * (1.0 - (x.duration_after / x.duration_before))) as usize
-                    )))
-                    .render_ctx(ctx),
-                );
-            } else {
-                row.push(
-                    Text::from(Line(format!(
-                        "{}% slower ",
-                        (100.0 * ((x.duration_after / x.duration_before) - 1.0)) as usize
-                    )))
-                    .render_ctx(ctx),
-                );
-            }
-        }
-        row.push(Text::from(Line(x.waiting.to_string())).render_ctx(ctx));
-        row.push(Text::from(Line(format!("{}%", x.percent_waiting))).render_ctx(ctx));
-
-        rows.push((x.trip.0.to_string(), row));
-    }
-
-    let btn = |value, name| {
-        if opts.sort_by == value {
-            Btn::text_bg2(format!(
-                "{} {}",
-                name,
-                if opts.descending { "↓" } else { "↑" }
-            ))
-            .build(ctx, name, None)
-        } else {
-            Btn::text_bg2(name).build_def(ctx, None)
-        }
-    };
-    let mut headers = vec![
-        Line("Trip ID").draw(ctx),
-        Line("Type").draw(ctx),
-        btn(SortBy::Departure, "Departure"),
-        btn(SortBy::Duration, "Duration"),
-    ];
-    if app.has_prebaked().is_some() {
-        headers.push(btn(SortBy::RelativeDuration, "Comparison"));
-        headers.push(btn(SortBy::PercentChangeDuration, "Normalized"));
-    }
-    headers.push(btn(SortBy::Waiting, "Time spent waiting"));
-    headers.push(btn(SortBy::PercentWaiting, "Percent waiting"));
+    let stats = summarize(data.iter());
 
     let mut col = vec![DashTab::TripTable.picker(ctx, app)];
     let mut filters = Vec::new();
@@ -333,6 +295,28 @@ fn make(ctx: &mut EventCtx, app: &App, opts: &Options) -> Composite {
         ])
         .margin_below(5),
     );
+    col.push(
+        if let Some(ref place) = opts.endpoint {
+            let name = match place {
+                ID::Building(b) => b.to_string(),
+                ID::Intersection(i) => i.to_string(),
+                _ => unreachable!(),
+            };
+            Widget::row(vec![
+                format!("Only trips starting or ending at {}", name)
+                    .draw_text(ctx)
+                    .margin_right(10),
+                Btn::text_fg("clear").build(ctx, "clear place filter", None),
+            ])
+        } else {
+            Btn::text_fg("Only show trips starting or ending at one place").build(
+                ctx,
+                "filter by place",
+                None,
+            )
+        }
+        .margin_below(5),
+    );
     col.push(
         format!(
             "{} trips aborted due to simulation glitch",
@@ -341,42 +325,81 @@ fn make(ctx: &mut EventCtx, app: &App, opts: &Options) -> Composite {
         .draw_text(ctx)
         .margin_below(5),
     );
-    col.push(
-        Widget::row(vec![
-            if opts.skip > 0 {
-                Btn::text_fg("<").build(ctx, "previous trips", None)
-            } else {
-                Btn::text_fg("<").inactive(ctx)
+    col.push(stats.draw(ctx).margin_below(5));
+    col.push(Checkbox::text(ctx, "group by mode", None, opts.group_by_mode).margin_below(5));
+
+    if opts.group_by_mode {
+        // Show the first page of each mode, with its own subtotal
+        for m in TripMode::all() {
+            let entries: Vec<&Entry> = data.iter().filter(|x| x.mode == m).collect();
+            if entries.is_empty() {
+                continue;
             }
-            .margin_right(10),
-            format!(
-                "{}-{} of {}",
-                if total_rows > 0 {
-                    prettyprint_usize(opts.skip + 1)
+            col.push(
+                Line(m.ongoing_verb())
+                    .small_heading()
+                    .fg(color_for_mode(app, m))
+                    .draw(ctx)
+                    .margin_above(10),
+            );
+            col.push(summarize(entries.iter().cloned()).draw(ctx).margin_below(5));
+            let rows = entries
+                .into_iter()
+                .take(ROWS)
+                .map(|x| make_row(ctx, app, x))
+                .collect();
+            col.extend(make_table(
+                ctx,
+                app,
+                make_headers(ctx, app, opts),
+                rows,
+                0.88 * ctx.canvas.window_width,
+            ));
+        }
+    } else {
+        col.push(
+            Widget::row(vec![
+                if opts.skip > 0 {
+                    Btn::text_fg("<").build(ctx, "previous trips", None)
+                } else {
+                    Btn::text_fg("<").inactive(ctx)
+                }
+                .margin_right(10),
+                format!(
+                    "{}-{} of {}",
+                    if total_rows > 0 {
+                        prettyprint_usize(opts.skip + 1)
+                    } else {
+                        "0".to_string()
+                    },
+                    prettyprint_usize((opts.skip + 1 + ROWS).min(total_rows)),
+                    prettyprint_usize(total_rows)
+                )
+                .draw_text(ctx)
+                .margin_right(10),
+                if opts.skip + 1 + ROWS < total_rows {
+                    Btn::text_fg(">").build(ctx, "next trips", None)
                 } else {
-                    "0".to_string()
+                    Btn::text_fg(">").inactive(ctx)
                 },
-                prettyprint_usize((opts.skip + 1 + ROWS).min(total_rows)),
-                prettyprint_usize(total_rows)
-            )
-            .draw_text(ctx)
-            .margin_right(10),
-            if opts.skip + 1 + ROWS < total_rows {
-                Btn::text_fg(">").build(ctx, "next trips", None)
-            } else {
-                Btn::text_fg(">").inactive(ctx)
-            },
-        ])
-        .margin_below(5),
-    );
+            ])
+            .margin_below(5),
+        );
 
-    col.extend(make_table(
-        ctx,
-        app,
-        headers,
-        rows,
-        0.88 * ctx.canvas.window_width,
-    ));
+        let rows = data
+            .iter()
+            .skip(opts.skip)
+            .take(ROWS)
+            .map(|x| make_row(ctx, app, x))
+            .collect();
+        col.extend(make_table(
+            ctx,
+            app,
+            make_headers(ctx, app, opts),
+            rows,
+            0.88 * ctx.canvas.window_width,
+        ));
+    }
     col.push(
         Filler::new(ScreenDims::new(
             0.15 * ctx.canvas.window_width,
@@ -392,6 +415,187 @@ fn make(ctx: &mut EventCtx, app: &App, opts: &Options) -> Composite {
         .build(ctx)
 }
 
+fn make_row(ctx: &EventCtx, app: &App, x: &Entry) -> (String, Vec<GeomBatch>) {
+    let mut row = vec![
+        Text::from(Line(x.trip.0.to_string())).render_ctx(ctx),
+        Text::from(Line(x.mode.ongoing_verb()).fg(color_for_mode(app, x.mode))).render_ctx(ctx),
+        Text::from(Line(x.departure.ampm_tostring())).render_ctx(ctx),
+        Text::from(Line(x.duration_after.to_string())).render_ctx(ctx),
+    ];
+    if app.has_prebaked().is_some() {
+        row.push(
+            Text::from_all(cmp_duration_shorter(x.duration_after, x.duration_before))
+                .render_ctx(ctx),
+        );
+        if x.duration_after == x.duration_before {
+            row.push(Text::from(Line("same")).render_ctx(ctx));
+        } else if x.duration_after < x.duration_before {
+            row.push(
+                Text::from(Line(format!(
+                    "{}% faster",
+                    (100.0 * (1.0 - (x.duration_after / x.duration_before))) as usize
+                )))
+                .render_ctx(ctx),
+            );
+        } else {
+            row.push(
+                Text::from(Line(format!(
+                    "{}% slower ",
+                    (100.0 * ((x.duration_after / x.duration_before) - 1.0)) as usize
+                )))
+                .render_ctx(ctx),
+            );
+        }
+    }
+    row.push(Text::from(Line(x.waiting.to_string())).render_ctx(ctx));
+    row.push(Text::from(Line(format!("{}%", x.percent_waiting))).render_ctx(ctx));
+
+    (x.trip.0.to_string(), row)
+}
+
+fn make_headers(ctx: &EventCtx, app: &App, opts: &Options) -> Vec<Widget> {
+    let btn = |value, name| {
+        if opts.sort_by == value {
+            Btn::text_bg2(format!(
+                "{} {}",
+                name,
+                if opts.descending { "↓" } else { "↑" }
+            ))
+            .build(ctx, name, None)
+        } else {
+            Btn::text_bg2(name).build_def(ctx, None)
+        }
+    };
+    let mut headers = vec![
+        Line("Trip ID").draw(ctx),
+        Line("Type").draw(ctx),
+        btn(SortBy::Departure, "Departure"),
+        btn(SortBy::Duration, "Duration"),
+    ];
+    if app.has_prebaked().is_some() {
+        headers.push(btn(SortBy::RelativeDuration, "Comparison"));
+        headers.push(btn(SortBy::PercentChangeDuration, "Normalized"));
+    }
+    headers.push(btn(SortBy::Waiting, "Time spent waiting"));
+    headers.push(btn(SortBy::PercentWaiting, "Percent waiting"));
+    headers
+}
+
+fn endpoint_id(endpoint: &TripEndpoint) -> ID {
+    match endpoint {
+        TripEndpoint::Bldg(b) => ID::Building(*b),
+        TripEndpoint::Border(i, _) => ID::Intersection(*i),
+    }
+}
+
+struct PickEndpoint {
+    composite: Composite,
+}
+
+impl PickEndpoint {
+    fn new(ctx: &mut EventCtx, app: &App) -> Box<dyn State> {
+        Box::new(PickEndpoint {
+            composite: Composite::new(
+                Widget::col(vec![
+                    Widget::row(vec![
+                        Line("Filter by place").small_heading().draw(ctx),
+                        Btn::plaintext("X")
+                            .build(ctx, "close", hotkey(Key::Escape))
+                            .align_right(),
+                    ]),
+                    "Click a building or border intersection".draw_text(ctx),
+                ])
+                .bg(app.cs.panel_bg)
+                .padding(10),
+            )
+            .aligned(HorizontalAlignment::Right, VerticalAlignment::Top)
+            .build(ctx),
+        })
+    }
+}
+
+impl State for PickEndpoint {
+    fn event(&mut self, ctx: &mut EventCtx, app: &mut App) -> Transition {
+        match self.composite.event(ctx) {
+            Some(Outcome::Clicked(x)) => match x.as_ref() {
+                "close" => {
+                    return Transition::Pop;
+                }
+                _ => unreachable!(),
+            },
+            None => {}
+        }
+
+        ctx.canvas_movement();
+
+        if ctx.redo_mouseover() {
+            app.primary.current_selection = app.calculate_current_selection(
+                ctx,
+                &app.primary.sim,
+                &ShowEverything::new(),
+                false,
+                true,
+                true,
+            );
+            // Trips only start or end at buildings and borders
+            match app.primary.current_selection {
+                Some(ID::Building(_)) => {}
+                Some(ID::Intersection(i)) if app.primary.map.get_i(i).is_border() => {}
+                _ => {
+                    app.primary.current_selection = None;
+                }
+            }
+        }
+        if let Some(place) = app.primary.current_selection.clone() {
+            if app.per_obj.left_click(ctx, "only show trips here") {
+                app.primary.current_selection = None;
+                return Transition::PopWithData(Box::new(move |state, ctx, app| {
+                    let table = state.downcast_mut::<TripTable>().unwrap();
+                    table.opts.endpoint = Some(place);
+                    table.opts.skip = 0;
+                    table.recalc(ctx, app);
+                }));
+            }
+        }
+
+        Transition::Keep
+    }
+
+    fn draw(&self, g: &mut GfxCtx, app: &App) {
+        self.composite.draw(g);
+        CommonState::draw_osd(g, app);
+    }
+}
+
+// Summarizes the filtered trips, not just the current page
+fn summarize<'a, I: Iterator<Item = &'a Entry>>(entries: I) -> Text {
+    let mut durations = Histogram::new();
+    let mut waiting = Histogram::new();
+    for x in entries {
+        durations.add(x.duration_after);
+        waiting.add(x.waiting);
+    }
+    let mut txt = Text::new();
+    if durations.count() == 0 {
+        txt.add(Line("No matching trips"));
+        return txt;
+    }
+    txt.add(Line(format!(
+        "{} matching trips",
+        prettyprint_usize(durations.count())
+    )));
+    for (name, hgram) in vec![("Duration", durations), ("Time spent waiting", waiting)] {
+        txt.add(Line(format!(
+            "{}: mean {}, median {}, 90%ile {}",
+            name,
+            hgram.select(Statistic::Mean),
+            hgram.select(Statistic::P50),
+            hgram.select(Statistic::P90)
+        )));
+    }
+    txt
+}
+
 // TODO Figure out a nicer API to construct generic sortable tables.
 pub fn make_table(
     ctx: &mut EventCtx,
@@ -1,36 +1,46 @@
 use crate::app::{App, ShowEverything};
 use crate::common::{CityPicker, CommonState};
 use crate::edit::EditMode;
-use crate::game::{State, Transition, WizardState};
+use crate::game::{msg, State, Transition, WizardState};
 use crate::helpers::{nice_map_name, ID};
+use crate::info::{OpenTrip, Tab};
 use crate::sandbox::gameplay::{GameplayMode, GameplayState};
 use crate::sandbox::SandboxControls;
 use crate::sandbox::SandboxMode;
-use abstutil::Timer;
+use abstutil::{prettyprint_usize, Counter, Timer};
 use ezgui::{
-    hotkey, lctrl, Btn, Choice, Color, Composite, EventCtx, GeomBatch, GfxCtx, HorizontalAlignment,
-    Key, Line, Outcome, ScreenRectangle, Spinner, Text, TextExt, VerticalAlignment, Widget,
+    hotkey, lctrl, Btn, Choice, Color, Composite, Drawable, EventCtx, GeomBatch, GfxCtx,
+    HorizontalAlignment, Key, Line, Outcome, ScreenRectangle, Spinner, Text, TextExt,
+    VerticalAlignment, Widget,
 };
-use geom::{Distance, Polygon};
+use geom::{Distance, Duration, Polygon, Time};
 use map_model::{
     BuildingID, IntersectionID, Map, PathConstraints, PathRequest, Position, NORMAL_LANE_THICKNESS,
 };
 use rand::seq::SliceRandom;
 use rand::Rng;
 use sim::{
-    DontDrawAgents, DrivingGoal, IndividTrip, PersonID, PersonSpec, Scenario, SidewalkSpot,
-    SpawnTrip, TripEndpoint, TripMode, TripSpec,
+    BorderSpawnOverTime, DontDrawAgents, DrivingGoal, IndividTrip, OriginDestination, PersonID,
+    PersonSpec, Scenario, ScenarioGenerator, SidewalkSpot, SpawnTrip, TripEndpoint, TripMode,
+    TripResult, TripSpec,
 };
 
 // TODO Maybe remember what things were spawned, offer to replay this later
 pub struct Freeform {
     top_center: Composite,
+    mode: GameplayMode,
+    can_undo_spawn: bool,
+    // How many trips have started from each border, as of some time
+    border_counts: (Time, Drawable),
 }
 
 impl Freeform {
     pub fn new(ctx: &mut EventCtx, app: &App, mode: GameplayMode) -> Box<dyn GameplayState> {
         Box::new(Freeform {
-            top_center: freeform_controller(ctx, app, mode, "none"),
+            top_center: freeform_controller(ctx, app, mode.clone(), "none"),
+            mode,
+            can_undo_spawn: app.primary.undo_spawn.is_some(),
+            border_counts: (app.primary.sim.time(), draw_border_counts(ctx, app)),
         })
     }
 }
@@ -42,6 +52,14 @@ impl GameplayState for Freeform {
         app: &mut App,
         _: &mut SandboxControls,
     ) -> Option<Transition> {
+        if self.border_counts.0 != app.primary.sim.time() {
+            self.border_counts = (app.primary.sim.time(), draw_border_counts(ctx, app));
+        }
+        if self.can_undo_spawn != app.primary.undo_spawn.is_some() {
+            self.can_undo_spawn = app.primary.undo_spawn.is_some();
+            self.top_center = freeform_controller(ctx, app, self.mode.clone(), "none");
+        }
+
         match self.top_center.event(ctx) {
             Some(Outcome::Clicked(x)) => match x.as_ref() {
                 "change map" => {
@@ -69,6 +87,28 @@ impl GameplayState for Freeform {
                     GameplayMode::Freeform(abstutil::path_map(app.primary.map.get_name())),
                 )))),
                 "Start a new trip" => Some(Transition::Push(AgentSpawner::new(ctx, app, None))),
+                "undo last spawn" => {
+                    let num_stuck = undo_spawn(app);
+                    app.recalculate_current_selection(ctx);
+                    if num_stuck > 0 {
+                        Some(Transition::Push(msg(
+                            "Undo last spawn",
+                            vec![format!(
+                                "{} trips couldn't be cancelled yet, like people riding a bus",
+                                num_stuck
+                            )],
+                        )))
+                    } else {
+                        Some(Transition::Keep)
+                    }
+                }
+                "Start through-traffic from every border" => {
+                    let before = app.primary.sim.get_all_people().len();
+                    spawn_border_traffic(app);
+                    remember_spawn(app, before);
+                    app.recalculate_current_selection(ctx);
+                    Some(Transition::Keep)
+                }
                 _ => unreachable!(),
             },
             None => None,
@@ -76,10 +116,33 @@ impl GameplayState for Freeform {
     }
 
     fn draw(&self, g: &mut GfxCtx, _: &App) {
+        g.redraw(&self.border_counts.1);
         self.top_center.draw(g);
     }
 }
 
+fn draw_border_counts(ctx: &mut EventCtx, app: &App) -> Drawable {
+    let map = &app.primary.map;
+    let mut counts: Counter<IntersectionID> = Counter::new();
+    for t in app.primary.sim.get_analytics().started_trips.keys() {
+        if let TripEndpoint::Border(i, _) = app.primary.sim.trip_info(*t).1 {
+            counts.inc(i);
+        }
+    }
+
+    let mut batch = GeomBatch::new();
+    for i in map.all_incoming_borders() {
+        batch.append(
+            Text::from(Line(abstutil::prettyprint_usize(counts.get(i.id))).fg(Color::BLACK))
+                .bg(Color::GREEN)
+                .render_to_batch(ctx.prerender)
+                .scale(0.5)
+                .centered_on(i.polygon.center()),
+        );
+    }
+    ctx.upload(batch)
+}
+
 pub fn freeform_controller(
     ctx: &mut EventCtx,
     app: &App,
@@ -108,9 +171,23 @@ pub fn freeform_controller(
     .centered()];
     if let GameplayMode::Freeform(_) = gameplay {
         rows.push(
-            Btn::text_fg("Start a new trip")
-                .build_def(ctx, None)
-                .centered_horiz(),
+            Widget::row(vec![
+                Btn::text_fg("Start a new trip").build_def(ctx, None),
+                Btn::text_fg("Start through-traffic from every border")
+                    .build_def(ctx, None)
+                    .margin_left(10),
+                if let Some(ref people) = app.primary.undo_spawn {
+                    Btn::text_fg(format!(
+                        "Undo last spawn ({} people)",
+                        prettyprint_usize(people.len())
+                    ))
+                    .build(ctx, "undo last spawn", lctrl(Key::Z))
+                    .margin_left(10)
+                } else {
+                    Widget::nothing()
+                },
+            ])
+            .centered_horiz(),
         );
         rows.push(
             Text::from_all(vec![
@@ -264,6 +341,7 @@ impl State for AgentSpawner {
                     return Transition::Pop;
                 }
                 "Confirm" => {
+                    let before = app.primary.sim.get_all_people().len();
                     let map = &app.primary.map;
                     let mut scenario = Scenario::empty(map, "one-shot");
                     let from = self.source.take().unwrap();
@@ -290,7 +368,11 @@ impl State for AgentSpawner {
                         &mut rng,
                         &mut Timer::new("spawn trip"),
                     );
-                    app.primary.sim.tiny_step(map, &mut app.primary.sim_cb);
+                    // If time is frozen, the new trips just start once it's unfrozen
+                    if !app.primary.frozen {
+                        app.primary.sim.tiny_step(map, &mut app.primary.sim_cb);
+                    }
+                    remember_spawn(app, before);
                     app.recalculate_current_selection(ctx);
                     return Transition::Pop;
                 }
@@ -444,6 +526,167 @@ impl State for AgentSpawner {
     }
 }
 
+// A stripped-down AgentSpawner for checking sidewalk connectivity: pick two buildings, and one
+// pedestrian immediately starts walking between them.
+struct WalkerSpawner {
+    composite: Composite,
+    source: BuildingID,
+    goal: Option<(BuildingID, Option<Polygon>)>,
+}
+
+impl WalkerSpawner {
+    fn new(ctx: &mut EventCtx, app: &App, source: BuildingID) -> Box<dyn State> {
+        Box::new(WalkerSpawner {
+            source,
+            goal: None,
+            composite: Composite::new(
+                Widget::col(vec![
+                    Widget::row(vec![
+                        Line("New walker").small_heading().draw(ctx),
+                        Btn::plaintext("X")
+                            .build(ctx, "close", hotkey(Key::Escape))
+                            .align_right(),
+                    ]),
+                    format!("Walking from {}", source).draw_text(ctx),
+                    "Click a building to specify the end".draw_text(ctx),
+                ])
+                .bg(app.cs.panel_bg)
+                .padding(10),
+            )
+            .aligned(HorizontalAlignment::Right, VerticalAlignment::Top)
+            .build(ctx),
+        })
+    }
+}
+
+impl State for WalkerSpawner {
+    fn event(&mut self, ctx: &mut EventCtx, app: &mut App) -> Transition {
+        match self.composite.event(ctx) {
+            Some(Outcome::Clicked(x)) => match x.as_ref() {
+                "close" => {
+                    return Transition::Pop;
+                }
+                _ => unreachable!(),
+            },
+            None => {}
+        }
+
+        ctx.canvas_movement();
+
+        if ctx.redo_mouseover() {
+            app.primary.current_selection = app.calculate_current_selection(
+                ctx,
+                &DontDrawAgents {},
+                &ShowEverything::new(),
+                false,
+                true,
+                true,
+            );
+            match app.primary.current_selection {
+                Some(ID::Building(b)) if b != self.source => {}
+                _ => {
+                    app.primary.current_selection = None;
+                }
+            }
+        }
+        let hovering = match app.primary.current_selection {
+            Some(ID::Building(b)) => b,
+            _ => {
+                self.goal = None;
+                return Transition::Keep;
+            }
+        };
+
+        if self
+            .goal
+            .as_ref()
+            .map(|(b, _)| *b != hovering)
+            .unwrap_or(true)
+        {
+            let map = &app.primary.map;
+            // Unlike AgentSpawner, show unreachable destinations too; finding them is the point.
+            let poly = path_request(
+                TripEndpoint::Bldg(self.source),
+                TripEndpoint::Bldg(hovering),
+                TripMode::Walk,
+                map,
+            )
+            .and_then(|req| map.pathfind(req))
+            .and_then(|path| path.trace(map, Distance::ZERO, None))
+            .map(|pl| pl.make_polygons(NORMAL_LANE_THICKNESS));
+            self.goal = Some((hovering, poly));
+        }
+
+        if self.goal.as_ref().unwrap().1.is_none() {
+            return Transition::Keep;
+        }
+        if app.per_obj.left_click(ctx, "walk here") {
+            let before = app.primary.sim.get_all_people().len();
+            let map = &app.primary.map;
+            let person = PersonID(app.primary.sim.get_all_people().len());
+            let mut scenario = Scenario::empty(map, "one-shot");
+            scenario.people.push(PersonSpec {
+                id: person,
+                orig_id: None,
+                trips: vec![IndividTrip {
+                    depart: app.primary.sim.time(),
+                    trip: SpawnTrip::new(
+                        TripEndpoint::Bldg(self.source),
+                        TripEndpoint::Bldg(hovering),
+                        TripMode::Walk,
+                        map,
+                    ),
+                }],
+            });
+            let mut rng = app.primary.current_flags.sim_flags.make_rng();
+            scenario.instantiate(
+                &mut app.primary.sim,
+                map,
+                &mut rng,
+                &mut Timer::new("spawn walker"),
+            );
+            if !app.primary.frozen {
+                app.primary.sim.tiny_step(map, &mut app.primary.sim_cb);
+            }
+            remember_spawn(app, before);
+            app.primary.current_selection = None;
+
+            // Open the new person's info panel, so it's easy to follow them.
+            let trip = app.primary.sim.get_person(person).trips[0];
+            return Transition::PopWithData(Box::new(move |state, ctx, app| {
+                let sandbox = state.downcast_mut::<SandboxMode>().unwrap();
+                let mut actions = sandbox.contextual_actions();
+                sandbox.controls.common.as_mut().unwrap().launch_info_panel(
+                    ctx,
+                    app,
+                    Tab::PersonTrips(person, OpenTrip::single(trip)),
+                    &mut actions,
+                );
+            }));
+        }
+
+        Transition::Keep
+    }
+
+    fn draw(&self, g: &mut GfxCtx, app: &App) {
+        self.composite.draw(g);
+        CommonState::draw_osd(g, app);
+
+        g.draw_polygon(
+            Color::BLUE.alpha(0.8),
+            &app.primary.map.get_b(self.source).polygon,
+        );
+        if let Some((b, ref poly)) = self.goal {
+            if let Some(p) = poly {
+                g.draw_polygon(Color::GREEN.alpha(0.8), &app.primary.map.get_b(b).polygon);
+                g.draw_polygon(Color::PURPLE, p);
+            } else {
+                g.draw_polygon(Color::RED.alpha(0.8), &app.primary.map.get_b(b).polygon);
+            }
+        }
+    }
+}
+
 // TODO This exists in a few other places, in less clear forms...
 fn path_request(
     from: TripEndpoint,
@@ -573,12 +816,74 @@ pub fn spawn_agents_around(i: IntersectionID, app: &mut App) {
     }
 
     sim.flush_spawner(spawner, map, &mut timer);
-    sim.tiny_step(map, &mut app.primary.sim_cb);
+    if !app.primary.frozen {
+        sim.tiny_step(map, &mut app.primary.sim_cb);
+    }
+}
+
+// Every way of spawning creates new people, so everybody past `before` came from this spawn.
+fn remember_spawn(app: &mut App, before: usize) {
+    let people = (before..app.primary.sim.get_all_people().len())
+        .map(PersonID)
+        .collect();
+    app.primary.undo_spawn = Some(people);
+}
+
+// Cancels every unfinished trip of the people from the last spawn. Returns the number of trips
+// that couldn't be cancelled.
+fn undo_spawn(app: &mut App) -> usize {
+    let mut num_stuck = 0;
+    for person in app.primary.undo_spawn.take().unwrap() {
+        for trip in app.primary.sim.get_person(person).trips.clone() {
+            match app.primary.sim.trip_to_agent(trip) {
+                TripResult::TripDone | TripResult::TripAborted => {}
+                _ => {
+                    if !app.primary.sim.cancel_trip(trip, &app.primary.map) {
+                        num_stuck += 1;
+                    }
+                }
+            }
+        }
+    }
+    num_stuck
+}
+
+// Over the next hour, send a steady stream of agents in from every border, heading anywhere.
+fn spawn_border_traffic(app: &mut App) {
+    let map = &app.primary.map;
+    let now = app.primary.sim.time();
+    let mut generator = ScenarioGenerator::empty("through-traffic");
+    for i in map.all_incoming_borders() {
+        // Borders without roads leading away can't start anything
+        if let Some(start_from_border) = i.some_outgoing_road(map) {
+            generator.border_spawn_over_time.push(BorderSpawnOverTime {
+                num_peds: 10,
+                num_cars: 30,
+                num_bikes: 5,
+                percent_use_transit: 0.0,
+                start_time: now,
+                stop_time: now + Duration::hours(1),
+                start_from_border,
+                goal: OriginDestination::Anywhere,
+            });
+        }
+    }
+
+    let mut timer = Timer::new("spawn through-traffic from borders");
+    let mut rng = app.primary.current_flags.sim_flags.make_rng();
+    let scenario = generator.generate(map, &mut rng, &mut timer);
+    scenario.instantiate(&mut app.primary.sim, map, &mut rng, &mut timer);
+    if !app.primary.frozen {
+        app.primary.sim.tiny_step(map, &mut app.primary.sim_cb);
+    }
 }
 
 pub fn actions(_: &App, id: ID) -> Vec<(Key, String)> {
     match id {
-        ID::Building(_) => vec![(Key::Z, "start a trip here".to_string())],
+        ID::Building(_) => vec![
+            (Key::Z, "start a trip here".to_string()),
+            (Key::W, "send a walker from here".to_string()),
+        ],
         ID::Intersection(_) => vec![(Key::Z, "spawn agents here".to_string())],
         _ => Vec::new(),
     }
@@ -589,8 +894,13 @@ pub fn execute(ctx: &mut EventCtx, app: &mut App, id: ID, action: String) -> Tra
         (ID::Building(b), "start a trip here") => {
             Transition::Push(AgentSpawner::new(ctx, app, Some(b)))
         }
+        (ID::Building(b), "send a walker from here") => {
+            Transition::Push(WalkerSpawner::new(ctx, app, b))
+        }
         (ID::Intersection(id), "spawn agents here") => {
+            let before = app.primary.sim.get_all_people().len();
             spawn_agents_around(id, app);
+            remember_spawn(app, before);
             Transition::Keep
         }
         _ => unreachable!(),
@@ -131,6 +131,51 @@ impl GameplayMode {
         })
     }
 
+    // The camera is remembered separately for each mode, so returning to a challenge resumes the
+    // same view. The tutorial controls the camera itself.
+    fn camera_state_name(&self) -> Option<String> {
+        let map = abstutil::basename(&self.map_path());
+        match self {
+            GameplayMode::Freeform(_) => Some(format!("{}_freeform", map)),
+            GameplayMode::PlayScenario(_, ref scenario) => Some(format!("{}_{}", map, scenario)),
+            GameplayMode::FixTrafficSignals => Some(format!("{}_fix_traffic_signals", map)),
+            GameplayMode::OptimizeCommute(p, _) => {
+                Some(format!("{}_optimize_commute_{}_{}", map, p.0, p.1))
+            }
+            GameplayMode::Tutorial(_) => None,
+        }
+    }
+
+    pub fn save_camera_state(&self, ctx: &EventCtx, app: &App) {
+        if let Some(name) = self.camera_state_name() {
+            // Another mode may have already taken over the camera
+            if app.session.camera_mode.as_ref() == Some(&name) {
+                ctx.canvas.save_camera_state(&name);
+            }
+        }
+    }
+
+    // Restarting the mode that already has the camera keeps the current view. Switching to another
+    // mode saves the old mode's view first.
+    pub fn take_camera(&self, ctx: &mut EventCtx, app: &mut App) {
+        let name = self.camera_state_name();
+        if app.session.camera_mode == name {
+            return;
+        }
+        if let Some(old) = app.session.camera_mode.take() {
+            ctx.canvas.save_camera_state(&old);
+        }
+        if let Some(ref name) = name {
+            ctx.canvas.load_camera_state(name);
+        }
+        app.session.camera_mode = name;
+    }
+
+    // Hands the camera to this mode as it is, without loading the mode's saved view.
+    pub fn keep_camera(&self, app: &mut App) {
+        app.session.camera_mode = self.camera_state_name();
+    }
+
     pub fn can_edit_lanes(&self) -> bool {
         match self {
             GameplayMode::FixTrafficSignals => false,
@@ -157,6 +202,8 @@ impl GameplayMode {
             match cmd {
                 EditCmd::ChangeLaneType { .. }
                 | EditCmd::ReverseLane { .. }
+                | EditCmd::AddLane { .. }
+                | EditCmd::RemoveLane { .. }
                 | EditCmd::ChangeSpeedLimit { .. } => {
                     if !self.can_edit_lanes() {
                         return false;
@@ -411,6 +458,7 @@ fn maybe_save_first(wiz: &mut Wizard, ctx: &mut EventCtx, app: &mut App) -> Opti
                 .map
                 .recalculate_pathfinding_after_edits(&mut timer);
         }
+        app.primary.proposal = None;
     });
     // TODO Don't make the player pick the FinalScore thing again :(
     Some(Transition::Pop)
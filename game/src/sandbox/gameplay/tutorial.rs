@@ -7,8 +7,8 @@ use crate::game::{msg, State, Transition};
 use crate::helpers::ID;
 use crate::sandbox::gameplay::{GameplayMode, GameplayState};
 use crate::sandbox::{
-    maybe_exit_sandbox, spawn_agents_around, AgentMeter, SandboxControls, SandboxMode,
-    SpeedControls, TimePanel,
+    maybe_exit_sandbox, maybe_exit_sandbox_with_progress, spawn_agents_around, AgentMeter,
+    SandboxControls, SandboxMode, SpeedControls, TimePanel,
 };
 use abstutil::Timer;
 use ezgui::{
@@ -17,6 +17,7 @@ use ezgui::{
     VerticalAlignment, Widget,
 };
 use geom::{ArrowCap, Distance, Duration, PolyLine, Polygon, Pt2D, Time};
+use instant::Instant;
 use map_model::raw::{OriginalIntersection, OriginalRoad};
 use map_model::{BuildingID, Map, OriginalLane, Position};
 use sim::{
@@ -106,7 +107,14 @@ impl Tutorial {
         match self.top_center.event(ctx) {
             Some(Outcome::Clicked(x)) => match x.as_ref() {
                 "Quit" => {
-                    return Some(maybe_exit_sandbox());
+                    if tut.current == TutorialPointer::new(0, 0) {
+                        return Some(maybe_exit_sandbox());
+                    }
+                    return Some(maybe_exit_sandbox_with_progress(format!(
+                        "You're on task {} of {}, and can resume from there later.",
+                        tut.current.stage + 1,
+                        tut.stages.len()
+                    )));
                 }
                 "previous tutorial" => {
                     tut.current = TutorialPointer::new(tut.current.stage - 1, 0);
@@ -358,8 +366,11 @@ impl GameplayState for Tutorial {
 
         if let Some(ref msg) = self.msg_panel {
             // Arrows underneath the message panel, but on top of other panels
-            if let Some((_, _, Some(fxn))) = tut.lines() {
-                let pt = (fxn)(g, app);
+            if let Some(pt) = tut
+                .lines()
+                .and_then(|(_, _, maybe_fxn)| maybe_fxn.as_ref())
+                .and_then(|fxn| (fxn)(g, app))
+            {
                 g.fork_screenspace();
                 g.draw_polygon(
                     Color::RED,
@@ -521,15 +532,25 @@ struct Stage {
     messages: Vec<(
         Vec<String>,
         HorizontalAlignment,
-        Option<Box<dyn Fn(&GfxCtx, &App) -> Pt2D>>,
+        Option<Box<dyn Fn(&GfxCtx, &App) -> Option<Pt2D>>>,
     )>,
     task: Task,
     warp_to: Option<(ID, f64)>,
     spawn: Option<Box<dyn Fn(&mut App)>>,
 }
 
-fn arrow(pt: ScreenPt) -> Option<Box<dyn Fn(&GfxCtx, &App) -> Pt2D>> {
-    Some(Box::new(move |_, _| pt.to_pt()))
+fn arrow(pt: ScreenPt) -> Option<Box<dyn Fn(&GfxCtx, &App) -> Option<Pt2D>>> {
+    Some(Box::new(move |_, _| Some(pt.to_pt())))
+}
+
+// Follows the agent around, and disappears once the agent does.
+fn arrow_to_agent(agent: AgentID) -> Option<Box<dyn Fn(&GfxCtx, &App) -> Option<Pt2D>>> {
+    Some(Box::new(move |g, app| {
+        app.primary
+            .sim
+            .canonical_pt_for_agent(agent, &app.primary.map)
+            .map(|pt| g.canvas.map_to_screen(pt).to_pt())
+    }))
 }
 
 impl Stage {
@@ -545,7 +566,7 @@ impl Stage {
     fn msg<I: Into<String>>(
         mut self,
         lines: Vec<I>,
-        point_to: Option<Box<dyn Fn(&GfxCtx, &App) -> Pt2D>>,
+        point_to: Option<Box<dyn Fn(&GfxCtx, &App) -> Option<Pt2D>>>,
     ) -> Stage {
         self.messages.push((
             lines.into_iter().map(|l| l.into()).collect(),
@@ -557,7 +578,7 @@ impl Stage {
     fn left_aligned_msg<I: Into<String>>(
         mut self,
         lines: Vec<I>,
-        point_to: Option<Box<dyn Fn(&GfxCtx, &App) -> Pt2D>>,
+        point_to: Option<Box<dyn Fn(&GfxCtx, &App) -> Option<Pt2D>>>,
     ) -> Stage {
         self.messages.push((
             lines.into_iter().map(|l| l.into()).collect(),
@@ -631,6 +652,11 @@ pub struct TutorialState {
     score_delivered: bool,
 
     fire_station: BuildingID,
+
+    // How long (in real time) the player spent on each stage, for UX research
+    timed_stage: usize,
+    stage_started: Instant,
+    stage_log: Vec<(usize, Duration)>,
 }
 
 fn make_bike_lane_scenario(map: &Map) -> ScenarioGenerator {
@@ -653,6 +679,7 @@ fn make_bike_lane_scenario(map: &Map) -> ScenarioGenerator {
 
 fn transition(ctx: &mut EventCtx, app: &mut App, tut: &mut TutorialState) -> Transition {
     tut.reset_state();
+    tut.log_stage_time(app.opts.dev);
     let mode = GameplayMode::Tutorial(tut.current);
     Transition::Replace(Box::new(SandboxMode::new(ctx, app, mode)))
 }
@@ -674,6 +701,37 @@ impl TutorialState {
         self.parking_found = false;
     }
 
+    // Only reports anything in dev mode
+    fn log_stage_time(&mut self, dev: bool) {
+        if self.current.stage == self.timed_stage {
+            return;
+        }
+        self.stage_log.push((
+            self.timed_stage,
+            Duration::realtime_elapsed(self.stage_started),
+        ));
+        self.timed_stage = self.current.stage;
+        self.stage_started = Instant::now();
+
+        if dev && self.stage().task == Task::Done {
+            let mut lines = Vec::new();
+            for (stage, dt) in &self.stage_log {
+                lines.push(format!(
+                    "Task {} ({}): {}",
+                    stage + 1,
+                    self.stages[*stage].task.label(),
+                    dt
+                ));
+            }
+            println!("Tutorial stage timing:\n{}", lines.join("\n"));
+            let path = abstutil::path_tutorial_log();
+            match std::fs::write(&path, lines.join("\n")) {
+                Ok(()) => println!("Wrote {}", path),
+                Err(err) => println!("Couldn't write {}: {}", path, err),
+            }
+        }
+    }
+
     fn stage(&self) -> &Stage {
         &self.stages[self.current.stage]
     }
@@ -691,7 +749,7 @@ impl TutorialState {
     ) -> Option<&(
         Vec<String>,
         HorizontalAlignment,
-        Option<Box<dyn Fn(&GfxCtx, &App) -> Pt2D>>,
+        Option<Box<dyn Fn(&GfxCtx, &App) -> Option<Pt2D>>>,
     )> {
         let stage = self.stage();
         if self.current.part == stage.messages.len() {
@@ -905,6 +963,10 @@ impl TutorialState {
             score_delivered: false,
 
             fire_station: app.primary.map.find_b_by_osm_id(731238736).unwrap(),
+
+            timed_stage: 0,
+            stage_started: Instant::now(),
+            stage_log: Vec::new(),
         };
 
         let tool_panel = tool_panel(ctx, app);
@@ -1149,16 +1211,7 @@ impl TutorialState {
                         "Why don't you follow this car to their destination,",
                         "see where they park, and then play a little... prank?",
                     ],
-                    Some(Box::new(|g, app| {
-                        g.canvas
-                            .map_to_screen(
-                                app.primary
-                                    .sim
-                                    .canonical_pt_for_agent(AgentID::Car(ESCORT), &app.primary.map)
-                                    .unwrap(),
-                            )
-                            .to_pt()
-                    })),
+                    arrow_to_agent(AgentID::Car(ESCORT)),
                 )
                 .msg(
                     vec![
@@ -23,8 +23,8 @@ use ezgui::{
     HorizontalAlignment, Key, Line, Outcome, Text, TextExt, VerticalAlignment, Widget, Wizard,
 };
 pub use gameplay::{spawn_agents_around, GameplayMode, TutorialPointer, TutorialState};
-use geom::{Polygon, Time};
-use map_model::MapEdits;
+use geom::{Distance, PolyLine, Polygon, Pt2D, Time};
+use map_model::{EditCmd, EditIntersection, MapEdits};
 use sim::{TripMode, VehicleType};
 pub use speed::TimeWarpScreen;
 pub use speed::{SpeedControls, TimePanel};
@@ -34,6 +34,8 @@ pub struct SandboxMode {
     pub gameplay_mode: GameplayMode,
 
     pub controls: SandboxControls,
+    // Only pop up the results once each time the simulation finishes
+    shown_results: bool,
 }
 
 pub struct SandboxControls {
@@ -46,10 +48,26 @@ pub struct SandboxControls {
     minimap: Option<Minimap>,
 }
 
+impl SandboxControls {
+    // After something like the font size changes, the existing panels need to be rebuilt.
+    pub fn recreate_panels(&mut self, ctx: &mut EventCtx, app: &App) {
+        if self.tool_panel.is_some() {
+            self.tool_panel = Some(tool_panel(ctx, app));
+        }
+        if self.time_panel.is_some() {
+            self.time_panel = Some(TimePanel::new(ctx, app));
+        }
+        if self.agent_meter.is_some() {
+            self.agent_meter = Some(AgentMeter::new(ctx, app));
+        }
+    }
+}
+
 impl SandboxMode {
     pub fn new(ctx: &mut EventCtx, app: &mut App, mode: GameplayMode) -> SandboxMode {
         app.primary.clear_sim();
         let gameplay = mode.initialize(ctx, app);
+        mode.take_camera(ctx, app);
 
         SandboxMode {
             controls: SandboxControls {
@@ -91,6 +109,7 @@ impl SandboxMode {
             },
             gameplay,
             gameplay_mode: mode,
+            shown_results: false,
         }
     }
 
@@ -124,11 +143,42 @@ impl State for SandboxMode {
             app.recalculate_current_selection(ctx);
         }
 
+        // Challenges and the tutorial handle finishing themselves
+        let is_sandbox = match self.gameplay_mode {
+            GameplayMode::Freeform(_) | GameplayMode::PlayScenario(_, _) => true,
+            _ => false,
+        };
+        if is_sandbox && app.opts.show_results_when_done {
+            // Don't count the empty simulation before anything has happened
+            let done = app.primary.sim.is_done() && app.primary.sim.num_trips().0 > 0;
+            if !done {
+                self.shown_results = false;
+            } else if !self.shown_results {
+                self.shown_results = true;
+                return Transition::Push(dashboards::results(ctx, app));
+            }
+        }
+
         // Order here is pretty arbitrary
         if app.opts.dev && ctx.input.new_was_pressed(&lctrl(Key::D).unwrap()) {
             return Transition::Push(Box::new(DebugMode::new(ctx, app)));
         }
-
+        // For quickly iterating on map imports. The old scenario might not match the re-imported
+        // map, so always start fresh in freeform mode, but keep looking at the same place.
+        if app.opts.dev && ctx.input.new_was_pressed(&lctrl(Key::R).unwrap()) {
+            let path = abstutil::path_map(app.primary.map.get_name());
+            let (cam_x, cam_y, cam_zoom) =
+                (ctx.canvas.cam_x, ctx.canvas.cam_y, ctx.canvas.cam_zoom);
+            ctx.loading_screen("reload map", |ctx, _| {
+                app.switch_map(ctx, path.clone());
+            });
+            ctx.canvas.cam_x = cam_x;
+            ctx.canvas.cam_y = cam_y;
+            ctx.canvas.cam_zoom = cam_zoom;
+            let mode = GameplayMode::Freeform(path);
+            mode.keep_camera(app);
+            return Transition::Replace(Box::new(SandboxMode::new(ctx, app, mode)));
+        }
         if let Some(ref mut m) = self.controls.minimap {
             if let Some(t) = m.event(ctx, app) {
                 return t;
@@ -228,7 +278,8 @@ impl State for SandboxMode {
         self.gameplay.draw(g, app);
     }
 
-    fn on_destroy(&mut self, _: &mut EventCtx, app: &mut App) {
+    fn on_destroy(&mut self, ctx: &mut EventCtx, app: &mut App) {
+        self.gameplay_mode.save_camera_state(ctx, app);
         app.layer = None;
         app.agent_cs = AgentColorScheme::new(&app.cs);
         self.gameplay.on_destroy(app);
@@ -236,13 +287,38 @@ impl State for SandboxMode {
 }
 
 pub fn maybe_exit_sandbox() -> Transition {
-    Transition::Push(WizardState::new(Box::new(exit_sandbox)))
+    Transition::Push(WizardState::new(Box::new(|wiz, ctx, app| {
+        exit_sandbox(wiz, ctx, app, None)
+    })))
 }
 
-fn exit_sandbox(wiz: &mut Wizard, ctx: &mut EventCtx, app: &mut App) -> Option<Transition> {
+// Like maybe_exit_sandbox, but first reminds the player how far they've gotten.
+pub fn maybe_exit_sandbox_with_progress(progress: String) -> Transition {
+    Transition::Push(WizardState::new(Box::new(move |wiz, ctx, app| {
+        exit_sandbox(wiz, ctx, app, Some(progress.clone()))
+    })))
+}
+
+fn exit_sandbox(
+    wiz: &mut Wizard,
+    ctx: &mut EventCtx,
+    app: &mut App,
+    progress: Option<String>,
+) -> Option<Transition> {
     let mut wizard = wiz.wrap(ctx);
     let unsaved = app.primary.map.unsaved_edits();
-    let (resp, _) = wizard.choose("Are you ready to leave this mode?", || {
+    let mut query = if unsaved {
+        format!(
+            "Are you ready to leave this mode? This will discard {}.",
+            summarize_edits(app.primary.map.get_edits())
+        )
+    } else {
+        "Are you ready to leave this mode?".to_string()
+    };
+    if let Some(progress) = progress {
+        query = format!("{} {}", progress, query);
+    }
+    let (resp, _) = wizard.choose(&query, || {
         let mut choices = Vec::new();
         choices.push(Choice::new("keep playing", ()));
         if unsaved {
@@ -264,6 +340,7 @@ fn exit_sandbox(wiz: &mut Wizard, ctx: &mut EventCtx, app: &mut App) -> Option<T
                 .map
                 .recalculate_pathfinding_after_edits(&mut timer);
         }
+        app.primary.proposal = None;
         app.primary.clear_sim();
         app.set_prebaked(None);
     });
@@ -271,16 +348,132 @@ fn exit_sandbox(wiz: &mut Wizard, ctx: &mut EventCtx, app: &mut App) -> Option<T
     Some(Transition::Clear(vec![MainMenu::new(ctx, app)]))
 }
 
+// Like "7 edits (3 signals, 4 lanes)"
+fn summarize_edits(edits: &MapEdits) -> String {
+    let mut signals = 0;
+    let mut stop_signs = 0;
+    let mut closures = 0;
+    let mut lanes = 0;
+    let mut speed_limits = 0;
+    for cmd in &edits.commands {
+        match cmd {
+            EditCmd::ChangeLaneType { .. }
+            | EditCmd::ReverseLane { .. }
+            | EditCmd::AddLane { .. }
+            | EditCmd::RemoveLane { .. } => {
+                lanes += 1;
+            }
+            EditCmd::ChangeSpeedLimit { .. } => {
+                speed_limits += 1;
+            }
+            EditCmd::ChangeIntersection { ref new, .. } => match new {
+                EditIntersection::TrafficSignal(_) => {
+                    signals += 1;
+                }
+                EditIntersection::StopSign(_) => {
+                    stop_signs += 1;
+                }
+                EditIntersection::Closed => {
+                    closures += 1;
+                }
+            },
+        }
+    }
+
+    let mut parts = Vec::new();
+    for (cnt, singular, plural) in vec![
+        (signals, "signal", "signals"),
+        (stop_signs, "stop sign", "stop signs"),
+        (lanes, "lane", "lanes"),
+        (closures, "closure", "closures"),
+        (speed_limits, "speed limit", "speed limits"),
+    ] {
+        if cnt == 1 {
+            parts.push(format!("1 {}", singular));
+        } else if cnt > 0 {
+            parts.push(format!("{} {}", cnt, plural));
+        }
+    }
+    format!("{} edits ({})", edits.commands.len(), parts.join(", "))
+}
+
 pub struct AgentMeter {
     time: Time,
     pub composite: Composite,
+    // The sparkline is only redrawn when more trips finish. (Number finished, drawn plot)
+    sparkline: (usize, GeomBatch),
+}
+
+const SPARKLINE_DIMS: (f64, f64) = (150.0, 30.0);
+
+// A tiny plot of how many trips have finished so far
+fn draw_sparkline(app: &App) -> GeomBatch {
+    let (width, height) = SPARKLINE_DIMS;
+    let num_buckets = 30;
+    let now = app.primary.sim.time();
+    let finished = &app.primary.sim.get_analytics().finished_trips;
+    let total = finished.len().max(1) as f64;
+
+    let mut pts = Vec::new();
+    let mut count = 0;
+    for i in 0..=num_buckets {
+        let pct = (i as f64) / (num_buckets as f64);
+        let t = Time::START_OF_DAY + (now - Time::START_OF_DAY) * pct;
+        while count < finished.len() && finished[count].0 <= t {
+            count += 1;
+        }
+        pts.push(Pt2D::new(
+            width * pct,
+            height * (1.0 - (count as f64) / total),
+        ));
+    }
+
+    let mut batch = GeomBatch::from(vec![(Color::grey(0.3), Polygon::rectangle(width, height))]);
+    if let Some(pl) = PolyLine::maybe_new(pts) {
+        batch.push(Color::WHITE, pl.make_polygons(Distance::meters(2.0)));
+    }
+    batch
+}
+
+// The sparkline links to the full table
+fn finished_trips_sparkline(ctx: &EventCtx, app: &App, batch: GeomBatch) -> Widget {
+    let rect = Polygon::rectangle(SPARKLINE_DIMS.0, SPARKLINE_DIMS.1);
+    let mut hovered = GeomBatch::from(vec![(app.cs.hovering, rect.clone())]);
+    hovered.append(batch.clone());
+
+    Btn::custom(batch, hovered, rect)
+        .tooltip(Text::from(Line("Finished trips over time")))
+        .build(ctx, "finished trips over time", None)
+        .centered_horiz()
+}
+
+// Shows "en route (finished)"
+fn mode_count(ctx: &EventCtx, (active, finished): (usize, usize)) -> Widget {
+    Text::from_all(vec![
+        Line(abstutil::prettyprint_usize(active)),
+        Line(format!(" ({})", abstutil::prettyprint_usize(finished))).secondary(),
+    ])
+    .draw(ctx)
 }
 
 impl AgentMeter {
     pub fn new(ctx: &mut EventCtx, app: &App) -> AgentMeter {
+        AgentMeter::with_sparkline(ctx, app, None)
+    }
+
+    fn with_sparkline(
+        ctx: &mut EventCtx,
+        app: &App,
+        prev_sparkline: Option<(usize, GeomBatch)>,
+    ) -> AgentMeter {
         use abstutil::prettyprint_usize;
 
         let (finished, unfinished, by_mode) = app.primary.sim.num_trips();
+        let num_finished = app.primary.sim.get_analytics().finished_trips.len();
+        let sparkline = match prev_sparkline {
+            Some((n, batch)) if n == num_finished => (n, batch),
+            _ => (num_finished, draw_sparkline(app)),
+        };
 
         let rows = vec![
             "Active trips".draw_text(ctx),
@@ -288,19 +481,19 @@ impl AgentMeter {
                 Widget::row(vec![
                     Widget::draw_svg(ctx, "../data/system/assets/meters/pedestrian.svg")
                         .margin_right(5),
-                    prettyprint_usize(by_mode[&TripMode::Walk]).draw_text(ctx),
+                    mode_count(ctx, by_mode[&TripMode::Walk]),
                 ]),
                 Widget::row(vec![
                     Widget::draw_svg(ctx, "../data/system/assets/meters/bike.svg").margin_right(5),
-                    prettyprint_usize(by_mode[&TripMode::Bike]).draw_text(ctx),
+                    mode_count(ctx, by_mode[&TripMode::Bike]),
                 ]),
                 Widget::row(vec![
                     Widget::draw_svg(ctx, "../data/system/assets/meters/car.svg").margin_right(5),
-                    prettyprint_usize(by_mode[&TripMode::Drive]).draw_text(ctx),
+                    mode_count(ctx, by_mode[&TripMode::Drive]),
                 ]),
                 Widget::row(vec![
                     Widget::draw_svg(ctx, "../data/system/assets/meters/bus.svg").margin_right(5),
-                    prettyprint_usize(by_mode[&TripMode::Transit]).draw_text(ctx),
+                    mode_count(ctx, by_mode[&TripMode::Transit]),
                 ]),
             ])
             .centered(),
@@ -327,12 +520,43 @@ impl AgentMeter {
                         prettyprint_usize(finished),
                         pct as usize
                     )));
+                    if app.has_prebaked().is_some() {
+                        let now = app.primary.sim.time();
+                        // Trips are recorded in the order they finish, so count the ones so far
+                        // with a binary search.
+                        let baseline = app
+                            .prebaked()
+                            .finished_trips
+                            .binary_search_by(|(t, _, _, _)| {
+                                if *t <= now {
+                                    std::cmp::Ordering::Less
+                                } else {
+                                    std::cmp::Ordering::Greater
+                                }
+                            })
+                            .unwrap_err();
+                        let color = if finished >= baseline {
+                            Color::GREEN
+                        } else {
+                            Color::RED
+                        };
+                        txt.append(
+                            Line(format!(" (baseline {})", prettyprint_usize(baseline))).fg(color),
+                        );
+                    }
                     txt.draw(ctx)
                 },
                 Btn::svg_def("../data/system/assets/meters/trip_histogram.svg")
                     .build(ctx, "more data", hotkey(Key::Q))
                     .align_right(),
             ]),
+            finished_trips_sparkline(ctx, app, sparkline.1.clone()).margin_above(5),
+            format!(
+                "Total delay: {:.1} person-hours",
+                app.primary.sim.total_delay().inner_seconds() / 3600.0
+            )
+            .draw_text(ctx)
+            .margin_above(5),
         ];
 
         let composite = Composite::new(Widget::col(rows).bg(app.cs.panel_bg).padding(20))
@@ -342,17 +566,19 @@ impl AgentMeter {
         AgentMeter {
             time: app.primary.sim.time(),
             composite,
+            sparkline,
         }
     }
 
     pub fn event(&mut self, ctx: &mut EventCtx, app: &mut App) -> Option<Transition> {
         if self.time != app.primary.sim.time() {
-            *self = AgentMeter::new(ctx, app);
+            let sparkline = std::mem::replace(&mut self.sparkline, (0, GeomBatch::new()));
+            *self = AgentMeter::with_sparkline(ctx, app, Some(sparkline));
             return self.event(ctx, app);
         }
         match self.composite.event(ctx) {
             Some(Outcome::Clicked(x)) => match x.as_ref() {
-                "more data" => {
+                "more data" | "finished trips over time" => {
                     return Some(Transition::Push(dashboards::TripTable::new(ctx, app)));
                 }
                 _ => unreachable!(),
@@ -3,13 +3,15 @@ use crate::common::Warping;
 use crate::game::{msg, State, Transition};
 use crate::helpers::ID;
 use crate::sandbox::{GameplayMode, SandboxMode};
+use abstutil::prettyprint_usize;
 use ezgui::{
-    hotkey, AreaSlider, Btn, Choice, Color, Composite, EventCtx, EventLoopMode, GeomBatch, GfxCtx,
-    HorizontalAlignment, Key, Line, Outcome, PersistentSplit, RewriteColor, Text,
+    hotkey, lctrl, AreaSlider, Btn, Choice, Color, Composite, EventCtx, EventLoopMode, GeomBatch,
+    GfxCtx, HorizontalAlignment, Key, Line, Outcome, PersistentSplit, RewriteColor, Text,
     VerticalAlignment, Widget,
 };
 use geom::{Duration, Polygon, Pt2D, Time};
 use instant::Instant;
+use map_model::IntersectionID;
 use sim::AlertLocation;
 
 pub struct SpeedControls {
@@ -17,6 +19,14 @@ pub struct SpeedControls {
 
     paused: bool,
     setting: SpeedSetting,
+
+    // How long the last simulation step took, and the stats displayed in dev mode
+    last_step_time: Duration,
+    perf_hud: Option<Composite>,
+    gridlock_banner: Option<Composite>,
+    gridlocks_seen: usize,
+    // For touring through gridlocked intersections
+    next_gridlock: usize,
 }
 
 #[derive(Clone, Copy, PartialEq, PartialOrd)]
@@ -97,6 +107,22 @@ impl SpeedControls {
             .bg(app.cs.section_bg)
             .margin_right(16),
         );
+        if app.opts.dev {
+            row.push(
+                Btn::text_fg("step back")
+                    .build(ctx, "step backwards", lctrl(Key::M))
+                    .bg(app.cs.section_bg)
+                    .centered_vert()
+                    .margin_right(16),
+            );
+            row.push(
+                Btn::text_fg("freeze")
+                    .build(ctx, "freeze time", lctrl(Key::Space))
+                    .bg(app.cs.section_bg)
+                    .centered_vert()
+                    .margin_right(16),
+            );
+        }
 
         row.push(
             Widget::row(vec![
@@ -118,12 +144,44 @@ impl SpeedControls {
             .build(ctx)
     }
 
+    // Only offers a way out, so nothing can accidentally step the sim
+    fn make_frozen_panel(ctx: &mut EventCtx, app: &App) -> Composite {
+        Composite::new(
+            Widget::row(vec![
+                Line("Time is frozen")
+                    .draw(ctx)
+                    .centered_vert()
+                    .margin_right(16),
+                Btn::text_fg("unfreeze")
+                    .build(ctx, "unfreeze time", lctrl(Key::Space))
+                    .bg(app.cs.section_bg),
+            ])
+            .bg(app.cs.panel_bg)
+            .padding(16),
+        )
+        .aligned(
+            HorizontalAlignment::Center,
+            VerticalAlignment::BottomAboveOSD,
+        )
+        .build(ctx)
+    }
+
     pub fn new(ctx: &mut EventCtx, app: &App) -> SpeedControls {
-        let composite = SpeedControls::make_panel(ctx, app, false, SpeedSetting::Realtime);
+        // Freezing outlives the sandbox, so the lock survives resetting or rewinding
+        let composite = if app.primary.frozen {
+            SpeedControls::make_frozen_panel(ctx, app)
+        } else {
+            SpeedControls::make_panel(ctx, app, false, SpeedSetting::Realtime)
+        };
         SpeedControls {
             composite,
-            paused: false,
+            paused: app.primary.frozen,
             setting: SpeedSetting::Realtime,
+            last_step_time: Duration::ZERO,
+            perf_hud: None,
+            gridlock_banner: None,
+            gridlocks_seen: app.primary.sim.get_analytics().gridlocks.len(),
+            next_gridlock: 0,
         }
     }
 
@@ -133,6 +191,31 @@ impl SpeedControls {
         app: &mut App,
         maybe_mode: Option<&GameplayMode>,
     ) -> Option<Transition> {
+        self.perf_hud = if app.opts.dev && app.opts.show_perf_hud {
+            Some(self.make_perf_hud(ctx, app))
+        } else {
+            None
+        };
+
+        if app.primary.frozen {
+            match self.composite.event(ctx) {
+                Some(Outcome::Clicked(x)) => match x.as_ref() {
+                    "unfreeze time" => {
+                        app.primary.frozen = false;
+                        self.composite =
+                            SpeedControls::make_panel(ctx, app, self.paused, self.setting);
+                    }
+                    _ => unreachable!(),
+                },
+                None => {}
+            }
+            return None;
+        }
+
+        if app.opts.pause_on_focus_loss && ctx.input.window_lost_cursor() {
+            self.pause(ctx, app);
+        }
+
         match self.composite.event(ctx) {
             Some(Outcome::Clicked(x)) => match x.as_ref() {
                 "real-time speed" => {
@@ -163,6 +246,12 @@ impl SpeedControls {
                 "pause" => {
                     self.pause(ctx, app);
                 }
+                "freeze time" => {
+                    self.paused = true;
+                    app.primary.frozen = true;
+                    self.composite = SpeedControls::make_frozen_panel(ctx, app);
+                    return None;
+                }
                 "reset to midnight" => {
                     if let Some(mode) = maybe_mode {
                         return Some(Transition::Replace(Box::new(SandboxMode::new(
@@ -200,6 +289,32 @@ impl SpeedControls {
                         false,
                     )));
                 }
+                "step backwards" => {
+                    if app.primary.frozen {
+                        return Some(Transition::Push(msg(
+                            "Error",
+                            vec!["Time is frozen. Unfreeze it before stepping backwards."],
+                        )));
+                    }
+                    // The simulation is deterministic, so just replay from midnight.
+                    let dt = self.composite.persistent_split_value("step forwards");
+                    let target = if app.primary.sim.time() - Time::START_OF_DAY > dt {
+                        app.primary.sim.time() - dt
+                    } else {
+                        Time::START_OF_DAY
+                    };
+                    if let Some(mode) = maybe_mode {
+                        return Some(Transition::ReplaceThenPush(
+                            Box::new(SandboxMode::new(ctx, app, mode.clone())),
+                            TimeWarpScreen::new(ctx, app, target, false),
+                        ));
+                    } else {
+                        return Some(Transition::Push(msg(
+                            "Error",
+                            vec!["Sorry, you can't go rewind time from this mode."],
+                        )));
+                    }
+                }
                 _ => unreachable!(),
             },
             None => {}
@@ -258,16 +373,79 @@ impl SpeedControls {
                     SpeedSetting::Faster => 30.0,
                     SpeedSetting::Fastest => 3600.0,
                 };
-                let dt = multiplier * real_dt;
+                let mut dt = multiplier * real_dt;
+                // Don't step past --end_time
+                let before = app.primary.sim.time();
+                let end_time = app.primary.current_flags.sim_flags.end_time;
+                if let Some(t) = end_time {
+                    if before < t {
+                        dt = dt.min(t - before);
+                    }
+                }
                 // TODO This should match the update frequency in ezgui. Plumb along the deadline
                 // or frequency to here.
+                let started = Instant::now();
                 app.primary.sim.time_limited_step(
                     &app.primary.map,
                     dt,
                     Duration::seconds(0.033),
                     &mut app.primary.sim_cb,
                 );
+                self.last_step_time = Duration::realtime_elapsed(started);
                 app.recalculate_current_selection(ctx);
+
+                if let Some(t) = end_time {
+                    if before < t && app.primary.sim.time() >= t {
+                        self.pause(ctx, app);
+                        let (finished, unfinished, _) = app.primary.sim.num_trips();
+                        return Some(Transition::Push(msg(
+                            "Reached the end time",
+                            vec![
+                                format!("Stopped at {}, as requested by --end_time", t),
+                                format!(
+                                    "{} trips finished, {} haven't",
+                                    prettyprint_usize(finished),
+                                    prettyprint_usize(unfinished)
+                                ),
+                                "You can resume the simulation to keep going.".to_string(),
+                            ],
+                        )));
+                    }
+                }
+            }
+        }
+
+        let gridlocks = &app.primary.sim.get_analytics().gridlocks;
+        if gridlocks.len() > self.gridlocks_seen {
+            self.gridlocks_seen = gridlocks.len();
+            let i = gridlocks.last().unwrap().1;
+            self.gridlock_banner = Some(make_gridlock_banner(ctx, app, i));
+        }
+        if self.gridlock_banner.is_some() && app.primary.sim.current_gridlocks().is_empty() {
+            // Everything cleared up
+            self.gridlock_banner = None;
+        }
+        if let Some(ref mut banner) = self.gridlock_banner {
+            match banner.event(ctx) {
+                Some(Outcome::Clicked(x)) => match x.as_ref() {
+                    "next gridlock" => {
+                        let current = app.primary.sim.current_gridlocks();
+                        let id = ID::Intersection(current[self.next_gridlock % current.len()]);
+                        self.next_gridlock += 1;
+                        return Some(Transition::Push(Warping::new(
+                            ctx,
+                            id.canonical_point(&app.primary).unwrap(),
+                            Some(10.0),
+                            Some(id),
+                            &mut app.primary,
+                        )));
+                    }
+                    "dismiss gridlock" => {
+                        self.gridlock_banner = None;
+                    }
+                    _ => unreachable!(),
+                },
+                None => {}
             }
         }
 
@@ -310,18 +488,39 @@ impl SpeedControls {
         None
     }
 
+    fn make_perf_hud(&self, ctx: &mut EventCtx, app: &App) -> Composite {
+        let mut txt = Text::from(Line("Performance").small_heading());
+        txt.add(Line(format!(
+            "Last frame: {} ({} draw calls)",
+            ctx.canvas.last_frame_time, ctx.canvas.last_frame_draw_calls
+        )));
+        txt.add(Line(format!("Last sim step: {}", self.last_step_time)));
+        Composite::new(txt.draw(ctx).bg(app.cs.panel_bg).padding(10))
+            .aligned(HorizontalAlignment::Left, VerticalAlignment::Center)
+            .build(ctx)
+    }
+
     pub fn draw(&self, g: &mut GfxCtx) {
         self.composite.draw(g);
+        if let Some(ref c) = self.perf_hud {
+            c.draw(g);
+        }
+        if let Some(ref c) = self.gridlock_banner {
+            c.draw(g);
+        }
     }
 
     pub fn pause(&mut self, ctx: &mut EventCtx, app: &App) {
-        if !self.paused {
+        if !self.paused && !app.primary.frozen {
             self.paused = true;
             self.composite = SpeedControls::make_panel(ctx, app, self.paused, self.setting);
         }
     }
 
     pub fn resume_realtime(&mut self, ctx: &mut EventCtx, app: &App) {
+        if app.primary.frozen {
+            return;
+        }
         if self.paused || self.setting != SpeedSetting::Realtime {
             self.paused = false;
             self.setting = SpeedSetting::Realtime;
@@ -378,6 +577,7 @@ impl JumpToTime {
                         ctx,
                         0.25 * ctx.canvas.window_width,
                         target.to_percent(end_of_day).min(1.0),
+                        Box::new(move |pct| end_of_day.percent_of(pct).ampm_tostring()),
                     )
                     .named("time slider")
                     .margin_below(15),
@@ -485,6 +685,12 @@ impl TimeWarpScreen {
         target: Time,
         mut traffic_jams: bool,
     ) -> Box<dyn State> {
+        if app.primary.frozen {
+            return msg(
+                "Error",
+                vec!["Time is frozen. Unfreeze it from the speed controls to jump ahead."],
+            );
+        }
         if traffic_jams {
             if app.primary.sim_cb.is_none() {
                 app.primary.sim_cb = Some(Box::new(FindDelayedIntersections {
@@ -685,6 +891,28 @@ impl TimePanel {
     }
 }
 
+fn make_gridlock_banner(ctx: &mut EventCtx, app: &App, i: IntersectionID) -> Composite {
+    let mut txt = Text::from(Line(format!("Gridlock detected at {}", i)).small_heading());
+    txt.add(Line(format!(
+        "{} intersections are currently gridlocked",
+        app.primary.sim.current_gridlocks().len()
+    )));
+    Composite::new(
+        Widget::row(vec![
+            txt.draw(ctx).margin_right(10),
+            Btn::text_bg2("Jump to next gridlock")
+                .build(ctx, "next gridlock", hotkey(Key::G))
+                .margin_right(5),
+            Btn::text_fg("X").build(ctx, "dismiss gridlock", None),
+        ])
+        .bg(Color::RED.alpha(0.8))
+        .padding(10)
+        .centered_vert(),
+    )
+    .aligned(HorizontalAlignment::Center, VerticalAlignment::Top)
+    .build(ctx)
+}
+
 fn area_under_curve(raw: Vec<(Time, usize)>, width: f64, height: f64) -> Polygon {
     assert!(!raw.is_empty());
     let min_x = Time::START_OF_DAY;
@@ -10,9 +10,12 @@ pub enum Statistic {
     P90,
     P99,
     Max,
+    // Any percentile in [0, 100]. Like P85, commonly used in traffic engineering.
+    Percentile(u8),
 }
 
 impl Statistic {
+    // Doesn't include every possible Percentile.
     pub fn all() -> Vec<Statistic> {
         vec![
             Statistic::Min,
@@ -34,6 +37,7 @@ impl std::fmt::Display for Statistic {
             Statistic::P90 => write!(f, "90%ile"),
             Statistic::P99 => write!(f, "99%ile"),
             Statistic::Max => write!(f, "maximum"),
+            Statistic::Percentile(p) => write!(f, "{}%ile", p),
         }
     }
 }
@@ -147,6 +151,10 @@ impl<T: HgramValue<T>> Histogram<T> {
             Statistic::P50 => self.histogram.percentile(50.0).unwrap(),
             Statistic::P90 => self.histogram.percentile(90.0).unwrap(),
             Statistic::P99 => self.histogram.percentile(99.0).unwrap(),
+            Statistic::Percentile(p) => {
+                assert!(p <= 100);
+                self.histogram.percentile(p as f64).unwrap()
+            }
             Statistic::Min => {
                 return self.min;
             }
@@ -1,19 +1,42 @@
 use abstutil::{prettyprint_usize, CmdArgs, Timer};
-use map_model::Map;
+use geom::{Duration, Speed, Time};
+use map_model::{Map, PathConstraints, PathRequest, Position};
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
-use sim::{AlertHandler, Scenario, Sim, SimFlags};
+use sim::{AlertHandler, DrivingGoal, Scenario, Sim, SimFlags, SpawnTrip};
 
 // This is specialized to experiment with running the pandemic model over long time periods.
 // Original functionality for profiling and debugging gridlock have been removed.
+//
+// With --end_time=HH:MM, the simulation stops at that time, even if trips are still in progress.
+//
+// With --compare_seeds=N, it instead runs the weekday scenario with N different RNG seeds and
+// reports how much the results vary, to tell if the effect of some edits is just noise.
+//
+// With --mode_shift, it doesn't simulate anything. For every driving trip in the weekday scenario,
+// it estimates how long the same trip would take by bike or on foot, and reports how many could be
+// faster.
 
 fn main() {
     let mut args = CmdArgs::new();
     let num_days = args
         .optional_parse("--days", |s| s.parse::<usize>())
         .unwrap_or(1);
+    let num_seeds = args.optional_parse("--compare_seeds", |s| s.parse::<u8>());
+    let end_time = args.optional_parse("--end_time", SimFlags::parse_end_time);
+    let mode_shift = args.enabled("--mode_shift");
     args.done();
 
+    if mode_shift {
+        estimate_mode_shift();
+        return;
+    }
+
+    if let Some(n) = num_seeds {
+        compare_seeds(n, end_time);
+        return;
+    }
+
     let mut sim_flags = SimFlags::synthetic_test("montlake", "pandemic");
     sim_flags.opts.enable_pandemic_model = Some(XorShiftRng::from_seed([sim_flags.rng_seed; 16]));
     // Less spam
@@ -34,25 +57,230 @@ fn main() {
         .instantiate(&mut sim, &map, &mut rng, &mut timer);
     timer.done();
 
-    run_experiment(&map, &mut sim);
+    run_experiment(&map, &mut sim, end_time);
 }
 
-fn run_experiment(map: &Map, sim: &mut Sim) {
+fn run_experiment(map: &Map, sim: &mut Sim, end_time: Option<Time>) {
     let timer = Timer::new("run sim until done");
-    sim.run_until_done(
-        &map,
-        |sim, _map| {
-            // This'll run every 30 sim seconds
-            if false {
-                println!(
-                    "At {}, {} infected",
-                    sim.time(),
-                    prettyprint_usize(sim.get_pandemic_model().unwrap().count_infected())
-                );
+    let callback = |sim: &mut Sim, _: &Map| {
+        // This'll run every 30 sim seconds
+        if false {
+            println!(
+                "At {}, {} infected",
+                sim.time(),
+                prettyprint_usize(sim.get_pandemic_model().unwrap().count_infected())
+            );
+        }
+    };
+    if let Some(t) = end_time {
+        sim.run_until_done_or_time(&map, callback, t);
+    } else {
+        sim.run_until_done(&map, callback, None);
+    }
+    timer.done();
+    let (finished, unfinished, _) = sim.num_trips();
+    println!(
+        "Done at {}: {} trips finished, {} unfinished",
+        sim.time(),
+        prettyprint_usize(finished),
+        prettyprint_usize(unfinished)
+    );
+}
+
+fn compare_seeds(num_seeds: u8, end_time: Option<Time>) {
+    let mut sim_flags = SimFlags::synthetic_test("montlake", "compare_seeds");
+    sim_flags.opts.alerts = AlertHandler::Silence;
+    let mut timer = Timer::new("compare RNG seeds");
+    let (map, _, _) = sim_flags.load(&mut timer);
+    let scenario: Scenario = abstutil::read_binary(
+        abstutil::path_scenario(map.get_name(), "weekday"),
+        &mut timer,
+    );
+
+    // Per seed: (finished trips, aborted trips, mean trip duration in seconds)
+    let mut results: Vec<(usize, usize, f64)> = Vec::new();
+    for seed in 0..num_seeds {
+        timer.start(format!("run with seed {}", seed));
+        let mut sim = Sim::new(&map, sim_flags.opts.clone(), &mut timer);
+        let mut rng = XorShiftRng::from_seed([seed; 16]);
+        scenario.instantiate(&mut sim, &map, &mut rng, &mut timer);
+        if let Some(t) = end_time {
+            sim.run_until_done_or_time(&map, |_, _| {}, t);
+        } else {
+            sim.run_until_done(&map, |_, _| {}, None);
+        }
+        timer.stop(format!("run with seed {}", seed));
+
+        let mut finished = 0;
+        let mut aborted = 0;
+        let mut total_secs = 0.0;
+        for (_, _, maybe_mode, dt) in &sim.get_analytics().finished_trips {
+            if maybe_mode.is_some() {
+                finished += 1;
+                total_secs += dt.inner_seconds();
+            } else {
+                aborted += 1;
             }
-        },
-        None,
+        }
+        let mean = if finished == 0 {
+            0.0
+        } else {
+            total_secs / (finished as f64)
+        };
+        println!(
+            "Seed {}: {} finished trips, {} aborted, mean duration {}",
+            seed,
+            prettyprint_usize(finished),
+            prettyprint_usize(aborted),
+            Duration::seconds(mean)
+        );
+        results.push((finished, aborted, mean));
+    }
+    timer.done();
+
+    println!();
+    describe_spread(
+        "finished trips",
+        results.iter().map(|(x, _, _)| *x as f64).collect(),
     );
+    describe_spread(
+        "aborted trips",
+        results.iter().map(|(_, x, _)| *x as f64).collect(),
+    );
+    describe_spread(
+        "mean trip duration (seconds)",
+        results.iter().map(|(_, _, x)| *x).collect(),
+    );
+}
+
+// Roughly the middle of the speeds Scenario picks
+const BIKE_SPEED: Speed = Speed::const_meters_per_second(4.0);
+const WALK_SPEED: Speed = Speed::const_meters_per_second(1.1);
+
+// Only trips between two buildings are considered; it's not clear how somebody off-map would bike
+// or walk in. Times assume empty roads and ignore parking, so driving is an optimistic estimate.
+// TODO Transit, but that depends on waiting for the bus.
+fn estimate_mode_shift() {
+    let sim_flags = SimFlags::synthetic_test("montlake", "mode_shift");
+    let mut timer = Timer::new("estimate mode shift");
+    let (map, _, _) = sim_flags.load(&mut timer);
+    let scenario: Scenario = abstutil::read_binary(
+        abstutil::path_scenario(map.get_name(), "weekday"),
+        &mut timer,
+    );
+
+    let mut driving_trips = 0;
+    let mut faster_by_bike = 0;
+    let mut faster_by_walking = 0;
+    let mut bike_time_saved = Duration::ZERO;
+    timer.start_iter("estimate trips", scenario.people.len());
+    for person in &scenario.people {
+        timer.next();
+        for trip in &person.trips {
+            let (b1, b2) = match trip.trip {
+                SpawnTrip::UsingParkedCar(b1, DrivingGoal::ParkNear(b2)) if b1 != b2 => (b1, b2),
+                _ => {
+                    continue;
+                }
+            };
+            let drive = match estimate_time(
+                &map,
+                DrivingGoal::ParkNear(b1).goal_pos(PathConstraints::Car, &map),
+                DrivingGoal::ParkNear(b2).goal_pos(PathConstraints::Car, &map),
+                PathConstraints::Car,
+                None,
+            ) {
+                Some(dt) => dt,
+                None => {
+                    continue;
+                }
+            };
+            driving_trips += 1;
+
+            if let Some(bike) = estimate_time(
+                &map,
+                DrivingGoal::ParkNear(b1).goal_pos(PathConstraints::Bike, &map),
+                DrivingGoal::ParkNear(b2).goal_pos(PathConstraints::Bike, &map),
+                PathConstraints::Bike,
+                Some(BIKE_SPEED),
+            ) {
+                if bike < drive {
+                    faster_by_bike += 1;
+                    bike_time_saved += drive - bike;
+                }
+            }
+            if let Some(walk) = estimate_time(
+                &map,
+                map.get_b(b1).front_path.sidewalk,
+                map.get_b(b2).front_path.sidewalk,
+                PathConstraints::Pedestrian,
+                Some(WALK_SPEED),
+            ) {
+                if walk < drive {
+                    faster_by_walking += 1;
+                }
+            }
+        }
+    }
     timer.done();
-    println!("Done at {}", sim.time());
+
+    println!();
+    println!(
+        "{} driving trips between buildings",
+        prettyprint_usize(driving_trips)
+    );
+    println!(
+        "{} could be faster by bike, saving {} total",
+        prettyprint_usize(faster_by_bike),
+        bike_time_saved
+    );
+    println!(
+        "{} could be faster on foot",
+        prettyprint_usize(faster_by_walking)
+    );
+}
+
+// Free-flow travel time along the best path, capped by the speed limit of every road
+fn estimate_time(
+    map: &Map,
+    start: Position,
+    end: Position,
+    constraints: PathConstraints,
+    max_speed: Option<Speed>,
+) -> Option<Duration> {
+    let path = map.pathfind(PathRequest {
+        start,
+        end,
+        constraints,
+    })?;
+    let mut total = Duration::ZERO;
+    for step in path.get_steps() {
+        let on = step.as_traversable();
+        let speed = if let Some(s) = max_speed {
+            on.speed_limit(map).min(s)
+        } else {
+            on.speed_limit(map)
+        };
+        total += on.length(map) / speed;
+    }
+    Some(total)
+}
+
+fn describe_spread(label: &str, values: Vec<f64>) {
+    if values.is_empty() {
+        return;
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    let min = values.iter().cloned().fold(std::f64::MAX, f64::min);
+    let max = values.iter().cloned().fold(std::f64::MIN, f64::max);
+    println!(
+        "{}: mean {:.1}, std dev {:.1}, min {:.1}, max {:.1}",
+        label,
+        mean,
+        variance.sqrt(),
+        min,
+        max
+    );
 }
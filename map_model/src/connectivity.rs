@@ -29,7 +29,10 @@ pub fn find_scc(map: &Map, constraints: PathConstraints) -> (HashSet<LaneID>, Ha
         .all_lanes()
         .iter()
         .filter_map(|l| {
-            if constraints.can_use(l, map) && !largest_group.contains(&l.id) {
+            if constraints.can_use(l, map)
+                && !largest_group.contains(&l.id)
+                && !map.is_lane_removed(l.id)
+            {
                 Some(l.id)
             } else {
                 None
@@ -15,6 +15,8 @@ pub struct MapEdits {
     // Derived from commands, kept up to date by update_derived
     pub original_lts: BTreeMap<LaneID, LaneType>,
     pub reversed_lanes: BTreeSet<LaneID>,
+    pub added_lanes: BTreeSet<LaneID>,
+    pub removed_lanes: BTreeSet<LaneID>,
     pub original_intersections: BTreeMap<IntersectionID, EditIntersection>,
     pub changed_speed_limits: BTreeSet<RoadID>,
 
@@ -53,6 +55,23 @@ pub enum EditCmd {
         new: EditIntersection,
         old: EditIntersection,
     },
+    // Lanes can't be deleted from the map, so a removed lane keeps its ID and just doesn't belong
+    // to the road anymore. An added lane takes the next unused ID. idx is the position on that
+    // side of the road, counting from the center.
+    AddLane {
+        l: LaneID,
+        r: RoadID,
+        fwd: bool,
+        idx: usize,
+        lt: LaneType,
+    },
+    RemoveLane {
+        l: LaneID,
+        r: RoadID,
+        fwd: bool,
+        idx: usize,
+        lt: LaneType,
+    },
 }
 
 impl EditCmd {
@@ -66,6 +85,10 @@ impl EditCmd {
                 EditIntersection::TrafficSignal(_) => format!("traffic signal #{}", i.0),
                 EditIntersection::Closed => format!("close {}", i),
             },
+            EditCmd::AddLane { lt, r, .. } => format!("add {} on #{}", lt.short_name(), r.0),
+            EditCmd::RemoveLane { lt, r, .. } => {
+                format!("remove {} on #{}", lt.short_name(), r.0)
+            }
         }
     }
 }
@@ -88,6 +111,8 @@ impl MapEdits {
 
             original_lts: BTreeMap::new(),
             reversed_lanes: BTreeSet::new(),
+            added_lanes: BTreeSet::new(),
+            removed_lanes: BTreeSet::new(),
             original_intersections: BTreeMap::new(),
             changed_speed_limits: BTreeSet::new(),
         }
@@ -116,6 +141,8 @@ impl MapEdits {
     pub(crate) fn update_derived(&mut self, map: &Map) {
         let mut orig_lts = BTreeMap::new();
         let mut reversed_lanes = BTreeSet::new();
+        let mut added_lanes = BTreeSet::new();
+        let mut removed_lanes = BTreeSet::new();
         let mut orig_intersections: BTreeMap<IntersectionID, EditIntersection> = BTreeMap::new();
         let mut changed_speed_limits = BTreeSet::new();
 
@@ -141,6 +168,16 @@ impl MapEdits {
                         orig_intersections.insert(*i, old.clone());
                     }
                 }
+                EditCmd::AddLane { l, .. } => {
+                    if !removed_lanes.remove(l) {
+                        added_lanes.insert(*l);
+                    }
+                }
+                EditCmd::RemoveLane { l, .. } => {
+                    if !added_lanes.remove(l) {
+                        removed_lanes.insert(*l);
+                    }
+                }
             }
         }
 
@@ -154,10 +191,31 @@ impl MapEdits {
 
         self.original_lts = orig_lts;
         self.reversed_lanes = reversed_lanes;
+        self.added_lanes = added_lanes;
+        self.removed_lanes = removed_lanes;
         self.original_intersections = orig_intersections;
         self.changed_speed_limits = changed_speed_limits;
     }
 
+    // Compares against some baseline, like a community proposal the player started from. Returns
+    // the commands added beyond the baseline, and the baseline's commands that were undone.
+    pub fn diff_against<'a>(
+        &'a self,
+        baseline: &'a MapEdits,
+    ) -> (Vec<&'a EditCmd>, Vec<&'a EditCmd>) {
+        let added = self
+            .commands
+            .iter()
+            .filter(|cmd| !baseline.commands.contains(cmd))
+            .collect();
+        let removed = baseline
+            .commands
+            .iter()
+            .filter(|cmd| !self.commands.contains(cmd))
+            .collect();
+        (added, removed)
+    }
+
     // Assumes update_derived has been called.
     pub(crate) fn compress(&mut self, map: &Map) {
         for (l, orig_lt) in &self.original_lts {
@@ -267,6 +325,19 @@ enum PermanentEditCmd {
         new: PermanentEditIntersection,
         old: PermanentEditIntersection,
     },
+    // The lane IDs are assigned again when loading.
+    AddLane {
+        r: OriginalRoad,
+        fwd: bool,
+        idx: usize,
+        lt: LaneType,
+    },
+    RemoveLane {
+        r: OriginalRoad,
+        fwd: bool,
+        idx: usize,
+        lt: LaneType,
+    },
 }
 
 impl PermanentMapEdits {
@@ -305,12 +376,45 @@ impl PermanentMapEdits {
                             old: old.to_permanent(map),
                         }
                     }
+                    EditCmd::AddLane {
+                        r, fwd, idx, lt, ..
+                    } => PermanentEditCmd::AddLane {
+                        r: map.get_r(*r).orig_id,
+                        fwd: *fwd,
+                        idx: *idx,
+                        lt: *lt,
+                    },
+                    EditCmd::RemoveLane {
+                        r, fwd, idx, lt, ..
+                    } => PermanentEditCmd::RemoveLane {
+                        r: map.get_r(*r).orig_id,
+                        fwd: *fwd,
+                        idx: *idx,
+                        lt: *lt,
+                    },
                 })
                 .collect(),
         }
     }
 
     pub fn from_permanent(perma: PermanentMapEdits, map: &Map) -> Result<MapEdits, String> {
+        // Added lanes get new IDs, and other lanes are described by their position after all of
+        // the edits, so first work out the lanes of every road that gains or loses some.
+        let mut layouts = LaneLayouts::new(map);
+        let mut changed_lanes = Vec::new();
+        for cmd in &perma.commands {
+            match cmd {
+                PermanentEditCmd::AddLane { r, fwd, idx, .. } => {
+                    changed_lanes.push(layouts.add(map, r, *fwd, *idx)?);
+                }
+                PermanentEditCmd::RemoveLane { r, fwd, idx, .. } => {
+                    changed_lanes.push(layouts.remove(map, r, *fwd, *idx)?);
+                }
+                _ => {}
+            }
+        }
+        let mut changed_lanes = changed_lanes.into_iter();
+
         let mut edits = MapEdits {
             edits_name: perma.edits_name,
             proposal_description: perma.proposal_description,
@@ -320,14 +424,14 @@ impl PermanentMapEdits {
                 .into_iter()
                 .map(|cmd| match cmd {
                     PermanentEditCmd::ChangeLaneType { id, lt, orig_lt } => {
-                        let l = id.clone().from_permanent(map)?;
-                        if map.get_l(l).lane_type != orig_lt {
+                        let l = layouts.lookup(id.clone(), map)?;
+                        if !layouts.added.contains(&l) && map.get_l(l).lane_type != orig_lt {
                             return Err(format!("basemap lanetype of {:?} has changed", id));
                         }
                         Ok(EditCmd::ChangeLaneType { id: l, lt, orig_lt })
                     }
                     PermanentEditCmd::ReverseLane { l, dst_i } => {
-                        let l = l.from_permanent(map)?;
+                        let l = layouts.lookup(l, map)?;
                         let dst_i = map.find_i_by_osm_id(dst_i.osm_node_id)?;
                         Ok(EditCmd::ReverseLane { l, dst_i })
                     }
@@ -350,11 +454,27 @@ impl PermanentMapEdits {
                                 .ok_or(format!("old ChangeIntersection of {} invalid", i))?,
                         })
                     }
+                    PermanentEditCmd::AddLane { r, fwd, idx, lt } => Ok(EditCmd::AddLane {
+                        l: changed_lanes.next().unwrap(),
+                        r: find_r(map, &r)?,
+                        fwd,
+                        idx,
+                        lt,
+                    }),
+                    PermanentEditCmd::RemoveLane { r, fwd, idx, lt } => Ok(EditCmd::RemoveLane {
+                        l: changed_lanes.next().unwrap(),
+                        r: find_r(map, &r)?,
+                        fwd,
+                        idx,
+                        lt,
+                    }),
                 })
                 .collect::<Result<Vec<EditCmd>, String>>()?,
 
             original_lts: BTreeMap::new(),
             reversed_lanes: BTreeSet::new(),
+            added_lanes: BTreeSet::new(),
+            removed_lanes: BTreeSet::new(),
             original_intersections: BTreeMap::new(),
             changed_speed_limits: BTreeSet::new(),
         };
@@ -442,3 +562,100 @@ impl OriginalLane {
         }
     }
 }
+
+fn find_r(map: &Map, r: &OriginalRoad) -> Result<RoadID, String> {
+    map.find_r_by_osm_id(r.osm_way_id, (r.i1.osm_node_id, r.i2.osm_node_id))
+}
+
+// The lanes of roads that AddLane and RemoveLane touch, while loading edits
+struct LaneLayouts {
+    next_id: usize,
+    added: BTreeSet<LaneID>,
+    roads: BTreeMap<RoadID, (Vec<LaneID>, Vec<LaneID>)>,
+}
+
+impl LaneLayouts {
+    fn new(map: &Map) -> LaneLayouts {
+        let mut layouts = LaneLayouts {
+            next_id: map.all_lanes().len(),
+            added: BTreeSet::new(),
+            roads: BTreeMap::new(),
+        };
+        // The new edits replace the current ones, so start from the lanes before those.
+        for cmd in map.get_edits().commands.iter().rev() {
+            match cmd {
+                EditCmd::AddLane { l, r, fwd, .. } => {
+                    layouts.side(map, *r, *fwd).retain(|x| x != l);
+                }
+                EditCmd::RemoveLane { l, r, fwd, idx, .. } => {
+                    layouts.side(map, *r, *fwd).insert(*idx, *l);
+                }
+                _ => {}
+            }
+        }
+        layouts
+    }
+
+    fn side(&mut self, map: &Map, r: RoadID, fwd: bool) -> &mut Vec<LaneID> {
+        let (fwds, backs) = self.roads.entry(r).or_insert_with(|| {
+            let road = map.get_r(r);
+            (
+                road.children_forwards.iter().map(|(l, _)| *l).collect(),
+                road.children_backwards.iter().map(|(l, _)| *l).collect(),
+            )
+        });
+        if fwd {
+            fwds
+        } else {
+            backs
+        }
+    }
+
+    fn add(
+        &mut self,
+        map: &Map,
+        r: &OriginalRoad,
+        fwd: bool,
+        idx: usize,
+    ) -> Result<LaneID, String> {
+        let l = LaneID(self.next_id);
+        let side = self.side(map, find_r(map, r)?, fwd);
+        if idx > side.len() {
+            return Err(format!("can't add lane {} to {:?}", idx, r));
+        }
+        side.insert(idx, l);
+        self.next_id += 1;
+        self.added.insert(l);
+        Ok(l)
+    }
+
+    fn remove(
+        &mut self,
+        map: &Map,
+        r: &OriginalRoad,
+        fwd: bool,
+        idx: usize,
+    ) -> Result<LaneID, String> {
+        let side = self.side(map, find_r(map, r)?, fwd);
+        if idx >= side.len() {
+            return Err(format!("can't remove lane {} from {:?}", idx, r));
+        }
+        Ok(side.remove(idx))
+    }
+
+    fn lookup(&self, orig: OriginalLane, map: &Map) -> Result<LaneID, String> {
+        match self.roads.get(&find_r(map, &orig.parent)?) {
+            Some((fwds, backs)) => {
+                if fwds.len() != orig.num_fwd || backs.len() != orig.num_back {
+                    return Err(format!("number of lanes has changed in {:?}", orig));
+                }
+                Ok(if orig.fwd {
+                    fwds[orig.idx]
+                } else {
+                    backs[orig.idx]
+                })
+            }
+            None => orig.from_permanent(map),
+        }
+    }
+}
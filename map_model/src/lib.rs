@@ -34,7 +34,7 @@ pub use crate::pathfind::uber_turns::{IntersectionCluster, UberTurn, UberTurnGro
 pub use crate::pathfind::{Path, PathConstraints, PathRequest, PathStep};
 pub use crate::road::{DirectedRoadID, Road, RoadID};
 pub use crate::stop_signs::{ControlStopSign, RoadWithStopSign};
-pub use crate::traffic_signals::{ControlTrafficSignal, Phase};
+pub use crate::traffic_signals::{describe_group, ControlTrafficSignal, Phase};
 pub use crate::traversable::{Position, Traversable};
 pub use crate::turn::{Turn, TurnGroup, TurnGroupID, TurnID, TurnPriority, TurnType};
 use abstutil::Cloneable;
@@ -44,6 +44,7 @@ use geom::Distance;
 pub const NORMAL_LANE_THICKNESS: Distance = Distance::const_meters(2.5);
 pub const SIDEWALK_THICKNESS: Distance = Distance::const_meters(1.5);
 
+impl Cloneable for BuildingID {}
 impl Cloneable for BusRouteID {}
 impl Cloneable for ControlTrafficSignal {}
 impl Cloneable for IntersectionID {}
@@ -50,6 +50,10 @@ pub struct Map {
     name: String,
     #[serde(skip_serializing, skip_deserializing)]
     edits: MapEdits,
+    // Adding or removing lanes rebuilds the polygons of the intersections at either end of the
+    // road. Remember the originals, to restore once those edits are undone.
+    #[serde(skip_serializing, skip_deserializing)]
+    orig_intersection_polygons: BTreeMap<IntersectionID, Polygon>,
 }
 
 impl Map {
@@ -142,6 +146,7 @@ impl Map {
             city_name: "blank city".to_string(),
             name: "blank".to_string(),
             edits: MapEdits::new(),
+            orig_intersection_polygons: BTreeMap::new(),
         }
     }
 
@@ -477,6 +482,12 @@ impl Map {
         roads.into_iter().collect()
     }
 
+    // Edits can remove lanes. LaneIDs index into the list of lanes, so a removed lane keeps its
+    // slot, but no road, intersection, or turn refers to it anymore.
+    pub fn is_lane_removed(&self, id: LaneID) -> bool {
+        !self.get_parent(id).all_lanes().contains(&id)
+    }
+
     pub fn get_parent(&self, id: LaneID) -> &Road {
         let l = self.get_l(id);
         self.get_r(l.parent)
@@ -798,8 +809,11 @@ impl Map {
 
     pub fn save_edits(&mut self) {
         let mut edits = self.edits.clone();
-        edits.commands.clear();
-        edits.compress(self);
+        // IDs of added lanes depend on the order of commands, so keep the whole history then.
+        if edits.added_lanes.is_empty() && edits.removed_lanes.is_empty() {
+            edits.commands.clear();
+            edits.compress(self);
+        }
         self.edits = edits;
 
         self.edits.save(self);
@@ -860,6 +874,25 @@ impl Map {
             }
         }
 
+        // Intersections that no longer have roads with lanes added or removed get their original
+        // shape back.
+        let mut touched = BTreeSet::new();
+        for cmd in &new_edits.commands {
+            if let EditCmd::AddLane { r, .. } | EditCmd::RemoveLane { r, .. } = cmd {
+                touched.insert(self.roads[r.0].src_i);
+                touched.insert(self.roads[r.0].dst_i);
+            }
+        }
+        for (i, polygon) in std::mem::replace(&mut self.orig_intersection_polygons, BTreeMap::new())
+        {
+            if touched.contains(&i) {
+                self.orig_intersection_polygons.insert(i, polygon);
+            } else {
+                self.intersections[i.0].polygon = polygon;
+                effects.changed_intersections.insert(i);
+            }
+        }
+
         new_edits.update_derived(self);
         self.edits = new_edits;
         self.pathfinder_dirty = true;
@@ -929,6 +962,7 @@ fn make_half_map(
         city_name: raw.city_name.clone(),
         name: raw.name.clone(),
         edits: MapEdits::new(),
+        orig_intersection_polygons: BTreeMap::new(),
     };
 
     let road_id_mapping: BTreeMap<OriginalRoad, RoadID> = initial_map
@@ -1288,6 +1322,98 @@ impl EditCmd {
                 }
                 true
             }
+            EditCmd::AddLane { l, r, fwd, idx, lt } => {
+                let (l, r, fwd, lt) = (*l, *r, *fwd, *lt);
+                if l.0 < map.lanes.len() && map.roads[r.0].all_lanes().contains(&l) {
+                    return false;
+                }
+
+                // The lanes on the other side don't move, so find the center before anything
+                // changes.
+                let center = current_center(&map.roads[r.0], map);
+                let road = &map.roads[r.0];
+                let (src_i, dst_i) = if fwd {
+                    (road.src_i, road.dst_i)
+                } else {
+                    (road.dst_i, road.src_i)
+                };
+                let width = if lt == LaneType::Sidewalk {
+                    SIDEWALK_THICKNESS
+                } else {
+                    NORMAL_LANE_THICKNESS
+                };
+                // The geometry gets filled out below.
+                if l.0 == map.lanes.len() {
+                    map.lanes.push(Lane {
+                        id: l,
+                        parent: r,
+                        lane_type: lt,
+                        lane_center_pts: center.clone(),
+                        width,
+                        src_i,
+                        dst_i,
+                        building_paths: Vec::new(),
+                        bus_stops: Vec::new(),
+                        parking_blackhole: None,
+                    });
+                } else {
+                    let lane = &mut map.lanes[l.0];
+                    lane.parent = r;
+                    lane.lane_type = lt;
+                    lane.width = width;
+                    lane.src_i = src_i;
+                    lane.dst_i = dst_i;
+                }
+                map.intersections[src_i.0].outgoing_lanes.push(l);
+                map.intersections[dst_i.0].incoming_lanes.push(l);
+
+                let road = &mut map.roads[r.0];
+                if fwd {
+                    road.children_forwards.insert(*idx, (l, lt));
+                } else {
+                    road.children_backwards.insert(*idx, (l, lt));
+                }
+                shift_lanes(map, r, fwd, center, timer);
+                fix_road_geometry(map, r);
+
+                effects.changed_roads.insert(r);
+                effects.changed_intersections.insert(src_i);
+                effects.changed_intersections.insert(dst_i);
+                recalculate_turns(src_i, map, effects, timer);
+                recalculate_turns(dst_i, map, effects, timer);
+                true
+            }
+            EditCmd::RemoveLane { l, r, fwd, .. } => {
+                let (l, r, fwd) = (*l, *r, *fwd);
+                if !map.roads[r.0].all_lanes().contains(&l) {
+                    return false;
+                }
+
+                let center = current_center(&map.roads[r.0], map);
+                let (src_i, dst_i) = (map.lanes[l.0].src_i, map.lanes[l.0].dst_i);
+                map.intersections[src_i.0]
+                    .outgoing_lanes
+                    .retain(|x| *x != l);
+                map.intersections[dst_i.0]
+                    .incoming_lanes
+                    .retain(|x| *x != l);
+
+                let road = &mut map.roads[r.0];
+                if fwd {
+                    road.children_forwards.retain(|(x, _)| *x != l);
+                } else {
+                    road.children_backwards.retain(|(x, _)| *x != l);
+                }
+                shift_lanes(map, r, fwd, center, timer);
+                fix_road_geometry(map, r);
+
+                effects.changed_roads.insert(r);
+                effects.changed_intersections.insert(src_i);
+                effects.changed_intersections.insert(dst_i);
+                recalculate_turns(src_i, map, effects, timer);
+                recalculate_turns(dst_i, map, effects, timer);
+                true
+            }
         }
     }
 
@@ -1332,8 +1458,152 @@ impl EditCmd {
                 new: old.clone(),
             }
             .apply(effects, map, timer),
+            EditCmd::AddLane { l, r, fwd, idx, lt } => EditCmd::RemoveLane {
+                l: *l,
+                r: *r,
+                fwd: *fwd,
+                idx: *idx,
+                lt: *lt,
+            }
+            .apply(effects, map, timer),
+            EditCmd::RemoveLane { l, r, fwd, idx, lt } => EditCmd::AddLane {
+                l: *l,
+                r: *r,
+                fwd: *fwd,
+                idx: *idx,
+                lt: *lt,
+            }
+            .apply(effects, map, timer),
+        }
+    }
+}
+
+// The line between the two directions of a road, oriented like the road. Unlike center_pts, this
+// accounts for contraflow lanes.
+fn current_center(road: &Road, map: &Map) -> PolyLine {
+    if road.children_forwards.is_empty() {
+        let lane = map.get_l(road.children_backwards[0].0);
+        map.left_shift(lane.lane_center_pts.clone(), lane.width / 2.0)
+            .unwrap()
+            .reversed()
+    } else {
+        road.get_current_center(map)
+    }
+}
+
+// Lay out the lanes on one side of a road again, going outwards from the center.
+fn shift_lanes(map: &mut Map, r: RoadID, fwd: bool, center: PolyLine, timer: &mut Timer) {
+    let (lanes, base) = if fwd {
+        (map.roads[r.0].children_forwards.clone(), center)
+    } else {
+        (map.roads[r.0].children_backwards.clone(), center.reversed())
+    };
+    let mut offset = Distance::ZERO;
+    for (l, _) in lanes {
+        let width = map.lanes[l.0].width;
+        map.lanes[l.0].lane_center_pts = map
+            .right_shift(base.clone(), offset + width / 2.0)
+            .with_context(timer, format!("shift for {}", l));
+        offset += width;
+    }
+}
+
+// Changing the number of lanes changes the road's width, so the intersections at either end and
+// the driveways leading to the road have to follow.
+fn fix_road_geometry(map: &mut Map, r: RoadID) {
+    for i in vec![map.roads[r.0].src_i, map.roads[r.0].dst_i] {
+        rebuild_intersection_polygon(map, i);
+    }
+
+    let lanes: BTreeSet<LaneID> = map.roads[r.0].all_lanes().into_iter().collect();
+    let pt = |map: &Map, pos: &Position| -> Option<Pt2D> {
+        map.get_l(pos.lane())
+            .safe_dist_along(pos.dist_along())
+            .map(|(pt, _)| pt)
+    };
+
+    let mut bldgs = Vec::new();
+    for b in &map.buildings {
+        if !lanes.contains(&b.front_path.sidewalk.lane()) {
+            continue;
+        }
+        let start = b.front_path.line.pt1();
+        if let Some(sidewalk_pt) = pt(map, &b.front_path.sidewalk) {
+            let driveway = b.parking.as_ref().and_then(|p| {
+                pt(map, &p.driving_pos).and_then(|driving_pt| {
+                    PolyLine::maybe_new(vec![start, sidewalk_pt, driving_pt])
+                })
+            });
+            if let Some(line) = Line::maybe_new(start, sidewalk_pt) {
+                bldgs.push((b.id, line, driveway));
+            }
+        }
+    }
+    for (b, line, driveway) in bldgs {
+        let b = &mut map.buildings[b.0];
+        b.front_path.line = line;
+        if let (Some(p), Some(driveway)) = (b.parking.as_mut(), driveway) {
+            p.driveway_line = driveway;
+        }
+    }
+
+    let mut lots = Vec::new();
+    for pl in &map.parking_lots {
+        if !lanes.contains(&pl.sidewalk_pos.lane()) {
+            continue;
+        }
+        let start = pl.sidewalk_line.pt1();
+        if let (Some(sidewalk_pt), Some(driving_pt)) =
+            (pt(map, &pl.sidewalk_pos), pt(map, &pl.driving_pos))
+        {
+            if let (Some(sidewalk_line), Some(driveway_line)) = (
+                Line::maybe_new(start, sidewalk_pt),
+                PolyLine::maybe_new(vec![start, sidewalk_pt, driving_pt]),
+            ) {
+                lots.push((pl.id, sidewalk_line, driveway_line));
+            }
         }
     }
+    for (pl, sidewalk_line, driveway_line) in lots {
+        map.parking_lots[pl.0].sidewalk_line = sidewalk_line;
+        map.parking_lots[pl.0].driveway_line = driveway_line;
+    }
+}
+
+// Cover the ends of every lane meeting at the intersection. This is cruder than the geometry made
+// from the raw map, but follows roads that got wider or narrower.
+fn rebuild_intersection_polygon(map: &mut Map, id: IntersectionID) {
+    let i = &map.intersections[id.0];
+    let mut pts = Vec::new();
+    for l in i.incoming_lanes.iter().chain(i.outgoing_lanes.iter()) {
+        let lane = &map.lanes[l.0];
+        let (pt, angle) = if lane.dst_i == id {
+            (
+                lane.lane_center_pts.last_pt(),
+                lane.lane_center_pts.last_line().angle(),
+            )
+        } else {
+            (
+                lane.lane_center_pts.first_pt(),
+                lane.lane_center_pts.first_line().angle(),
+            )
+        };
+        pts.push(pt.project_away(lane.width / 2.0, angle.rotate_degs(90.0)));
+        pts.push(pt.project_away(lane.width / 2.0, angle.rotate_degs(-90.0)));
+    }
+    pts.sort_by_key(|pt| pt.to_hashable());
+    pts.dedup();
+    // Dead-ends and borders only have the lanes of one road, so there's nothing to cover.
+    if i.roads.len() < 2 || pts.len() < 3 {
+        return;
+    }
+
+    let polygon = Polygon::convex_hull(vec![Polygon::new(&pts)]);
+    if !map.orig_intersection_polygons.contains_key(&id) {
+        map.orig_intersection_polygons
+            .insert(id, map.intersections[id.0].polygon.clone());
+    }
+    map.intersections[id.0].polygon = polygon;
 }
 
 // This clobbers previously set traffic signal overrides.
@@ -114,8 +114,22 @@ impl VehiclePathfinder {
     }
 
     pub fn apply_edits(&mut self, map: &Map) {
-        // The NodeMap is just all lanes and uber-turns -- it won't change. So we can also reuse
-        // the node ordering.
+        // Lanes added by edits aren't in the graph, and uber-turns might go through removed
+        // lanes, so start over then. Added lanes always have the highest IDs.
+        let last_lane = LaneID(map.all_lanes().len() - 1);
+        if !self.nodes.contains(Node::Lane(last_lane))
+            || self.uber_turns.iter().any(|ut| {
+                ut.path
+                    .iter()
+                    .any(|t| map.is_lane_removed(t.src) || map.is_lane_removed(t.dst))
+            })
+        {
+            *self = VehiclePathfinder::new(map, self.constraints, None);
+            return;
+        }
+
+        // Otherwise the NodeMap is just all lanes and uber-turns -- it won't change. So we can
+        // also reuse the node ordering.
         // TODO Make sure the result of this is deterministic and equivalent to computing from
         // scratch.
         let input_graph = make_input_graph(map, &self.nodes, &self.uber_turns, self.constraints);
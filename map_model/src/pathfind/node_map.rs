@@ -37,6 +37,10 @@ impl<T: Copy + Ord + Debug + Serialize> NodeMap<T> {
         }
     }
 
+    pub fn contains(&self, node: T) -> bool {
+        self.node_to_id.contains_key(&node)
+    }
+
     pub fn translate(&self, path: &ShortestPath) -> Vec<T> {
         path.get_nodes()
             .iter()
@@ -33,7 +33,7 @@ impl SidewalkPathfinder {
         let mut nodes = NodeMap::new();
         // We're assuming that to start with, no sidewalks are closed for construction!
         for l in map.all_lanes() {
-            if l.is_sidewalk() {
+            if l.is_sidewalk() && !map.is_lane_removed(l.id) {
                 nodes.get_or_insert(Node::SidewalkEndpoint(l.id, true));
                 nodes.get_or_insert(Node::SidewalkEndpoint(l.id, false));
             }
@@ -55,8 +55,18 @@ impl SidewalkPathfinder {
     }
 
     pub fn apply_edits(&mut self, map: &Map, bus_graph: &VehiclePathfinder) {
-        // The NodeMap is all sidewalks and bus stops -- it won't change. So we can also reuse the
-        // node ordering.
+        // Sidewalks added by edits aren't in the graph, so start over then.
+        if map.all_lanes().iter().any(|l| {
+            l.is_sidewalk()
+                && !map.is_lane_removed(l.id)
+                && !self.nodes.contains(Node::SidewalkEndpoint(l.id, true))
+        }) {
+            *self = SidewalkPathfinder::new(map, self.use_transit, bus_graph);
+            return;
+        }
+
+        // Otherwise the NodeMap is all sidewalks and bus stops -- it won't change. So we can also
+        // reuse the node ordering.
         let input_graph = make_input_graph(map, &self.nodes, self.use_transit, bus_graph);
         let node_ordering = self.graph.get_node_ordering();
         self.graph = fast_paths::prepare_with_order(&input_graph, &node_ordering).unwrap();
@@ -205,7 +215,7 @@ fn make_input_graph(
     let mut input_graph = InputGraph::new();
 
     for l in map.all_lanes() {
-        if l.is_sidewalk() {
+        if l.is_sidewalk() && !map.is_lane_removed(l.id) {
             let cost = to_s(l.length());
             let n1 = nodes.get(Node::SidewalkEndpoint(l.id, true));
             let n2 = nodes.get(Node::SidewalkEndpoint(l.id, false));
@@ -1,9 +1,10 @@
 use crate::make::traffic_signals::{brute_force, get_possible_policies};
 use crate::{
-    DirectedRoadID, IntersectionID, Map, TurnGroup, TurnGroupID, TurnID, TurnPriority, TurnType,
+    DirectedRoadID, IntersectionID, Map, RoadID, TurnGroup, TurnGroupID, TurnID, TurnPriority,
+    TurnType,
 };
 use abstutil::{deserialize_btreemap, retain_btreeset, serialize_btreemap, Timer};
-use geom::{Duration, Time};
+use geom::{Angle, Duration, Time};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -158,6 +159,139 @@ impl ControlTrafficSignal {
         }
         self != &orig
     }
+
+    // Copies this timing plan onto another intersection with the same number of roads, matching
+    // up roads by their angle relative to each other, so the two intersections can face different
+    // directions. Turn groups that don't exist at the destination are dropped, and both those and
+    // the destination's groups that the plan doesn't cover are described in the returned list of
+    // problems.
+    pub fn transfer(
+        &self,
+        map: &Map,
+        to: IntersectionID,
+    ) -> Result<(ControlTrafficSignal, Vec<String>), String> {
+        let src_roads = road_angles(map, self.id);
+        let dst_roads = road_angles(map, to);
+        if src_roads.len() != dst_roads.len() {
+            return Err(format!(
+                "The timing plan is for an intersection with {} roads, but {} has {}",
+                src_roads.len(),
+                to,
+                dst_roads.len()
+            ));
+        }
+        // Both lists are sorted by angle, so try every way of lining them up, and keep the one
+        // where the worst road is the least out of place.
+        let n = src_roads.len();
+        let (offset, worst) = (0..n)
+            .map(|offset| {
+                let worst = (0..n)
+                    .map(|idx| {
+                        let src = src_roads[idx]
+                            .1
+                            .rotate_degs(-src_roads[0].1.normalized_degrees());
+                        let dst = dst_roads[(idx + offset) % n]
+                            .1
+                            .rotate_degs(-dst_roads[offset].1.normalized_degrees());
+                        let diff = src.shortest_rotation_towards(dst).normalized_degrees();
+                        diff.min(360.0 - diff)
+                    })
+                    .fold(0.0, f64::max);
+                (offset, worst)
+            })
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .unwrap();
+        if worst > MAX_TRANSFER_ANGLE_DEGREES {
+            return Err(format!(
+                "The roads at {} are arranged too differently from the ones this timing plan is \
+                 for",
+                to
+            ));
+        }
+        let translate = |dr: DirectedRoadID| -> DirectedRoadID {
+            let src_r = map.get_r(dr.id);
+            let toward =
+                (dr.forwards && src_r.dst_i == self.id) || (!dr.forwards && src_r.src_i == self.id);
+            let idx = src_roads.iter().position(|(r, _)| *r == dr.id).unwrap();
+            let dst_r = map.get_r(dst_roads[(idx + offset) % n].0);
+            DirectedRoadID {
+                id: dst_r.id,
+                forwards: if toward {
+                    dst_r.dst_i == to
+                } else {
+                    dst_r.src_i == to
+                },
+            }
+        };
+
+        let turn_groups = TurnGroup::for_i(to, map);
+        let mut problems = Vec::new();
+        let mut covered = BTreeSet::new();
+        let mut phases = Vec::new();
+        for (idx, phase) in self.phases.iter().enumerate() {
+            let mut new_phase = Phase::new();
+            new_phase.duration = phase.duration;
+            for (groups, protected) in vec![
+                (&phase.protected_groups, true),
+                (&phase.yield_groups, false),
+            ] {
+                for g in groups {
+                    let id = TurnGroupID {
+                        from: translate(g.from),
+                        to: translate(g.to),
+                        parent: to,
+                        crosswalk: g.crosswalk,
+                    };
+                    if !turn_groups.contains_key(&id) {
+                        problems.push(format!(
+                            "Phase {}: {} doesn't exist at {}",
+                            idx + 1,
+                            describe_group(g, map),
+                            to
+                        ));
+                        continue;
+                    }
+                    covered.insert(id);
+                    if protected {
+                        new_phase.protected_groups.insert(id);
+                    } else {
+                        new_phase.yield_groups.insert(id);
+                    }
+                }
+            }
+            phases.push(new_phase);
+        }
+        for g in turn_groups.keys() {
+            if !covered.contains(g) {
+                problems.push(format!(
+                    "{} isn't part of any phase",
+                    describe_group(g, map)
+                ));
+            }
+        }
+
+        let signal = ControlTrafficSignal {
+            id: to,
+            phases,
+            offset: self.offset,
+            turn_groups,
+        };
+        for phase in &signal.phases {
+            for g1 in &phase.protected_groups {
+                for g2 in &phase.protected_groups {
+                    if signal.turn_groups[g1].conflicts_with(&signal.turn_groups[g2]) {
+                        return Err(format!(
+                            "{} and {} would both be protected at once, but they conflict at {}",
+                            describe_group(g1, map),
+                            describe_group(g2, map),
+                            to
+                        ));
+                    }
+                }
+            }
+        }
+        Ok((signal, problems))
+    }
 }
 
 impl Phase {
@@ -329,3 +463,36 @@ fn find_r(id: seattle_traffic_signals::DirectedRoad, map: &Map) -> Option<Direct
         forwards: id.is_forwards,
     })
 }
+
+// How far apart the matched-up roads of two intersections can be when transferring a timing plan
+const MAX_TRANSFER_ANGLE_DEGREES: f64 = 30.0;
+
+// Every road of an intersection, with the angle it approaches from, sorted by that angle
+fn road_angles(map: &Map, i: IntersectionID) -> Vec<(RoadID, Angle)> {
+    let i = map.get_i(i);
+    let center = i.polygon.center();
+    i.get_roads_sorted_by_incoming_angle(map.all_roads())
+        .into_iter()
+        .map(|id| {
+            let r = map.get_r(id);
+            let endpt = if r.src_i == i.id {
+                r.center_pts.first_pt()
+            } else {
+                r.center_pts.last_pt()
+            };
+            (id, endpt.angle_to(center))
+        })
+        .collect()
+}
+
+pub fn describe_group(g: &TurnGroupID, map: &Map) -> String {
+    if g.crosswalk {
+        format!("the crosswalk across {}", map.get_r(g.from.id).get_name())
+    } else {
+        format!(
+            "the turn from {} to {}",
+            map.get_r(g.from.id).get_name(),
+            map.get_r(g.to.id).get_name()
+        )
+    }
+}
@@ -6,7 +6,7 @@ use map_model::{
     Traversable, TurnGroupID,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Analytics {
@@ -24,9 +24,14 @@ pub struct Analytics {
     // TODO This subsumes finished_trips
     pub trip_log: Vec<(Time, TripID, Option<PathRequest>, TripPhaseType)>,
     pub intersection_delays: BTreeMap<IntersectionID, Vec<(Time, Duration, TripMode)>>,
+    // For signals, the total delay and number of agents measured per incoming road
+    pub approach_delays: BTreeMap<IntersectionID, BTreeMap<RoadID, (Duration, usize)>>,
     // Per parking lane or lot, when does a spot become filled (true) or free (false)
     pub parking_lane_changes: BTreeMap<LaneID, Vec<(Time, bool)>>,
     pub parking_lot_changes: BTreeMap<ParkingLotID, Vec<(Time, bool)>>,
+    // Every spot in the map (including off-street), when does it become filled or free
+    pub parking_spot_changes: Vec<(Time, bool)>,
+    pub gridlocks: Vec<(Time, IntersectionID)>,
     pub(crate) alerts: Vec<(Time, AlertLocation, String)>,
 
     // After we restore from a savestate, don't record anything. This is only going to make sense
@@ -47,8 +52,11 @@ impl Analytics {
             finished_trips: Vec::new(),
             trip_log: Vec::new(),
             intersection_delays: BTreeMap::new(),
+            approach_delays: BTreeMap::new(),
             parking_lane_changes: BTreeMap::new(),
             parking_lot_changes: BTreeMap::new(),
+            parking_spot_changes: Vec::new(),
+            gridlocks: Vec::new(),
             alerts: Vec::new(),
             record_anything: true,
         }
@@ -115,15 +123,24 @@ impl Analytics {
         }
 
         // Intersection delays
-        if let Event::IntersectionDelayMeasured(id, delay, mode) = ev {
+        if let Event::IntersectionDelayMeasured(id, approach, delay, mode) = ev {
             self.intersection_delays
                 .entry(id)
                 .or_insert_with(Vec::new)
                 .push((time, delay, mode));
+            let entry = self
+                .approach_delays
+                .entry(id)
+                .or_insert_with(BTreeMap::new)
+                .entry(approach)
+                .or_insert((Duration::ZERO, 0));
+            entry.0 += delay;
+            entry.1 += 1;
         }
 
         // Parking spot changes
         if let Event::CarReachedParkingSpot(_, spot) = ev {
+            self.parking_spot_changes.push((time, true));
             if let ParkingSpot::Onstreet(l, _) = spot {
                 self.parking_lane_changes
                     .entry(l)
@@ -137,6 +154,7 @@ impl Analytics {
             }
         }
         if let Event::CarLeftParkingSpot(_, spot) = ev {
+            self.parking_spot_changes.push((time, false));
             if let ParkingSpot::Onstreet(l, _) = spot {
                 self.parking_lane_changes
                     .entry(l)
@@ -168,6 +186,14 @@ impl Analytics {
             Event::Alert(loc, msg) => {
                 self.alerts.push((time, loc, msg));
             }
+            Event::Gridlock(i, stuck) => {
+                self.gridlocks.push((time, i));
+                self.alerts.push((
+                    time,
+                    AlertLocation::Intersection(i),
+                    format!("Gridlock detected at {}; nobody's moved for {}", i, stuck),
+                ));
+            }
             _ => {}
         }
     }
@@ -319,6 +345,84 @@ impl Analytics {
         delays_to_stop
     }
 
+    // Buses on a route that bunched up, based on the last two arrivals at each stop. If the second
+    // bus arrived less than min_headway after the first, both are flagged.
+    pub fn bunched_buses(
+        &self,
+        now: Time,
+        r: BusRouteID,
+        min_headway: Duration,
+    ) -> BTreeSet<CarID> {
+        let mut last_two: BTreeMap<BusStopID, Vec<(Time, CarID)>> = BTreeMap::new();
+        for (t, car, route, stop) in &self.bus_arrivals {
+            if *t > now {
+                break;
+            }
+            if *route == r {
+                let arrivals = last_two.entry(*stop).or_insert_with(Vec::new);
+                arrivals.push((*t, *car));
+                if arrivals.len() > 2 {
+                    arrivals.remove(0);
+                }
+            }
+        }
+
+        let mut bunched = BTreeSet::new();
+        for arrivals in last_two.values() {
+            if arrivals.len() == 2
+                && arrivals[0].1 != arrivals[1].1
+                && arrivals[1].0 - arrivals[0].0 < min_headway
+            {
+                bunched.insert(arrivals[0].1);
+                bunched.insert(arrivals[1].1);
+            }
+        }
+        bunched
+    }
+
+    // How evenly spaced are buses along a route? Every headway (the time between consecutive
+    // arrivals at a stop) is compared against the mean headway at that stop, which is what an even
+    // schedule would have. Returns the percent of headways within 50% of that, or None if there
+    // aren't enough arrivals yet.
+    pub fn route_reliability(&self, now: Time, r: BusRouteID) -> Option<f64> {
+        let mut per_stop: BTreeMap<BusStopID, Vec<Time>> = BTreeMap::new();
+        for (t, _, route, stop) in &self.bus_arrivals {
+            if *t > now {
+                break;
+            }
+            if *route == r {
+                per_stop.entry(*stop).or_insert_with(Vec::new).push(*t);
+            }
+        }
+
+        let mut total = 0;
+        let mut reliable = 0;
+        for times in per_stop.values() {
+            // A single headway always matches itself
+            if times.len() < 3 {
+                continue;
+            }
+            let headways: Vec<Duration> = times.windows(2).map(|pair| pair[1] - pair[0]).collect();
+            let nominal = headways.iter().cloned().sum::<Duration>() / (headways.len() as f64);
+            for h in headways {
+                let diff = if h > nominal {
+                    h - nominal
+                } else {
+                    nominal - h
+                };
+                total += 1;
+                if diff <= nominal * 0.5 {
+                    reliable += 1;
+                }
+            }
+        }
+        if total == 0 {
+            None
+        } else {
+            Some(100.0 * (reliable as f64) / (total as f64))
+        }
+    }
+
     // At some moment in time, what's the distribution of passengers waiting for a route like?
     pub fn bus_passenger_delays(
         &self,
@@ -488,6 +592,42 @@ impl Analytics {
         }
     }
 
+    // Returns the filled spots over the entire map. Since we don't know how many spots started
+    // filled, work backwards from the number filled at now.
+    pub fn parking_spots_filled(&self, now: Time, filled_now: usize) -> Vec<(Time, usize)> {
+        let mut net: isize = 0;
+        for (t, filled) in &self.parking_spot_changes {
+            if *t > now {
+                break;
+            }
+            net += if *filled { 1 } else { -1 };
+        }
+
+        let mut pts = Vec::new();
+        let mut cnt = (filled_now as isize - net).max(0) as usize;
+        let mut last_t = Time::START_OF_DAY;
+        for (t, filled) in &self.parking_spot_changes {
+            if *t > now {
+                break;
+            }
+            if *t != last_t {
+                // Step functions. Don't interpolate.
+                pts.push((last_t, cnt));
+            }
+            last_t = *t;
+            if *filled {
+                cnt += 1;
+            } else {
+                cnt = cnt.saturating_sub(1);
+            }
+        }
+        pts.push((last_t, cnt));
+        if last_t != now {
+            pts.push((now, cnt));
+        }
+        pts
+    }
+
     fn parking_spot_availability(
         now: Time,
         changes: &Vec<(Time, bool)>,
@@ -571,16 +711,21 @@ impl<X: Ord + Clone> TimeSeriesCount<X> {
     }
 
     pub fn total_for(&self, id: X) -> usize {
+        TripMode::all()
+            .into_iter()
+            .map(|mode| self.total_for_mode(id.clone(), mode))
+            .sum()
+    }
+
+    pub fn total_for_mode(&self, id: X, mode: TripMode) -> usize {
         let mut cnt = 0;
-        for mode in TripMode::all() {
-            // TODO Hmm
-            for hour in 0..24 {
-                cnt += self
-                    .counts
-                    .get(&(id.clone(), mode, hour))
-                    .cloned()
-                    .unwrap_or(0);
-            }
+        // TODO Hmm
+        for hour in 0..24 {
+            cnt += self
+                .counts
+                .get(&(id.clone(), mode, hour))
+                .cloned()
+                .unwrap_or(0);
         }
         cnt
     }
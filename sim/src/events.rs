@@ -3,7 +3,8 @@ use crate::{
 };
 use geom::Duration;
 use map_model::{
-    BuildingID, BusRouteID, BusStopID, IntersectionID, LaneID, Map, Path, PathRequest, Traversable,
+    BuildingID, BusRouteID, BusStopID, IntersectionID, LaneID, Map, Path, PathRequest, RoadID,
+    Traversable,
 };
 use serde::{Deserialize, Serialize};
 
@@ -30,7 +31,8 @@ pub enum Event {
     BikeStoppedAtSidewalk(CarID, LaneID),
 
     AgentEntersTraversable(AgentID, Traversable),
-    IntersectionDelayMeasured(IntersectionID, Duration, TripMode),
+    // The road is the approach the agent was waiting on
+    IntersectionDelayMeasured(IntersectionID, RoadID, Duration, TripMode),
 
     TripFinished {
         trip: TripID,
@@ -46,6 +48,8 @@ pub enum Event {
     PathAmended(Path),
 
     Alert(AlertLocation, String),
+    // Nobody has made it through this intersection for the given duration
+    Gridlock(IntersectionID, Duration),
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
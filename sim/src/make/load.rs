@@ -1,5 +1,6 @@
 use crate::{AlertHandler, Scenario, Sim, SimOptions};
 use abstutil::CmdArgs;
+use geom::{Duration, Time};
 use map_model::{Map, MapEdits};
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
@@ -9,8 +10,12 @@ const RNG_SEED: u8 = 42;
 #[derive(Clone)]
 pub struct SimFlags {
     pub load: String,
+    // A savestate or exported sim state, to start at that exact moment. Overrides load.
+    pub load_savestate: Option<String>,
     pub rng_seed: u8,
     pub opts: SimOptions,
+    // Stop the simulation at this time, instead of running until everything is done
+    pub end_time: Option<Time>,
 }
 
 impl SimFlags {
@@ -23,6 +28,7 @@ impl SimFlags {
             load: args
                 .optional_free()
                 .unwrap_or_else(|| "../data/system/maps/montlake.bin".to_string()),
+            load_savestate: args.optional("--load_savestate"),
             rng_seed,
             opts: SimOptions {
                 run_name: args
@@ -47,7 +53,18 @@ impl SimFlags {
                     })
                     .unwrap_or(AlertHandler::Print),
                 pathfinding_upfront: args.enabled("--pathfinding_upfront"),
+                bus_headway: args.optional_parse("--bus_headway", Duration::parse),
             },
+            end_time: args.optional_parse("--end_time", SimFlags::parse_end_time),
+        }
+    }
+
+    // Unlike Time::parse, "12:00" means noon, not 12 minutes.
+    pub fn parse_end_time(value: &str) -> Result<Time, abstutil::Error> {
+        if value.matches(':').count() == 1 {
+            Time::parse(&format!("{}:00", value))
+        } else {
+            Time::parse(value)
         }
     }
 
@@ -59,8 +76,10 @@ impl SimFlags {
     pub fn synthetic_test(map: &str, run_name: &str) -> SimFlags {
         SimFlags {
             load: abstutil::path_map(map),
+            load_savestate: None,
             rng_seed: RNG_SEED,
             opts: SimOptions::new(run_name),
+            end_time: None,
         }
     }
 
@@ -74,17 +93,23 @@ impl SimFlags {
 
         let mut opts = self.opts.clone();
 
-        if self.load.starts_with("../data/player/saves/") {
-            timer.note(format!("Resuming from {}", self.load));
+        if let Some(ref path) = self.load_savestate {
+            timer.note(format!("Resuming from {}", path));
 
-            let mut sim: Sim = abstutil::read_binary(self.load.clone(), timer);
+            // An export is a savestate followed by the analytics, so reading a plain savestate as
+            // an export fails once the file runs out.
+            let sim = match Sim::read_export(path.clone(), timer) {
+                Ok(sim) => sim,
+                Err(_) => abstutil::read_binary(path.clone(), timer),
+            };
+            let (map, sim) = resume(sim, timer);
 
-            let mut map = Map::new(abstutil::path_map(&sim.map_name), timer);
-            if sim.edits_name != "untitled edits" {
-                map.apply_edits(MapEdits::load(&map, &sim.edits_name, timer).unwrap(), timer);
-                map.recalculate_pathfinding_after_edits(timer);
-            }
-            sim.restore_paths(&map, timer);
+            (map, sim, rng)
+        } else if self.load.starts_with("../data/player/saves/") {
+            timer.note(format!("Resuming from {}", self.load));
+
+            let sim: Sim = abstutil::read_binary(self.load.clone(), timer);
+            let (map, sim) = resume(sim, timer);
 
             (map, sim, rng)
         } else if self.load.starts_with("../data/system/scenarios/") {
@@ -122,3 +147,14 @@ impl SimFlags {
         }
     }
 }
+
+// Load the map that a savestate was made on.
+fn resume(mut sim: Sim, timer: &mut abstutil::Timer) -> (Map, Sim) {
+    let mut map = Map::new(abstutil::path_map(&sim.map_name), timer);
+    if sim.edits_name != "untitled edits" {
+        map.apply_edits(MapEdits::load(&map, &sim.edits_name, timer).unwrap(), timer);
+        map.recalculate_pathfinding_after_edits(timer);
+    }
+    sim.restore_paths(&map, timer);
+    (map, sim)
+}
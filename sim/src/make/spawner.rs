@@ -207,6 +207,17 @@ impl TripSpawner {
         if profile {
             abstutil::stop_profiler();
         }
+        // These trips will abort as soon as they start, so flag them now, rather than letting
+        // somebody discover it mid-simulation.
+        if pathfinding_upfront {
+            let unroutable = paths
+                .iter()
+                .filter(|(_, req, path)| req.is_some() && path.is_none())
+                .count();
+            if unroutable > 0 {
+                timer.warn(format!("{} trips have no valid path", unroutable));
+            }
+        }
 
         timer.start_iter("spawn trips", paths.len());
         for ((p, start_time, spec, trip_start), maybe_req, maybe_path) in paths {
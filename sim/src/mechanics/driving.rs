@@ -564,6 +564,7 @@ impl DrivingSimState {
         c: CarID,
         now: Time,
         map: &Map,
+        parking: &mut ParkingSimState,
         scheduler: &mut Scheduler,
         intersections: &mut IntersectionSimState,
     ) -> Vehicle {
@@ -575,6 +576,11 @@ impl DrivingSimState {
         let idx = dists.iter().position(|(id, _)| *id == c).unwrap();
         let mut car = self.cars.remove(&c).unwrap();
 
+        // A car in the middle of parking already reserved its spot
+        if let CarState::Parking(_, spot, _) = car.state {
+            parking.unreserve_spot(spot);
+        }
+
         // Hacks to delete cars that're mid-turn
         if let Traversable::Turn(_) = car.router.head() {
             let queue = self.queues.get_mut(&car.router.head()).unwrap();
@@ -915,6 +921,27 @@ impl DrivingSimState {
         }
     }
 
+    // How many cars are stopped on each lane, waiting to move forward
+    pub fn queued_cars_per_lane(&self) -> BTreeMap<LaneID, usize> {
+        let mut results = BTreeMap::new();
+        for queue in self.queues.values() {
+            if let Traversable::Lane(l) = queue.id {
+                let num = queue
+                    .cars
+                    .iter()
+                    .filter(|c| match self.cars[*c].state {
+                        CarState::Queued { .. } | CarState::WaitingToAdvance { .. } => true,
+                        _ => false,
+                    })
+                    .count();
+                if num > 0 {
+                    results.insert(l, num);
+                }
+            }
+        }
+        results
+    }
+
     pub fn agent_properties(&self, id: CarID, now: Time) -> AgentProperties {
         let car = self.cars.get(&id).unwrap();
         let path = car.router.get_path();
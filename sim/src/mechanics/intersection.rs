@@ -22,6 +22,8 @@ pub struct IntersectionSimState {
     // (x, y) means x is blocked by y. It's a many-to-many relationship. TODO Better data
     // structure.
     blocked_by: BTreeSet<(CarID, CarID)>,
+    // Only report gridlock once, until it clears up
+    gridlocked: BTreeSet<IntersectionID>,
     events: Vec<Event>,
 }
 
@@ -57,6 +59,7 @@ impl IntersectionSimState {
             dont_block_the_box,
             break_turn_conflict_cycles,
             blocked_by: BTreeSet::new(),
+            gridlocked: BTreeSet::new(),
             events: Vec::new(),
         };
         for i in map.all_intersections() {
@@ -293,6 +296,7 @@ impl IntersectionSimState {
         if map.maybe_get_traffic_signal(state.id).is_some() {
             self.events.push(Event::IntersectionDelayMeasured(
                 turn.parent,
+                map.get_l(turn.src).parent,
                 delay,
                 TripMode::from_agent(agent),
             ));
@@ -359,6 +363,21 @@ impl IntersectionSimState {
         candidates
     }
 
+    pub fn check_for_gridlock(&mut self, now: Time, threshold: Duration) {
+        let mut gridlocked = BTreeSet::new();
+        for (i, earliest) in self.delayed_intersections(now, threshold) {
+            if !self.gridlocked.contains(&i) {
+                self.events.push(Event::Gridlock(i, now - earliest));
+            }
+            gridlocked.insert(i);
+        }
+        self.gridlocked = gridlocked;
+    }
+
+    pub fn current_gridlocks(&self) -> Vec<IntersectionID> {
+        self.gridlocked.iter().cloned().collect()
+    }
+
     // Weird way to measure this, but it works.
     pub fn worst_delay(
         &self,
@@ -150,6 +150,11 @@ impl ParkingSimState {
         }
     }
 
+    // The car that reserved the spot won't park there after all
+    pub fn unreserve_spot(&mut self, spot: ParkingSpot) {
+        assert!(self.reserved_spots.remove(&spot));
+    }
+
     pub fn remove_parked_car(&mut self, p: ParkedCar) {
         self.parked_cars
             .remove(&p.vehicle.id)
@@ -362,6 +367,12 @@ impl ParkingSimState {
     pub fn lookup_parked_car(&self, id: CarID) -> Option<&ParkedCar> {
         self.parked_cars.get(&id)
     }
+    pub fn get_parked_cars_by_owner(&self, id: PersonID) -> Vec<&ParkedCar> {
+        self.parked_cars
+            .values()
+            .filter(|p| p.vehicle.owner == Some(id))
+            .collect()
+    }
 
     // (Filled, available)
     pub fn get_all_parking_spots(&self) -> (Vec<ParkingSpot>, Vec<ParkingSpot>) {
@@ -472,7 +483,7 @@ struct ParkingLane {
 
 impl ParkingLane {
     fn new(lane: &Lane, map: &Map, timer: &mut Timer) -> Option<ParkingLane> {
-        if lane.lane_type != LaneType::Parking {
+        if lane.lane_type != LaneType::Parking || map.is_lane_removed(lane.id) {
             return None;
         }
 
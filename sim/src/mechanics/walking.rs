@@ -308,6 +308,39 @@ impl WalkingSimState {
         }
     }
 
+    // Returns false if the pedestrian is waiting for a bus, since transit also tracks them then.
+    pub fn delete_ped(
+        &mut self,
+        id: PedestrianID,
+        now: Time,
+        map: &Map,
+        scheduler: &mut Scheduler,
+        intersections: &mut IntersectionSimState,
+    ) -> bool {
+        let ped = &self.peds[&id];
+        match ped.state {
+            PedState::WaitingForBus(_, _) => {
+                return false;
+            }
+            PedState::WaitingToTurn(_, _) => {
+                if let PathStep::Turn(t) = ped.path.next_step() {
+                    intersections.cancel_request(AgentID::Pedestrian(id), t);
+                }
+            }
+            PedState::Crossing(_, _) => {
+                if let PathStep::Turn(t) = ped.path.current_step() {
+                    intersections.turn_finished(now, AgentID::Pedestrian(id), t, scheduler, map);
+                }
+            }
+            _ => {}
+        }
+        let ped = self.peds.remove(&id).unwrap();
+        self.peds_per_traversable
+            .remove(ped.path.current_step().as_traversable(), id);
+        scheduler.cancel(Command::UpdatePed(id));
+        true
+    }
+
     pub fn ped_boarded_bus(&mut self, now: Time, id: PedestrianID) {
         let mut ped = self.peds.remove(&id).unwrap();
         match ped.state {
@@ -1,3 +1,4 @@
+use crate::scheduler::CommandType;
 use crate::{
     AgentID, AlertLocation, Analytics, CarID, Command, CreateCar, DrawCarInput, DrawPedCrowdInput,
     DrawPedestrianInput, DrivingSimState, Event, GetDrawAgents, IntersectionSimState, OrigPersonID,
@@ -21,6 +22,9 @@ use std::panic;
 
 // TODO Do something else.
 const BLIND_RETRY_TO_SPAWN: Duration = Duration::const_seconds(5.0);
+const CHECK_FOR_GRIDLOCK_FREQUENCY: Duration = Duration::const_seconds(60.0);
+// If nobody's made it through an intersection for this long, something's wrong.
+const GRIDLOCK_THRESHOLD: Duration = Duration::const_seconds(15.0 * 60.0);
 
 #[derive(Serialize, Deserialize, Clone, Derivative)]
 #[derivative(PartialEq)]
@@ -60,6 +64,9 @@ pub struct Sim {
     #[derivative(PartialEq = "ignore")]
     #[serde(skip_serializing, skip_deserializing)]
     alerts: AlertHandler,
+    #[derivative(PartialEq = "ignore")]
+    #[serde(skip_serializing, skip_deserializing)]
+    bus_headway: Option<Duration>,
 }
 
 #[derive(Clone)]
@@ -72,6 +79,9 @@ pub struct SimOptions {
     pub enable_pandemic_model: Option<XorShiftRng>,
     pub alerts: AlertHandler,
     pub pathfinding_upfront: bool,
+    // If set, keep adding buses to every seeded route this often, until the first bus finishes a
+    // loop.
+    pub bus_headway: Option<Duration>,
 }
 
 #[derive(Clone)]
@@ -101,6 +111,7 @@ impl SimOptions {
             enable_pandemic_model: None,
             alerts: AlertHandler::Print,
             pathfinding_upfront: false,
+            bus_headway: None,
         }
     }
 }
@@ -109,6 +120,10 @@ impl SimOptions {
 impl Sim {
     pub fn new(map: &Map, opts: SimOptions, timer: &mut Timer) -> Sim {
         let mut scheduler = Scheduler::new();
+        scheduler.push(
+            Time::START_OF_DAY + CHECK_FOR_GRIDLOCK_FREQUENCY,
+            Command::CheckForGridlock,
+        );
         Sim {
             driving: DrivingSimState::new(map, opts.recalc_lanechanging),
             parking: ParkingSimState::new(map, timer),
@@ -137,6 +152,7 @@ impl Sim {
             step_count: 0,
             trip_positions: None,
             alerts: opts.alerts,
+            bus_headway: opts.bus_headway,
 
             analytics: Analytics::new(),
         }
@@ -237,6 +253,17 @@ impl Sim {
     }
 
     pub fn seed_bus_route(&mut self, route: &BusRoute, map: &Map, timer: &mut Timer) -> Vec<CarID> {
+        let results = self.spawn_bus(route, map, timer);
+        if let Some(headway) = self.bus_headway {
+            if !results.is_empty() {
+                self.scheduler
+                    .push(self.time + headway, Command::SeedBus(route.id, headway));
+            }
+        }
+        results
+    }
+
+    fn spawn_bus(&mut self, route: &BusRoute, map: &Map, timer: &mut Timer) -> Vec<CarID> {
         let mut results: Vec<CarID> = Vec::new();
 
         // Try to spawn just ONE bus anywhere.
@@ -591,6 +618,14 @@ impl Sim {
                     .unwrap()
                     .handle_cmd(self.time, cmd, &mut self.scheduler);
             }
+            Command::CheckForGridlock => {
+                self.scheduler.push(
+                    self.time + CHECK_FOR_GRIDLOCK_FREQUENCY,
+                    Command::CheckForGridlock,
+                );
+                self.intersections
+                    .check_for_gridlock(self.time, GRIDLOCK_THRESHOLD);
+            }
             Command::FinishRemoteTrip(trip) => {
                 self.trips.remote_trip_finished(
                     self.time,
@@ -600,6 +635,24 @@ impl Sim {
                     &mut self.scheduler,
                 );
             }
+            Command::SeedBus(r, headway) => {
+                // Once any bus is back at the first stop, the route has enough buses to maintain
+                // the headway. That might never happen in gridlock or with analytics off, so also
+                // stop at one bus per stop, and at the end of the day.
+                let route = map.get_br(r);
+                let looped = self
+                    .analytics
+                    .bus_arrivals
+                    .iter()
+                    .any(|(_, _, br, bs)| *br == r && *bs == route.stops[0]);
+                let enough = self.transit.buses_for_route(r).len() >= route.stops.len();
+                let end_of_day = self.time + headway > Time::START_OF_DAY + Duration::hours(24);
+                if !looped && !enough && !end_of_day {
+                    self.spawn_bus(route, map, &mut Timer::throwaway());
+                    self.scheduler
+                        .push(self.time + headway, Command::SeedBus(r, headway));
+                }
+            }
         }
 
         // Record events at precisely the time they occur.
@@ -734,13 +787,43 @@ impl Sim {
         callback: F,
         // Interpreted as a relative time
         time_limit: Option<Duration>,
+    ) {
+        self.inner_run_until_done(map, callback, time_limit, None);
+    }
+
+    // Like run_until_done, but quietly stops at end_time, even if trips are still in progress.
+    pub fn run_until_done_or_time<F: Fn(&mut Sim, &Map)>(
+        &mut self,
+        map: &Map,
+        callback: F,
+        end_time: Time,
+    ) {
+        self.inner_run_until_done(map, callback, None, Some(end_time));
+    }
+
+    fn inner_run_until_done<F: Fn(&mut Sim, &Map)>(
+        &mut self,
+        map: &Map,
+        callback: F,
+        time_limit: Option<Duration>,
+        end_time: Option<Time>,
     ) {
         let mut last_print = Instant::now();
         let mut last_sim_time = self.time();
 
         loop {
+            if let Some(t) = end_time {
+                if self.time() >= t {
+                    println!("Stopping at {}, the requested end time", self.time());
+                    break;
+                }
+            }
+
             // TODO Regular printing doesn't happen if we use a time_limit :\
-            let dt = time_limit.unwrap_or_else(|| Duration::seconds(30.0));
+            let mut dt = time_limit.unwrap_or_else(|| Duration::seconds(30.0));
+            if let Some(t) = end_time {
+                dt = dt.min(t - self.time());
+            }
 
             match panic::catch_unwind(panic::AssertUnwindSafe(|| {
                 self.timed_step(map, dt, &mut None, &mut Timer::throwaway());
@@ -849,6 +932,38 @@ impl Sim {
         path
     }
 
+    pub fn export_dir(&self) -> String {
+        abstutil::path_all_sim_exports(&self.map_name, &self.edits_name, &self.run_name)
+    }
+
+    // Unlike a normal savestate, this also includes analytics, so the exact state (including
+    // dashboards and info panels) can be shared and reproduced later.
+    pub fn export(&mut self) -> String {
+        let restore = self.scheduler.before_savestate();
+        let path = abstutil::path_sim_export(
+            &self.map_name,
+            &self.edits_name,
+            &self.run_name,
+            self.time.as_filename(),
+        );
+        abstutil::write_binary(path.clone(), &(&*self, &self.analytics));
+        self.scheduler.after_savestate(restore);
+        path
+    }
+
+    pub fn load_export(path: String, map: &Map, timer: &mut Timer) -> Result<Sim, std::io::Error> {
+        let mut sim = Sim::read_export(path, timer)?;
+        sim.restore_paths(map, timer);
+        Ok(sim)
+    }
+
+    // The caller still has to restore_paths.
+    pub(crate) fn read_export(path: String, timer: &mut Timer) -> Result<Sim, std::io::Error> {
+        let (mut sim, analytics): (Sim, Analytics) = abstutil::maybe_read_binary(path, timer)?;
+        sim.analytics = analytics;
+        Ok(sim)
+    }
+
     pub fn find_previous_savestate(&self, base_time: Time) -> Option<String> {
         abstutil::find_prev_file(self.save_path(base_time))
     }
@@ -891,10 +1006,15 @@ impl Sim {
         self.time == Time::START_OF_DAY && self.is_done()
     }
 
-    // (number of finished trips, number of unfinished trips, number of active by mode)
-    pub fn num_trips(&self) -> (usize, usize, BTreeMap<TripMode, usize>) {
+    // (number of finished trips, number of unfinished trips, (number of active agents, number
+    // of finished trips) by mode)
+    pub fn num_trips(&self) -> (usize, usize, BTreeMap<TripMode, (usize, usize)>) {
         self.trips.num_trips()
     }
+    // Total delay of all finished trips
+    pub fn total_delay(&self) -> Duration {
+        self.trips.total_delay()
+    }
     // (total number of people, just in buildings, just off map)
     pub fn num_ppl(&self) -> (usize, usize, usize) {
         self.trips.num_ppl()
@@ -960,6 +1080,26 @@ impl Sim {
         self.trips.active_agents()
     }
 
+    // The active trips stuck in one place for the longest right now, worst first
+    pub fn longest_waiting_trips(&self, limit: usize) -> Vec<(TripID, AgentID, Duration)> {
+        let mut results = Vec::new();
+        for a in self.active_agents() {
+            // Riding a bus doesn't count as being stuck
+            if let AgentID::BusPassenger(_, _) = a {
+                continue;
+            }
+            if let Some(trip) = self.agent_to_trip(a) {
+                let waiting = self.agent_properties(a).waiting_here;
+                if waiting > Duration::ZERO {
+                    results.push((trip, a, waiting));
+                }
+            }
+        }
+        results.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap());
+        results.truncate(limit);
+        results
+    }
+
     pub fn agent_to_trip(&self, id: AgentID) -> Option<TripID> {
         self.trips.agent_to_trip(id)
     }
@@ -993,6 +1133,9 @@ impl Sim {
     pub fn lookup_parked_car(&self, id: CarID) -> Option<&ParkedCar> {
         self.parking.lookup_parked_car(id)
     }
+    pub fn get_parked_cars_by_owner(&self, id: PersonID) -> Vec<&ParkedCar> {
+        self.parking.get_parked_cars_by_owner(id)
+    }
 
     pub fn lookup_person(&self, id: PersonID) -> Option<&Person> {
         self.trips.get_person(id)
@@ -1039,6 +1182,9 @@ impl Sim {
     pub fn get_all_driving_paths(&self) -> Vec<&Path> {
         self.driving.get_all_driving_paths()
     }
+    pub fn queued_cars_per_lane(&self) -> BTreeMap<LaneID, usize> {
+        self.driving.queued_cars_per_lane()
+    }
 
     pub fn trace_route(
         &self,
@@ -1143,6 +1289,11 @@ impl Sim {
             .delayed_intersections(self.time, threshold)
     }
 
+    // Intersections that were detected as gridlocked and haven't cleared up yet
+    pub fn current_gridlocks(&self) -> Vec<IntersectionID> {
+        self.intersections.current_gridlocks()
+    }
+
     pub fn bldg_to_people(&self, b: BuildingID) -> Vec<PersonID> {
         self.trips.bldg_to_people(b)
     }
@@ -1177,6 +1328,7 @@ impl Sim {
                 id,
                 self.time,
                 map,
+                &mut self.parking,
                 &mut self.scheduler,
                 &mut self.intersections,
             );
@@ -1194,6 +1346,59 @@ impl Sim {
         }
     }
 
+    // Removes the trip's agent from the map and aborts the trip. A trip that hasn't started yet is
+    // just deleted. Returns false if the trip is in a state that can't be interrupted yet, like
+    // riding a bus.
+    pub fn cancel_trip(&mut self, id: TripID, map: &Map) -> bool {
+        let (agent, vehicle) = match self.trips.trip_to_agent(id) {
+            TripResult::TripNotStarted => {
+                if self.trips.is_trip_delayed(id) {
+                    return false;
+                }
+                self.scheduler
+                    .must_cancel_by_type(CommandType::StartTrip(id));
+                self.trips.delete_unstarted_trip(id);
+                return true;
+            }
+            TripResult::Ok(AgentID::Car(c)) => {
+                let vehicle = self.driving.kill_stuck_car(
+                    c,
+                    self.time,
+                    map,
+                    &mut self.parking,
+                    &mut self.scheduler,
+                    &mut self.intersections,
+                );
+                (Some(AgentID::Car(c)), Some(vehicle))
+            }
+            TripResult::Ok(AgentID::Pedestrian(p)) => {
+                if !self.walking.delete_ped(
+                    p,
+                    self.time,
+                    map,
+                    &mut self.scheduler,
+                    &mut self.intersections,
+                ) {
+                    return false;
+                }
+                (Some(AgentID::Pedestrian(p)), None)
+            }
+            _ => {
+                return false;
+            }
+        };
+        self.trips.cancel_trip(
+            self.time,
+            id,
+            agent,
+            vehicle,
+            &mut self.parking,
+            &mut self.scheduler,
+            map,
+        );
+        true
+    }
+
     pub fn clear_alerts(&mut self) -> Vec<(Time, AlertLocation, String)> {
         std::mem::replace(&mut self.analytics.alerts, Vec::new())
     }
@@ -130,7 +130,8 @@ impl TransitSimState {
                 )
             })
             .collect();
-        self.routes.insert(bus_route.id, route);
+        // Seeding more buses later shouldn't forget about the existing ones
+        self.routes.entry(bus_route.id).or_insert(route);
         stops
     }
 
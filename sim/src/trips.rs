@@ -24,6 +24,10 @@ pub struct TripManager {
     )]
     active_trip_mode: BTreeMap<AgentID, TripID>,
     unfinished_trips: usize,
+    // Includes aborted trips
+    finished_per_mode: BTreeMap<TripMode, usize>,
+    // The time every finished trip spent waiting, added up as they finish
+    total_delay: Duration,
     pub pathfinding_upfront: bool,
 
     car_id_counter: usize,
@@ -38,6 +42,8 @@ impl TripManager {
             people: Vec::new(),
             active_trip_mode: BTreeMap::new(),
             unfinished_trips: 0,
+            finished_per_mode: BTreeMap::new(),
+            total_delay: Duration::ZERO,
             car_id_counter: 0,
             events: Vec::new(),
             pathfinding_upfront,
@@ -193,6 +199,8 @@ impl TripManager {
                     assert!(!trip.finished_at.is_some());
                     trip.finished_at = Some(now);
                     self.unfinished_trips -= 1;
+                    self.total_delay += trip.total_blocked_time;
+                    *self.finished_per_mode.entry(trip.mode).or_insert(0) += 1;
                     self.events.push(Event::TripFinished {
                         trip: trip.id,
                         mode: trip.mode,
@@ -219,6 +227,7 @@ impl TripManager {
             &mut self.events,
         ) {
             self.unfinished_trips -= 1;
+            *self.finished_per_mode.entry(trip.mode).or_insert(0) += 1;
         }
     }
 
@@ -402,6 +411,7 @@ impl TripManager {
             &mut self.events,
         ) {
             self.unfinished_trips -= 1;
+            *self.finished_per_mode.entry(trip.mode).or_insert(0) += 1;
         }
     }
 
@@ -427,6 +437,8 @@ impl TripManager {
         assert!(!trip.finished_at.is_some());
         trip.finished_at = Some(now);
         self.unfinished_trips -= 1;
+        self.total_delay += trip.total_blocked_time;
+        *self.finished_per_mode.entry(trip.mode).or_insert(0) += 1;
         self.events.push(Event::TripFinished {
             trip: trip.id,
             mode: trip.mode,
@@ -544,6 +556,7 @@ impl TripManager {
             &mut self.events,
         ) {
             self.unfinished_trips -= 1;
+            *self.finished_per_mode.entry(trip.mode).or_insert(0) += 1;
         }
     }
 
@@ -575,6 +588,8 @@ impl TripManager {
         assert!(!trip.finished_at.is_some());
         trip.finished_at = Some(now);
         self.unfinished_trips -= 1;
+        self.total_delay += trip.total_blocked_time;
+        *self.finished_per_mode.entry(trip.mode).or_insert(0) += 1;
         self.events.push(Event::TripFinished {
             trip: trip.id,
             mode: trip.mode,
@@ -618,6 +633,8 @@ impl TripManager {
         assert!(!trip.finished_at.is_some());
         trip.finished_at = Some(now);
         self.unfinished_trips -= 1;
+        self.total_delay += trip.total_blocked_time;
+        *self.finished_per_mode.entry(trip.mode).or_insert(0) += 1;
         self.events.push(Event::TripFinished {
             trip: trip.id,
             mode: trip.mode,
@@ -655,6 +672,8 @@ impl TripManager {
         assert!(!trip.finished_at.is_some());
         trip.finished_at = Some(now);
         self.unfinished_trips -= 1;
+        self.total_delay += trip.total_blocked_time;
+        *self.finished_per_mode.entry(trip.mode).or_insert(0) += 1;
         self.events.push(Event::TripFinished {
             trip: trip.id,
             mode: trip.mode,
@@ -679,6 +698,7 @@ impl TripManager {
     ) {
         let trip = &mut self.trips[id.0];
         self.unfinished_trips -= 1;
+        *self.finished_per_mode.entry(trip.mode).or_insert(0) += 1;
         trip.aborted = true;
         self.events.push(Event::TripAborted(trip.id));
         let person = trip.person;
@@ -756,6 +776,37 @@ impl TripManager {
         self.person_finished_trip(now, person, parking, scheduler, map);
     }
 
+    // Like abort_trip, but the agent was deliberately removed from the map first
+    pub fn cancel_trip(
+        &mut self,
+        now: Time,
+        id: TripID,
+        agent: Option<AgentID>,
+        abandoned_vehicle: Option<Vehicle>,
+        parking: &mut ParkingSimState,
+        scheduler: &mut Scheduler,
+        map: &Map,
+    ) {
+        if let Some(a) = agent {
+            assert_eq!(self.active_trip_mode.remove(&a), Some(id));
+        }
+        self.abort_trip(now, id, abandoned_vehicle, parking, scheduler, map);
+    }
+
+    // Unlike aborting, nothing happened yet, so the trip doesn't count as finished and nobody moves.
+    pub fn delete_unstarted_trip(&mut self, id: TripID) {
+        self.unfinished_trips -= 1;
+        self.trips[id.0].aborted = true;
+    }
+
+    // Trips waiting for their person to finish something else aren't scheduled yet
+    pub fn is_trip_delayed(&self, id: TripID) -> bool {
+        self.people[self.trips[id.0].person.0]
+            .delayed_trips
+            .iter()
+            .any(|(t, _, _, _)| *t == id)
+    }
+
     pub fn active_agents(&self) -> Vec<AgentID> {
         self.active_trip_mode.keys().cloned().collect()
     }
@@ -811,7 +862,7 @@ impl TripManager {
         }
     }
 
-    pub fn num_trips(&self) -> (usize, usize, BTreeMap<TripMode, usize>) {
+    pub fn num_trips(&self) -> (usize, usize, BTreeMap<TripMode, (usize, usize)>) {
         let mut cnt = Counter::new();
         for a in self.active_trip_mode.keys() {
             // TODO This conflates bus riders and buses...
@@ -819,7 +870,15 @@ impl TripManager {
         }
         let per_mode = TripMode::all()
             .into_iter()
-            .map(|k| (k, cnt.get(k)))
+            .map(|k| {
+                (
+                    k,
+                    (
+                        cnt.get(k),
+                        self.finished_per_mode.get(&k).cloned().unwrap_or(0),
+                    ),
+                )
+            })
             .collect();
         (
             self.trips.len() - self.unfinished_trips,
@@ -861,6 +920,10 @@ impl TripManager {
         Some((t.finished_at? - t.departure, t.total_blocked_time))
     }
 
+    pub fn total_delay(&self) -> Duration {
+        self.total_delay
+    }
+
     pub fn bldg_to_people(&self, b: BuildingID) -> Vec<PersonID> {
         let mut people = Vec::new();
         for p in &self.people {